@@ -14,6 +14,28 @@ pub struct AdrToolConfig {
     pub adr_search_index: String,
     pub use_id_prefix: bool,
     pub id_prefix_width: usize,
+    #[serde(default = "default_id_strategy")]
+    pub id_strategy: String,
+    #[serde(default = "default_name_pattern")]
+    pub name_pattern: String,
+    #[serde(default = "default_lint_required_sections")]
+    pub lint_required_sections: String,
+    #[serde(default = "default_lint_forbidden_strings")]
+    pub lint_forbidden_strings: String,
+    #[serde(default = "default_lint_max_title_length")]
+    pub lint_max_title_length: usize,
+    #[serde(default)]
+    pub lint_allowed_tags: String,
+    #[serde(default)]
+    pub lint_disabled_rules: String,
+    #[serde(default)]
+    pub auto_commit: bool,
+    #[serde(default = "default_commit_message")]
+    pub commit_message: String,
+    #[serde(default)]
+    pub auto_branch: bool,
+    #[serde(default = "default_branch_pattern")]
+    pub branch_pattern: String,
 }
 
 pub const LOG_LEVEL: &str = "log_level";
@@ -24,6 +46,82 @@ pub const ADR_TEMPLATE_FILE: &str = "adr_template_file";
 pub const ADR_SEARCH_INDEX: &str = "adr_search_dir";
 pub const USE_ID_PREFIX: &str = "use_id_prefix";
 pub const ID_PREFIX_WIDTH: &str = "id_prefix_width";
+pub const ID_STRATEGY: &str = "id_strategy";
+pub const NAME_PATTERN: &str = "name_pattern";
+pub const LINT_REQUIRED_SECTIONS: &str = "lint_required_sections";
+pub const LINT_FORBIDDEN_STRINGS: &str = "lint_forbidden_strings";
+pub const LINT_MAX_TITLE_LENGTH: &str = "lint_max_title_length";
+pub const LINT_ALLOWED_TAGS: &str = "lint_allowed_tags";
+pub const LINT_DISABLED_RULES: &str = "lint_disabled_rules";
+pub const AUTO_COMMIT: &str = "auto_commit";
+pub const COMMIT_MESSAGE: &str = "commit_message";
+pub const AUTO_BRANCH: &str = "auto_branch";
+pub const BRANCH_PATTERN: &str = "branch_pattern";
+
+/// The names accepted by `set_config`
+pub const CONFIG_NAMES: [&str; 18] = [
+    ADR_ROOT_DIR,
+    ADR_SRC_DIR,
+    ADR_TEMPLATE_DIR,
+    ADR_TEMPLATE_FILE,
+    LOG_LEVEL,
+    USE_ID_PREFIX,
+    ID_PREFIX_WIDTH,
+    ID_STRATEGY,
+    NAME_PATTERN,
+    LINT_REQUIRED_SECTIONS,
+    LINT_FORBIDDEN_STRINGS,
+    LINT_MAX_TITLE_LENGTH,
+    LINT_ALLOWED_TAGS,
+    LINT_DISABLED_RULES,
+    AUTO_COMMIT,
+    COMMIT_MESSAGE,
+    AUTO_BRANCH,
+    BRANCH_PATTERN,
+];
+
+/// next id is the last id + 1
+pub const ID_STRATEGY_SEQUENTIAL: &str = "sequential";
+/// next id is the current timestamp (YYYYMMDDHHMMSS), so ADRs created on different branches do not collide
+pub const ID_STRATEGY_TIMESTAMP: &str = "timestamp";
+/// next id is the last id + 1, considering the ADRs of all the (local and remote) git branches
+pub const ID_STRATEGY_GIT: &str = "git";
+
+fn default_id_strategy() -> String {
+    ID_STRATEGY_SEQUENTIAL.to_string()
+}
+
+/// the file name of a new ADR - `{id}`, `{slug}` (built from the title) and `{date}` (YYYY-MM-DD) are replaced.
+/// e.g. `ADR-{id}-{slug}` or `{date}-{slug}.adoc`
+fn default_name_pattern() -> String {
+    "{id}-{slug}.adoc".to_string()
+}
+
+/// the (comma separated) sections each ADR must have
+fn default_lint_required_sections() -> String {
+    "Context and Problem Statement, Considered Options, Decision Outcome".to_string()
+}
+
+/// the (comma separated) placeholders of the template which should not be left in an ADR
+fn default_lint_forbidden_strings() -> String {
+    "bla bla, To be defined, @John Smith".to_string()
+}
+
+/// 0 means no limit
+fn default_lint_max_title_length() -> usize {
+    80
+}
+
+/// the message of the commits done by the lifecycle commands - `{id}`, `{title}`, `{slug}`, `{status}` and `{action}`
+/// (e.g. `superseded by ADR-57`) are replaced
+fn default_commit_message() -> String {
+    "ADR-{id}: {action}".to_string()
+}
+
+/// the branch created for a new ADR - `{id}`, `{title}`, `{slug}` and `{status}` are replaced
+fn default_branch_pattern() -> String {
+    "adr/{id}-{slug}".to_string()
+}
 
 impl ::std::default::Default for AdrToolConfig {
     fn default() -> Self {
@@ -36,6 +134,17 @@ impl ::std::default::Default for AdrToolConfig {
             log_level: 4, //info
             use_id_prefix: true,
             id_prefix_width: 6,
+            id_strategy: default_id_strategy(),
+            name_pattern: default_name_pattern(),
+            lint_required_sections: default_lint_required_sections(),
+            lint_forbidden_strings: default_lint_forbidden_strings(),
+            lint_max_title_length: default_lint_max_title_length(),
+            lint_allowed_tags: String::new(),
+            lint_disabled_rules: String::new(),
+            auto_commit: false,
+            commit_message: default_commit_message(),
+            auto_branch: false,
+            branch_pattern: default_branch_pattern(),
         }
     }
 }
@@ -75,7 +184,7 @@ pub fn init_from_name(config_name: &str) -> Result<()> {
 
     let from = "./templates/adr-template-v0.1.adoc";
     //check 'from' and 'to' are not the same to avoid file to be truncated
-    match fs::metadata(&from) {
+    match fs::metadata(from) {
         Ok(_) => {
             warn!(
                 get_logger(),
@@ -83,7 +192,7 @@ pub fn init_from_name(config_name: &str) -> Result<()> {
             );
         }
         Err(_) => {
-            match fs::copy(&from, format!("{0}/{1}", &path, cfg.adr_template_file)) {
+            match fs::copy(from, format!("{0}/{1}", &path, cfg.adr_template_file)) {
                 Err(why) => {
                     warn!(
                         get_logger(),
@@ -128,13 +237,24 @@ pub fn set_config_from_name(config: &str, name: &str, value: &str) -> Result<()>
 
         let new_cfg = AdrToolConfig {
             //adr_root_dir: String::from(value),
-            adr_src_dir: adr_src_dir,
+            adr_src_dir,
             adr_template_dir: format!("{}", adr_template_dir.display()),
             adr_template_file: cfg.adr_template_file,
             adr_search_index: format!("{}", adr_search_index.display()),
             log_level: cfg.log_level, //info
             use_id_prefix: cfg.use_id_prefix,
             id_prefix_width: cfg.id_prefix_width,
+            id_strategy: cfg.id_strategy,
+            name_pattern: cfg.name_pattern,
+            lint_required_sections: cfg.lint_required_sections,
+            lint_forbidden_strings: cfg.lint_forbidden_strings,
+            lint_max_title_length: cfg.lint_max_title_length,
+            lint_allowed_tags: cfg.lint_allowed_tags,
+            lint_disabled_rules: cfg.lint_disabled_rules,
+            auto_commit: cfg.auto_commit,
+            commit_message: cfg.commit_message,
+            auto_branch: cfg.auto_branch,
+            branch_pattern: cfg.branch_pattern,
         };
 
         confy::store(config, None, new_cfg).unwrap();
@@ -220,16 +340,123 @@ pub fn set_config_from_name(config: &str, name: &str, value: &str) -> Result<()>
         };
     }
 
+    if ID_STRATEGY == name {
+        let mut cfg: AdrToolConfig = get_config_from_name(config);
+        match value {
+            ID_STRATEGY_SEQUENTIAL | ID_STRATEGY_TIMESTAMP | ID_STRATEGY_GIT => {
+                cfg.id_strategy = String::from(value);
+                match confy::store(config, None, &cfg) {
+                    Err(why) => {
+                        error!(
+                            get_logger(),
+                            "Error while updating config file for property [{}] - [{}]", &name, &why
+                        );
+                    }
+                    Ok(_e) => {}
+                };
+            }
+            _ => {
+                error!(
+                    get_logger(),
+                    "Unknown value [{}] for property [{}] - should be one of [{}, {}, {}]",
+                    value,
+                    &name,
+                    ID_STRATEGY_SEQUENTIAL,
+                    ID_STRATEGY_TIMESTAMP,
+                    ID_STRATEGY_GIT
+                );
+            }
+        }
+    }
+
+    if NAME_PATTERN == name {
+        let mut cfg: AdrToolConfig = get_config_from_name(config);
+        match value.contains("{slug}") {
+            true => {
+                cfg.name_pattern = String::from(value);
+                match confy::store(config, None, &cfg) {
+                    Err(why) => {
+                        error!(
+                            get_logger(),
+                            "Error while updating config file for property [{}] - [{}]", &name, &why
+                        );
+                    }
+                    Ok(_e) => {}
+                };
+            }
+            false => {
+                error!(
+                    get_logger(),
+                    "Invalid value [{}] for property [{}] - should at least contain {{slug}}", value, &name
+                );
+            }
+        }
+    }
+
+    if [LINT_REQUIRED_SECTIONS, LINT_FORBIDDEN_STRINGS, LINT_MAX_TITLE_LENGTH, LINT_ALLOWED_TAGS, LINT_DISABLED_RULES]
+        .contains(&name)
+    {
+        let mut cfg: AdrToolConfig = get_config_from_name(config);
+        match name {
+            LINT_REQUIRED_SECTIONS => cfg.lint_required_sections = String::from(value),
+            LINT_FORBIDDEN_STRINGS => cfg.lint_forbidden_strings = String::from(value),
+            LINT_ALLOWED_TAGS => cfg.lint_allowed_tags = String::from(value),
+            LINT_DISABLED_RULES => cfg.lint_disabled_rules = String::from(value),
+            _ => match value.parse() {
+                Ok(val) => cfg.lint_max_title_length = val,
+                Err(_) => {
+                    error!(
+                        get_logger(),
+                        "Invalid value [{}] for property [{}] - should be a number (0 for no limit)", value, &name
+                    );
+                    return Ok(());
+                }
+            },
+        }
+        match confy::store(config, None, &cfg) {
+            Err(why) => {
+                error!(
+                    get_logger(),
+                    "Error while updating config file for property [{}] - [{}]", &name, &why
+                );
+            }
+            Ok(_e) => {}
+        };
+    }
+
+    if [AUTO_COMMIT, COMMIT_MESSAGE, AUTO_BRANCH, BRANCH_PATTERN].contains(&name) {
+        let mut cfg: AdrToolConfig = get_config_from_name(config);
+        match name {
+            COMMIT_MESSAGE => cfg.commit_message = String::from(value),
+            BRANCH_PATTERN => cfg.branch_pattern = String::from(value),
+            _ => match value.parse() {
+                Ok(val) if name == AUTO_COMMIT => cfg.auto_commit = val,
+                Ok(val) => cfg.auto_branch = val,
+                Err(_) => {
+                    error!(
+                        get_logger(),
+                        "Invalid value [{}] for property [{}] - should be true or false", value, &name
+                    );
+                    return Ok(());
+                }
+            },
+        }
+        match confy::store(config, None, &cfg) {
+            Err(why) => {
+                error!(
+                    get_logger(),
+                    "Error while updating config file for property [{}] - [{}]", &name, &why
+                );
+            }
+            Ok(_e) => {}
+        };
+    }
+
     Ok(())
 }
 
 pub fn get_config_from_name(config: &str) -> AdrToolConfig {
-    let cfg: AdrToolConfig = match confy::load(config, None) {
-        Err(_why) => AdrToolConfig::default(),
-        Ok(e) => e,
-    };
-
-    cfg
+    confy::load(config, None).unwrap_or_default()
 }
 
 #[cfg(test)]
@@ -275,7 +502,7 @@ mod tests {
         super::set_config_from_name(config, "use_id_prefix", "false").unwrap();
         let cfg = super::get_config_from_name(config);
 
-        assert_eq!(cfg.use_id_prefix, false);
+        assert!(!cfg.use_id_prefix);
 
         teardown(config);
     }
@@ -299,6 +526,91 @@ mod tests {
         teardown(config);
     }
 
+    #[test]
+    fn test_set_config_id_strategy() {
+        let uuid = Uuid::new_v4();
+        let name = format!("adrust-tools-4-tests-{}", uuid);
+        let config = name.as_str();
+
+        let cfg = super::get_config_from_name(config);
+        assert_eq!(cfg.id_strategy, super::ID_STRATEGY_SEQUENTIAL);
+
+        super::set_config_from_name(config, "id_strategy", "timestamp").unwrap();
+        let cfg = super::get_config_from_name(config);
+        assert_eq!(cfg.id_strategy, super::ID_STRATEGY_TIMESTAMP);
+
+        //unknown strategies are ignored
+        super::set_config_from_name(config, "id_strategy", "uuid").unwrap();
+        let cfg = super::get_config_from_name(config);
+        assert_eq!(cfg.id_strategy, super::ID_STRATEGY_TIMESTAMP);
+
+        teardown(config);
+    }
+
+    #[test]
+    fn test_set_config_name_pattern() {
+        let uuid = Uuid::new_v4();
+        let name = format!("adrust-tools-4-tests-{}", uuid);
+        let config = name.as_str();
+
+        let cfg = super::get_config_from_name(config);
+        assert_eq!(cfg.name_pattern, "{id}-{slug}.adoc");
+
+        super::set_config_from_name(config, "name_pattern", "ADR-{id}-{slug}").unwrap();
+        let cfg = super::get_config_from_name(config);
+        assert_eq!(cfg.name_pattern, "ADR-{id}-{slug}");
+
+        //a pattern without slug is ignored
+        super::set_config_from_name(config, "name_pattern", "{id}").unwrap();
+        let cfg = super::get_config_from_name(config);
+        assert_eq!(cfg.name_pattern, "ADR-{id}-{slug}");
+
+        teardown(config);
+    }
+
+    #[test]
+    fn test_set_config_lint_rules() {
+        let uuid = Uuid::new_v4();
+        let name = format!("adrust-tools-4-tests-{}", uuid);
+        let config = name.as_str();
+
+        let cfg = super::get_config_from_name(config);
+        assert_eq!(cfg.lint_max_title_length, 80);
+        assert!(cfg.lint_allowed_tags.is_empty());
+
+        super::set_config_from_name(config, "lint_allowed_tags", "security, data").unwrap();
+        super::set_config_from_name(config, "lint_max_title_length", "60").unwrap();
+        //not a number, ignored
+        super::set_config_from_name(config, "lint_max_title_length", "sixty").unwrap();
+        let cfg = super::get_config_from_name(config);
+        assert_eq!(cfg.lint_allowed_tags, "security, data");
+        assert_eq!(cfg.lint_max_title_length, 60);
+
+        teardown(config);
+    }
+
+    #[test]
+    fn test_set_config_git_integration() {
+        let uuid = Uuid::new_v4();
+        let name = format!("adrust-tools-4-tests-{}", uuid);
+        let config = name.as_str();
+
+        let cfg = super::get_config_from_name(config);
+        assert!(!cfg.auto_commit);
+        assert_eq!(cfg.commit_message, "ADR-{id}: {action}");
+
+        super::set_config_from_name(config, "auto_commit", "true").unwrap();
+        //not a boolean, ignored
+        super::set_config_from_name(config, "auto_branch", "yes").unwrap();
+        super::set_config_from_name(config, "branch_pattern", "decision/{slug}").unwrap();
+        let cfg = super::get_config_from_name(config);
+        assert!(cfg.auto_commit);
+        assert!(!cfg.auto_branch);
+        assert_eq!(cfg.branch_pattern, "decision/{slug}");
+
+        teardown(config);
+    }
+
     fn teardown(name: &str) {
         println!("Want to delete folders [{:?}]", name);
         //delete confy files
@@ -0,0 +1,722 @@
+extern crate slog;
+extern crate slog_term;
+use slog::*;
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self};
+use std::io::{self};
+use std::path::{Path, PathBuf};
+
+extern crate regex;
+use regex::{Captures, Regex};
+
+extern crate adr_config;
+use adr_config::config::AdrToolConfig;
+
+use crate::adr_graph::{self, Node};
+use crate::adr_repo::{list_all_adr, slugify, Adr, Status};
+use crate::adr_template;
+
+fn get_logger() -> slog::Logger {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+
+    let decorator = slog_term::TermDecorator::new().build();
+    let drain = slog_term::FullFormat::new(decorator).build().fuse();
+    let drain = slog_async::Async::new(drain).build().fuse();
+    let drain = slog::LevelFilter::new(
+        drain,
+        Level::from_usize(cfg.log_level).unwrap_or(Level::Debug),
+    )
+    .fuse();
+
+    slog::Logger::root(drain, o!())
+}
+
+/// The theme files shipped with adrust, used if they are not in `adr_template_dir`
+const BUILTIN_THEMES: [(&str, &str); 3] = [
+    ("theme.css", include_str!("../../../templates/theme.css")),
+    ("theme_dark.css", include_str!("../../../templates/theme_dark.css")),
+    ("docinfo.html", include_str!("../../../templates/docinfo.html")),
+];
+
+const STATE_DIAGRAM: &str = include_str!("../../../docs/state-diagram.mermaid");
+
+const SITE_CSS: &str = "
+#header nav a { margin-right: 1em; }
+.filters { margin: 1em 0; }
+.filters select { margin-right: 1em; }
+.tags { text-decoration: none; margin-right: .3em; }
+ul.timeline { list-style: none; border-left: 2px solid #aaa6a6; padding-left: 1em; }
+ul.timeline li { margin-bottom: .5em; }
+.related td, .related th { padding: .2em .8em .2em 0; text-align: left; }
+";
+
+const FILTER_JS: &str = "
+function filterAdrs() {
+    var status = document.getElementById('status').value;
+    var tag = document.getElementById('tag').value;
+    document.querySelectorAll('#adrs tbody tr').forEach(function (row) {
+        var visible = (!status || row.dataset.status === status)
+            && (!tag || row.dataset.tags.split('|').indexOf(tag) >= 0);
+        row.style.display = visible ? '' : 'none';
+    });
+}
+//e.g. index.html?tag=security
+var params = new URLSearchParams(window.location.search);
+['status', 'tag'].forEach(function (name) {
+    if (params.get(name)) { document.getElementById(name).value = params.get(name); }
+});
+filterAdrs();
+";
+
+/// The path of a page, relative to the site root, e.g. `adr/data/000002-use-postgres.html`
+fn page_path(file_path: &str) -> String {
+    let path = Path::new(file_path).with_extension("html");
+    format!("adr/{}", path.to_string_lossy().replace('\\', "/"))
+}
+
+/// The prefix to go back to the site root from `path`, e.g. `../../` for `adr/data/000002-use-postgres.html`
+fn root_of(path: &str) -> String {
+    "../".repeat(path.matches('/').count())
+}
+
+fn escape_html(val: &str) -> String {
+    val.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Renders the inline markup supported by the ADRs: attribute references, roles (e.g. the status labels and tags),
+/// bold, italic, monospace, images, links and cross references. `links` maps the ADR file names to their pages.
+fn render_inline(
+    val: &str,
+    attributes: &HashMap<String, String>,
+    links: &HashMap<String, String>,
+    root: &str,
+) -> String {
+    lazy_static! {
+        static ref RE_ATTRIBUTE: Regex = Regex::new(r"\{([\w-]+)\}").unwrap();
+        static ref RE_PASS: Regex = Regex::new(r"^pass:(?:q|quotes)?\[(.*)\]$").unwrap();
+        static ref RE_CODE: Regex = Regex::new(r"`([^`]+)`").unwrap();
+        static ref RE_TAG: Regex = Regex::new(r"\[tags\]#([^#]+)#").unwrap();
+        static ref RE_ROLE: Regex = Regex::new(r"\[\.([\w.-]+)\]#([^#]+)#").unwrap();
+        static ref RE_FILE: Regex = Regex::new(r"(^|\s)([\w.-]+\.adoc)\b").unwrap();
+        static ref RE_IMAGE: Regex = Regex::new(r"image::?([^\s\[]+)\[([^\]]*)\]").unwrap();
+        static ref RE_XREF: Regex = Regex::new(r"xref:([^\s\[#]+)(#[\w-]*)?\[([^\]]*)\]").unwrap();
+        static ref RE_LINK: Regex =
+            Regex::new(r"link:([^\s\[\]]+)\[([^\]]*)\]|((?:https?://|mailto:)[^\s\[\]]+)\[([^\]]*)\]").unwrap();
+        static ref RE_CODE_PLACEHOLDER: Regex = Regex::new("\u{1}([0-9]+)\u{1}").unwrap();
+        static ref RE_BOLD: Regex = Regex::new(r"(^|[\s(>|])\*([^*\s][^*\n]*?)\*($|[\s.,;:!?)<])").unwrap();
+        static ref RE_ITALIC: Regex = Regex::new(r"(^|[\s(>|])_([^_\s][^_\n]*?)_($|[\s.,;:!?)<])").unwrap();
+    }
+
+    let val = RE_ATTRIBUTE.replace_all(val, |cap: &Captures| match attributes.get(&cap[1]) {
+        Some(value) => match RE_PASS.captures(value) {
+            Some(pass) => pass[1].to_string(),
+            None => value.to_string(),
+        },
+        None => cap[0].to_string(),
+    });
+    let val = escape_html(&val);
+
+    //the monospace texts are rendered as is, e.g. `args[0]` is not a link
+    let mut code = Vec::new();
+    let val = RE_CODE.replace_all(&val, |cap: &Captures| {
+        code.push(format!("<code>{}</code>", &cap[1]));
+        format!("\u{1}{}\u{1}", code.len() - 1)
+    });
+    let val = RE_TAG.replace_all(&val, |cap: &Captures| {
+        format!(
+            "<a class=\"tags\" href=\"{}tags/{}.html\">{}</a>",
+            root,
+            slugify(cap[1].trim()),
+            cap[1].trim()
+        )
+    });
+    let val = RE_ROLE.replace_all(&val, |cap: &Captures| {
+        format!("<span class=\"{}\">{}</span>", cap[1].replace('.', " "), &cap[2])
+    });
+    //file names after the status, e.g. {superseded} 000003-use-kafka.adoc
+    let val = RE_FILE.replace_all(&val, |cap: &Captures| match links.get(&cap[2]) {
+        Some(href) => format!("{}<a href=\"{}{}\">{}</a>", &cap[1], root, href, &cap[2]),
+        None => cap[0].to_string(),
+    });
+    let val = RE_IMAGE.replace_all(&val, |cap: &Captures| match is_safe_url(&cap[1]) {
+        true => format!("<img src=\"{}\" alt=\"{}\">", &cap[1], &cap[2]),
+        false => cap[2].to_string(),
+    });
+    let val = RE_XREF.replace_all(&val, |cap: &Captures| {
+        let name = Path::new(&cap[1]).file_name().unwrap_or_default().to_string_lossy().to_string();
+        let href = match links.get(&name) {
+            Some(href) => format!("{}{}", root, href),
+            None => cap[1].replace(".adoc", ".html"),
+        };
+        let text = match cap[3].is_empty() {
+            true => cap[1].to_string(),
+            false => cap[3].to_string(),
+        };
+        match is_safe_url(&href) {
+            true => format!("<a href=\"{}{}\">{}</a>", href, cap.get(2).map(|a| a.as_str()).unwrap_or(""), text),
+            false => text,
+        }
+    });
+    let val = RE_LINK.replace_all(&val, |cap: &Captures| {
+        let (target, text) = match cap.get(1) {
+            Some(target) => (target.as_str(), &cap[2]),
+            None => (&cap[3], &cap[4]),
+        };
+        let text = match text.is_empty() {
+            true => target,
+            false => text,
+        };
+        match is_safe_url(target) {
+            true => format!("<a href=\"{}\">{}</a>", target, text),
+            false => text.to_string(),
+        }
+    });
+    let val = RE_BOLD.replace_all(&val, "$1<strong>$2</strong>$3");
+    let val = RE_ITALIC.replace_all(&val, "$1<em>$2</em>$3");
+    let val = RE_CODE_PLACEHOLDER.replace_all(&val, |cap: &Captures| {
+        code[cap[1].parse::<usize>().unwrap()].to_string()
+    });
+
+    val.to_string()
+}
+
+/// Only the relative URLs and the `http`, `https` and `mailto` ones are rendered, e.g. not `javascript:`
+fn is_safe_url(url: &str) -> bool {
+    lazy_static! {
+        static ref RE_SCHEME: Regex = Regex::new(r"^([a-zA-Z][a-zA-Z0-9+.-]*):").unwrap();
+    }
+
+    match RE_SCHEME.captures(url) {
+        Some(cap) => ["http", "https", "mailto"].contains(&cap[1].to_lowercase().as_str()),
+        None => true,
+    }
+}
+
+/// Renders the rows of a `|===` table. The number of columns comes from the `cols` attribute, or the first row.
+fn render_table(
+    lines: &[&str],
+    block_attributes: &str,
+    attributes: &HashMap<String, String>,
+    links: &HashMap<String, String>,
+    root: &str,
+) -> String {
+    lazy_static! {
+        static ref RE_COLS: Regex = Regex::new(r#"cols="([^"]*)""#).unwrap();
+    }
+
+    let columns = match RE_COLS.captures(block_attributes) {
+        Some(cap) => cap[1].split(',').count(),
+        None => lines
+            .iter()
+            .find(|l| !l.trim().is_empty())
+            .map(|l| l.split('|').skip(1).count())
+            .unwrap_or(1),
+    }
+    .max(1);
+    let has_header = block_attributes.contains("header") || (lines.len() > 1 && lines[1].trim().is_empty());
+
+    let cells: Vec<String> = lines
+        .join("\n")
+        .split('|')
+        .skip(1)
+        .map(|c| render_inline(c.trim(), attributes, links, root))
+        .collect();
+
+    let mut html = String::from("<table class=\"tableblock frame-all grid-all stretch\">\n");
+    for (index, row) in cells.chunks(columns).enumerate() {
+        let tag = match has_header && index == 0 {
+            true => "th",
+            false => "td",
+        };
+        html.push_str("<tr>");
+        for cell in row {
+            html.push_str(&format!("<{tag} class=\"tableblock\">{}</{tag}>", cell.replace('\n', "<br>")));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</table>\n");
+
+    html
+}
+
+/// Renders the AsciiDoc subset used by the Decision Records (attributes, headings, paragraphs, lists, tables,
+/// listing blocks and the inline markup of `render_inline`) as HTML. The document attributes are added to `attributes`.
+pub fn asciidoc_to_html(
+    content: &str,
+    attributes: &mut HashMap<String, String>,
+    links: &HashMap<String, String>,
+    root: &str,
+) -> String {
+    lazy_static! {
+        static ref RE_ATTRIBUTE_ENTRY: Regex = Regex::new(r"^:([\w-]+):\s*(.*)$").unwrap();
+        static ref RE_HEADING: Regex = Regex::new(r"^(=+)\s+(.+)$").unwrap();
+        static ref RE_BLOCK_ATTRIBUTES: Regex = Regex::new(r"^\[(.*)\]$").unwrap();
+        static ref RE_LIST_ITEM: Regex = Regex::new(r"^\s*(\*+|-|\.+)\s+(.*)$").unwrap();
+        static ref RE_SOURCE: Regex = Regex::new(r"^source,\s*([\w+-]+)").unwrap();
+    }
+
+    let mut html = String::new();
+    let mut paragraph: Vec<String> = Vec::new();
+    //the open lists, e.g. ["ul", "ol"]
+    let mut lists: Vec<&str> = Vec::new();
+    let mut block_attributes = String::new();
+
+    let flush_paragraph = |html: &mut String, paragraph: &mut Vec<String>| {
+        if !paragraph.is_empty() {
+            html.push_str(&format!("<div class=\"paragraph\"><p>{}</p></div>\n", paragraph.join("\n")));
+            paragraph.clear();
+        }
+    };
+    let close_lists = |html: &mut String, lists: &mut Vec<&str>, depth: usize| {
+        while lists.len() > depth {
+            html.push_str(&format!("</li></{}>\n", lists.pop().unwrap()));
+        }
+    };
+
+    let lines: Vec<&str> = content.lines().collect();
+    let mut index = 0;
+    while index < lines.len() {
+        let line = lines[index];
+        let trimmed = line.trim();
+        index += 1;
+
+        //delimited blocks
+        if ["----", "....", "////", "|==="].contains(&trimmed) {
+            flush_paragraph(&mut html, &mut paragraph);
+            close_lists(&mut html, &mut lists, 0);
+            let end = lines[index..]
+                .iter()
+                .position(|l| l.trim() == trimmed)
+                .map(|end| index + end)
+                .unwrap_or(lines.len());
+            let block = &lines[index..end];
+            match trimmed {
+                "////" => {}
+                "|===" => html.push_str(&render_table(block, &block_attributes, attributes, links, root)),
+                _ => {
+                    let language = RE_SOURCE
+                        .captures(&block_attributes)
+                        .map(|cap| format!(" class=\"language-{}\"", &cap[1]))
+                        .unwrap_or_default();
+                    html.push_str(&format!(
+                        "<div class=\"listingblock\"><div class=\"content\"><pre class=\"highlight\"><code{}>{}</code></pre></div></div>\n",
+                        language,
+                        escape_html(&block.join("\n"))
+                    ));
+                }
+            }
+            block_attributes.clear();
+            index = end + 1;
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            flush_paragraph(&mut html, &mut paragraph);
+            continue;
+        }
+        if trimmed.starts_with("//") || trimmed == "<<<" {
+            continue;
+        }
+        if let Some(cap) = RE_ATTRIBUTE_ENTRY.captures(line) {
+            attributes.insert(cap[1].to_string(), cap[2].trim().to_string());
+            continue;
+        }
+        if paragraph.is_empty() {
+            if let Some(cap) = RE_BLOCK_ATTRIBUTES.captures(trimmed) {
+                //not an inline role, e.g. [tags]#security#
+                if !trimmed.contains('#') {
+                    block_attributes = cap[1].to_string();
+                    continue;
+                }
+            }
+        }
+        if let Some(cap) = RE_HEADING.captures(line) {
+            flush_paragraph(&mut html, &mut paragraph);
+            close_lists(&mut html, &mut lists, 0);
+            let level = cap[1].len().min(6);
+            let title = render_inline(&cap[2], attributes, links, root);
+            html.push_str(&format!("<h{level} id=\"{}\">{}</h{level}>\n", slugify(&cap[2]), title));
+            block_attributes.clear();
+            continue;
+        }
+        if trimmed == "'''" {
+            flush_paragraph(&mut html, &mut paragraph);
+            html.push_str("<hr>\n");
+            continue;
+        }
+        if let Some(cap) = RE_LIST_ITEM.captures(line) {
+            flush_paragraph(&mut html, &mut paragraph);
+            let (kind, depth) = match cap[1].starts_with('.') {
+                true => ("ol", cap[1].len()),
+                false => ("ul", cap[1].len()),
+            };
+            match lists.len().cmp(&depth) {
+                std::cmp::Ordering::Less => {
+                    while lists.len() < depth {
+                        html.push_str(&format!("<{}>\n<li>", kind));
+                        lists.push(kind);
+                    }
+                }
+                _ => {
+                    close_lists(&mut html, &mut lists, depth);
+                    html.push_str("</li>\n<li>");
+                }
+            }
+            html.push_str(&render_inline(&cap[2], attributes, links, root));
+            continue;
+        }
+
+        //continuation of a list item, or a paragraph
+        let text = render_inline(trimmed, attributes, links, root);
+        match lists.is_empty() || !paragraph.is_empty() {
+            true => paragraph.push(text),
+            false => {
+                html.push(' ');
+                html.push_str(&text);
+            }
+        }
+    }
+    flush_paragraph(&mut html, &mut paragraph);
+    close_lists(&mut html, &mut lists, 0);
+
+    html
+}
+
+/// The theme file `name`, from `adr_template_dir` if it exists there, else the one shipped with adrust
+pub fn get_theme(cfg: &AdrToolConfig, name: &str) -> io::Result<String> {
+    let path = Path::new(&cfg.adr_template_dir).join(name);
+    match path.is_file() {
+        true => fs::read_to_string(path),
+        false => Ok(BUILTIN_THEMES
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, content)| content.to_string())
+            .unwrap_or_default()),
+    }
+}
+
+struct Site {
+    docinfo: String,
+    /// the status labels, e.g. `{wip}`
+    attributes: HashMap<String, String>,
+    files: Vec<PathBuf>,
+}
+
+impl Site {
+    fn status_label(&self, status: Status) -> String {
+        match status {
+            Status::NONE => String::from("unknown"),
+            _ => render_inline(&format!("{{{}}}", status.as_str()), &self.attributes, &HashMap::new(), ""),
+        }
+    }
+
+    fn write_page(&mut self, out_dir: &Path, path: &str, title: &str, body: &str) -> io::Result<()> {
+        let root = root_of(path);
+        let page = format!(
+            "<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<meta name=\"viewport\" content=\"width=device-width, initial-scale=1.0\">
+<title>{title}</title>
+<link rel=\"stylesheet\" href=\"{root}css/theme.css\" media=\"not all and (prefers-color-scheme: dark)\">
+<link rel=\"stylesheet\" href=\"{root}css/theme_dark.css\" media=\"(prefers-color-scheme: dark)\">
+<style>{css}</style>
+{docinfo}
+</head>
+<body class=\"article\">
+<div id=\"header\">
+<nav><a href=\"{root}index.html\">Decision Records</a><a href=\"{root}timeline.html\">Timeline</a><a href=\"{root}tags/index.html\">Tags</a></nav>
+</div>
+<div id=\"content\">
+{body}
+</div>
+<div id=\"footer\"><div id=\"footer-text\">Generated by adrust</div></div>
+</body>
+</html>
+",
+            title = escape_html(title),
+            root = root,
+            css = SITE_CSS,
+            docinfo = self.docinfo,
+            body = body
+        );
+
+        let target = out_dir.join(path);
+        fs::create_dir_all(target.parent().unwrap())?;
+        fs::write(&target, page)?;
+        debug!(get_logger(), "Page [{}] written", target.display());
+        self.files.push(target);
+
+        Ok(())
+    }
+
+    /// A table of Decision Records, with the data used by the index filters
+    fn adr_table(&self, adrs: &[&Adr], root: &str) -> String {
+        let mut html = String::from(
+            "<table id=\"adrs\" class=\"tableblock frame-all grid-all stretch\">\n<thead><tr><th>ID</th><th>Title</th><th>Status</th><th>Date</th><th>Tags</th></tr></thead>\n<tbody>\n",
+        );
+        for adr in adrs {
+            let tags: Vec<&str> = adr.tags_array.iter().map(|t| t.trim()).collect();
+            let tag_links: Vec<String> = tags
+                .iter()
+                .map(|tag| format!("<a class=\"tags\" href=\"{}tags/{}.html\">{}</a>", root, slugify(tag), escape_html(tag)))
+                .collect();
+            html.push_str(&format!(
+                "<tr data-status=\"{}\" data-tags=\"{}\"><td>{}</td><td><a href=\"{}{}\">{}</a></td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                adr.status.as_str(),
+                escape_html(&tags.join("|")),
+                adr.file_id,
+                root,
+                page_path(&adr.file_path),
+                escape_html(&adr.title),
+                self.status_label(adr.status),
+                adr.date,
+                tag_links.join(" ")
+            ));
+        }
+        html.push_str("</tbody>\n</table>\n");
+
+        html
+    }
+}
+
+/// Generates a static site in `out_dir` from the Decision Records of `adr_src_dir`: an index page (filterable by status and tag),
+/// one page per Decision Record, tag pages and a timeline, using the themes of `adr_template_dir` (or the shipped ones).
+/// Returns the written files.
+pub fn export_site(cfg: &AdrToolConfig, out_dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let adrs = list_all_adr(Path::new(&cfg.adr_src_dir))?;
+
+    let mut attributes = HashMap::new();
+    asciidoc_to_html(&adr_template::get_status_labels(), &mut attributes, &HashMap::new(), "");
+    let mut site = Site {
+        docinfo: get_theme(cfg, "docinfo.html")?,
+        attributes,
+        files: Vec::new(),
+    };
+
+    fs::create_dir_all(out_dir.join("css"))?;
+    for name in ["theme.css", "theme_dark.css"] {
+        let target = out_dir.join("css").join(name);
+        fs::write(&target, get_theme(cfg, name)?)?;
+        site.files.push(target);
+    }
+
+    //the pages of the file names, to link the Decision Records
+    let links: HashMap<String, String> = adrs.iter().map(|adr| (adr.file_name.clone(), page_path(&adr.file_path))).collect();
+    let graph = adr_graph::build_graph(&adrs);
+
+    //one page per Decision Record
+    for adr in adrs.iter() {
+        let path = page_path(&adr.file_path);
+        let root = root_of(&path);
+        let mut attributes = site.attributes.clone();
+        let mut body = asciidoc_to_html(&adr.content, &mut attributes, &links, &root);
+
+        //the links to and from the other Decision Records
+        let related: Vec<(Status, &Node)> = graph.get_related(&adr.file_path.replace('\\', "/"));
+        if !related.is_empty() {
+            body.push_str("<h3 id=\"related-decisions\">Related Decisions</h3>\n<table class=\"related\">\n");
+            for (status, other) in related {
+                body.push_str(&format!(
+                    "<tr><td>{}</td><td><a href=\"{}{}\">{}</a></td><td>{}</td></tr>\n",
+                    site.status_label(status),
+                    root,
+                    page_path(&other.path),
+                    escape_html(&other.title),
+                    site.status_label(other.status)
+                ));
+            }
+            body.push_str("</table>\n");
+        }
+        site.write_page(out_dir, &path, &adr.title, &body)?;
+    }
+
+    //the index, filterable by status and tag
+    let mut tags: BTreeMap<String, Vec<&Adr>> = BTreeMap::new();
+    for adr in adrs.iter() {
+        for tag in adr.tags_array.iter() {
+            tags.entry(tag.trim().to_string()).or_default().push(adr);
+        }
+    }
+    let mut statuses: Vec<&str> = adrs.iter().map(|adr| adr.status.as_str()).collect();
+    statuses.sort();
+    statuses.dedup();
+
+    let mut body = String::from("<h1>Decision Records</h1>\n<div class=\"filters\">\n<label for=\"status\">Status</label> <select id=\"status\" onchange=\"filterAdrs()\"><option value=\"\">all</option>");
+    for status in statuses {
+        body.push_str(&format!("<option>{}</option>", status));
+    }
+    body.push_str("</select>\n<label for=\"tag\">Tag</label> <select id=\"tag\" onchange=\"filterAdrs()\"><option value=\"\">all</option>");
+    for tag in tags.keys() {
+        body.push_str(&format!("<option>{}</option>", escape_html(tag)));
+    }
+    body.push_str("</select>\n</div>\n");
+    body.push_str(&site.adr_table(&adrs.iter().collect::<Vec<&Adr>>(), ""));
+    body.push_str(&format!(
+        "<details><summary>Status lifecycle</summary>\n{}\n</details>\n<script src=\"https://cdn.jsdelivr.net/npm/mermaid/dist/mermaid.min.js\"></script>\n<script>{}</script>\n",
+        STATE_DIAGRAM, FILTER_JS
+    ));
+    site.write_page(out_dir, "index.html", "Decision Records", &body)?;
+
+    //the tag pages
+    let mut body = String::from("<h1>Tags</h1>\n<div class=\"ulist\"><ul>\n");
+    for (tag, tagged) in tags.iter() {
+        body.push_str(&format!(
+            "<li><a class=\"tags\" href=\"{}.html\">{}</a> ({})</li>\n",
+            slugify(tag),
+            escape_html(tag),
+            tagged.len()
+        ));
+    }
+    body.push_str("</ul></div>\n");
+    site.write_page(out_dir, "tags/index.html", "Tags", &body)?;
+    for (tag, tagged) in tags.iter() {
+        let body = format!("<h1>Tag {}</h1>\n{}", escape_html(tag), site.adr_table(tagged, "../"));
+        site.write_page(out_dir, &format!("tags/{}.html", slugify(tag)), tag, &body)?;
+    }
+
+    //the timeline, the most recent first
+    let mut sorted: Vec<&Adr> = adrs.iter().collect();
+    sorted.sort_by(|a, b| b.date.cmp(&a.date).then(b.file_id.cmp(&a.file_id)));
+    let mut body = String::from("<h1>Timeline</h1>\n");
+    let mut month = String::new();
+    for adr in sorted {
+        let current = adr.date.chars().take(7).collect::<String>();
+        if current != month {
+            if !month.is_empty() {
+                body.push_str("</ul>\n");
+            }
+            body.push_str(&format!("<h2>{}</h2>\n<ul class=\"timeline\">\n", current));
+            month = current;
+        }
+        let references: Vec<String> = graph
+            .get_related(&adr.file_path.replace('\\', "/"))
+            .into_iter()
+            .map(|(status, other)| {
+                format!("{} <a href=\"{}\">{}</a>", site.status_label(status), page_path(&other.path), escape_html(&other.title))
+            })
+            .collect();
+        body.push_str(&format!(
+            "<li>{} {} <a href=\"{}\">{}</a> {}</li>\n",
+            adr.date,
+            site.status_label(adr.status),
+            page_path(&adr.file_path),
+            escape_html(&adr.title),
+            references.join(" ")
+        ));
+    }
+    if !month.is_empty() {
+        body.push_str("</ul>\n");
+    }
+    site.write_page(out_dir, "timeline.html", "Timeline", &body)?;
+
+    info!(get_logger(), "Site exported in [{}]", out_dir.display());
+
+    Ok(site.files)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::fs;
+
+    use adr_config::config::AdrToolConfig;
+    use tempdir::TempDir;
+
+    #[test]
+    fn test_asciidoc_to_html() {
+        let mut attributes = HashMap::new();
+        let mut links = HashMap::new();
+        links.insert(String::from("000002-b.adoc"), String::from("adr/000002-b.html"));
+
+        let html = super::asciidoc_to_html(
+            ":decided: pass:q[[.label.decided]#Decided#]\n\n== My <Decision>\n\n*Status:* {decided} 000002-b.adoc\n\n* first _one_\n** nested\n\n[source,java]\n----\nclass A<T> {}\n----\n\n[cols=\"1h,4\",options=\"header\",]\n|===\n|role |people\n|_Deciders_ |@John\n|===\n\n[tags]#Security#",
+            &mut attributes,
+            &links,
+            "../",
+        );
+        assert_eq!(
+            html,
+            "<h2 id=\"my-decision\">My &lt;Decision&gt;</h2>
+<div class=\"paragraph\"><p><strong>Status:</strong> <span class=\"label decided\">Decided</span> <a href=\"../adr/000002-b.html\">000002-b.adoc</a></p></div>
+<ul>
+<li>first <em>one</em><ul>
+<li>nested</li></ul>
+</li></ul>
+<div class=\"listingblock\"><div class=\"content\"><pre class=\"highlight\"><code class=\"language-java\">class A&lt;T&gt; {}</code></pre></div></div>
+<table class=\"tableblock frame-all grid-all stretch\">
+<tr><th class=\"tableblock\">role</th><th class=\"tableblock\">people</th></tr>
+<tr><td class=\"tableblock\"><em>Deciders</em></td><td class=\"tableblock\">@John</td></tr>
+</table>
+<div class=\"paragraph\"><p><a class=\"tags\" href=\"../tags/security.html\">Security</a></p></div>
+"
+        );
+        assert!(attributes.contains_key("decided"));
+    }
+
+    #[test]
+    fn test_render_links() {
+        let render = |val: &str| super::render_inline(val, &HashMap::new(), &HashMap::new(), "");
+
+        assert_eq!(render("`a[0]` and map[key]"), "<code>a[0]</code> and map[key]");
+        assert_eq!(render("`link:https://adr.github.io[ADR]`"), "<code>link:https://adr.github.io[ADR]</code>");
+        assert_eq!(render("link:javascript:alert(1)[x]"), "x");
+        assert_eq!(render("link:JavaScript:alert(1)[x]"), "x");
+        assert_eq!(render("xref:javascript:alert(1)[x]"), "x");
+        assert_eq!(render("image:data:image/svg+xml,x[logo]"), "logo");
+        assert_eq!(
+            render("see link:https://adr.github.io[ADR], https://adr.github.io[the site] or mailto:me@adr.io[]"),
+            "see <a href=\"https://adr.github.io\">ADR</a>, <a href=\"https://adr.github.io\">the site</a> or <a href=\"mailto:me@adr.io\">mailto:me@adr.io</a>"
+        );
+        assert_eq!(render("link:other.html[*other*]"), "<a href=\"other.html\"><strong>other</strong></a>");
+        assert_eq!(render("image:logo.png[logo]"), "<img src=\"logo.png\" alt=\"logo\">");
+    }
+
+    #[test]
+    fn test_export_site() {
+        let src = TempDir::new("my_src_folder").unwrap();
+        fs::create_dir_all(src.path().join("data")).unwrap();
+        fs::write(
+            src.path().join("000001-use-postgres.adoc"),
+            ":adr-status: superseded\n:adr-date: 2024-01-02\n\n== Use Postgres\n\n*Status:* {superseded} 000002-use-kafka.adoc\n\n[tags]#storage#",
+        )
+        .unwrap();
+        fs::write(
+            src.path().join("data/000002-use-kafka.adoc"),
+            ":adr-status: supersedes\n:adr-date: 2024-03-04\n\n== Use Kafka\n\n*Status:* {supersedes} 000001-use-postgres.adoc\n\n[tags]#storage# [tags]#Data Streaming#",
+        )
+        .unwrap();
+
+        let out = TempDir::new("my_site_folder").unwrap();
+        let cfg = AdrToolConfig {
+            adr_src_dir: format!("{}", src.path().display()),
+            adr_template_dir: format!("{}", src.path().join("templates").display()),
+            ..Default::default()
+        };
+        let files = super::export_site(&cfg, out.path()).unwrap();
+        //2 themes, 2 decisions, index, 3 tag pages, timeline
+        assert_eq!(9, files.len());
+
+        let index = fs::read_to_string(out.path().join("index.html")).unwrap();
+        assert!(index.contains("<tr data-status=\"supersedes\" data-tags=\"storage|Data Streaming\"><td>2</td><td><a href=\"adr/data/000002-use-kafka.html\">Use Kafka</a>"));
+        assert!(index.contains("<option>Data Streaming</option>"));
+
+        let kafka = fs::read_to_string(out.path().join("adr/data/000002-use-kafka.html")).unwrap();
+        assert!(kafka.contains("href=\"../../css/theme.css\""));
+        assert!(kafka.contains("<a href=\"../../adr/000001-use-postgres.html\">000001-use-postgres.adoc</a>"));
+        assert!(kafka.contains("<a class=\"tags\" href=\"../../tags/data-streaming.html\">Data Streaming</a>"));
+        assert!(kafka.contains("Related Decisions"));
+
+        let tag = fs::read_to_string(out.path().join("tags/storage.html")).unwrap();
+        assert!(tag.contains("href=\"../adr/000001-use-postgres.html\""));
+
+        let timeline = fs::read_to_string(out.path().join("timeline.html")).unwrap();
+        assert!(timeline.find("Use Kafka").unwrap() < timeline.find("Use Postgres").unwrap());
+        assert!(timeline.contains("<h2>2024-03</h2>"));
+    }
+}
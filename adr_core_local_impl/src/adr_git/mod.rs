@@ -0,0 +1,871 @@
+extern crate slog;
+extern crate slog_term;
+use slog::*;
+
+use std::fs::{self};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+
+extern crate adr_config;
+use adr_config::config::AdrToolConfig;
+
+use crate::adr_repo::{Adr, Status};
+
+fn get_logger() -> slog::Logger {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+
+    let decorator = slog_term::TermDecorator::new().build();
+    let drain = slog_term::FullFormat::new(decorator).build().fuse();
+    let drain = slog_async::Async::new(drain).build().fuse();
+    let drain = slog::LevelFilter::new(
+        drain,
+        Level::from_usize(cfg.log_level).unwrap_or(Level::Debug),
+    )
+    .fuse();
+
+    slog::Logger::root(drain, o!())
+}
+
+/// Identifies the hooks installed by adrust, so that other hooks are never overwritten nor removed
+const HOOK_MARKER: &str = "# adrust pre-commit hook";
+
+/// Runs the local `git` binary in `dir`, returns its standard output
+pub fn git(dir: &Path, args: &[&str]) -> io::Result<String> {
+    let output = run_git(dir, args, None)?;
+
+    Ok(String::from_utf8_lossy(&output).to_string())
+}
+
+/// Runs the local `git` binary in `dir`, writing `input` to its standard input, returns its raw standard output
+fn run_git(dir: &Path, args: &[&str], input: Option<Vec<u8>>) -> io::Result<Vec<u8>> {
+    let mut child = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .stdin(match input {
+            Some(_) => Stdio::piped(),
+            None => Stdio::null(),
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    //written from another thread, git may fill its standard output before reading all its input
+    let writer = match (input, child.stdin.take()) {
+        (Some(input), Some(mut stdin)) => Some(thread::spawn(move || stdin.write_all(&input))),
+        _ => None,
+    };
+    let output = child.wait_with_output()?;
+    if let Some(writer) = writer {
+        writer
+            .join()
+            .map_err(|_| io::Error::other("unable to write the git standard input"))??;
+    }
+
+    match output.status.success() {
+        true => Ok(output.stdout),
+        false => {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            debug!(get_logger(), "git {:?} failed in [{}] - [{}]", args, dir.display(), stderr);
+            Err(io::Error::other(format!("git {} failed - {}", args.join(" "), stderr)))
+        }
+    }
+}
+
+/// The root directory of the git repository containing `dir`
+pub fn get_repository_root(dir: &Path) -> io::Result<PathBuf> {
+    let root = git(dir, &["rev-parse", "--show-toplevel"]).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("[{}] is not in a git repository", dir.display()),
+        )
+    })?;
+
+    Ok(PathBuf::from(root.trim()))
+}
+
+fn get_hook_path(dir: &Path) -> io::Result<PathBuf> {
+    let root = get_repository_root(dir)?;
+    //hooks may be elsewhere, e.g. core.hooksPath or worktrees
+    let hooks = PathBuf::from(git(&root, &["rev-parse", "--git-path", "hooks"])?.trim());
+    let hooks = match hooks.is_absolute() {
+        true => hooks,
+        false => root.join(hooks),
+    };
+
+    Ok(hooks.join("pre-commit"))
+}
+
+/// Installs a pre-commit hook, running `adr hooks run` (`adr` from the PATH, this binary otherwise), in the git
+/// repository containing `adr_src_dir`.
+/// An existing pre-commit hook which has not been installed by adrust is left untouched (AlreadyExists error).
+pub fn install_hook(cfg: &AdrToolConfig) -> io::Result<PathBuf> {
+    let path = get_hook_path(Path::new(&cfg.adr_src_dir))?;
+    if path.exists() && !fs::read_to_string(&path).unwrap_or_default().contains(HOOK_MARKER) {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("[{}] already exists and has not been installed by adrust, not overwritten", path.display()),
+        ));
+    }
+
+    //`adr` from the PATH first, so the hook survives upgrades and works for every clone; this binary otherwise
+    let fallback = match std::env::current_exe() {
+        Ok(exe) => format!("exec \"{}\" hooks run\n", exe.display()),
+        Err(_) => String::from("echo \"adr not found in PATH\" >&2\nexit 1\n"),
+    };
+    let script = format!(
+        "#!/bin/sh\n{}, installed by `adr hooks install` and removed by `adr hooks uninstall`\n# validates the staged Decision Records\nif command -v adr >/dev/null 2>&1; then\n    exec adr hooks run\nfi\n{}",
+        HOOK_MARKER, fallback
+    );
+
+    fs::create_dir_all(path.parent().unwrap())?;
+    fs::write(&path, script)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+    }
+    info!(get_logger(), "pre-commit hook installed in [{}]", path.display());
+
+    Ok(path)
+}
+
+/// Removes the pre-commit hook installed by `install_hook`. Returns false if there is none.
+pub fn uninstall_hook(cfg: &AdrToolConfig) -> io::Result<bool> {
+    let path = get_hook_path(Path::new(&cfg.adr_src_dir))?;
+    if !path.exists() {
+        return Ok(false);
+    }
+    if !fs::read_to_string(&path).unwrap_or_default().contains(HOOK_MARKER) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("[{}] has not been installed by adrust, not removed", path.display()),
+        ));
+    }
+
+    fs::remove_file(&path)?;
+    info!(get_logger(), "pre-commit hook removed from [{}]", path.display());
+
+    Ok(true)
+}
+
+/// The staged (added, copied, modified or renamed) Decision Records of `adr_src_dir`, built from their staged content
+pub fn get_staged_adrs(cfg: &AdrToolConfig) -> io::Result<Vec<Adr>> {
+    let src_dir = fs::canonicalize(&cfg.adr_src_dir)?;
+    let root = fs::canonicalize(get_repository_root(&src_dir)?)?;
+
+    let staged: Vec<PathBuf> = git(&root, &["diff", "--cached", "--name-only", "--diff-filter=ACMR", "-z"])?
+        .split('\0')
+        .filter(|file| !file.is_empty())
+        .map(|file| root.join(file))
+        .collect();
+    if staged.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    //the staged content, which may differ from the working tree
+    let mut adrs: Vec<Adr> = list_all_staged_adr(cfg)?
+        .into_iter()
+        .filter(|adr| staged.contains(&src_dir.join(&adr.file_path)))
+        .collect();
+    adrs.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+
+    Ok(adrs)
+}
+
+/// A commit touching a Decision Record
+#[derive(Debug, Clone, PartialEq)]
+pub struct Commit {
+    pub hash: String,
+    pub author: String,
+    pub email: String,
+    /// YYYY-MM-DD
+    pub date: String,
+    pub subject: String,
+    /// the path of the file (relative to the repository root) in this commit, it may have been renamed since
+    pub path: String,
+    /// the status of the Decision Record in this commit
+    pub status: Status,
+}
+
+impl Commit {
+    pub fn short_hash(&self) -> &str {
+        &self.hash[..self.hash.len().min(7)]
+    }
+}
+
+/// The git history of a Decision Record, oldest commit first. Empty if the file has never been committed.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct History {
+    pub commits: Vec<Commit>,
+}
+
+impl History {
+    /// The commit adding the Decision Record, its author is considered as the author of the decision
+    pub fn created(&self) -> Option<&Commit> {
+        self.commits.first()
+    }
+
+    pub fn last_modified(&self) -> Option<&Commit> {
+        self.commits.last()
+    }
+
+    /// The commits changing the status (the creation commit included)
+    pub fn status_changes(&self) -> Vec<&Commit> {
+        let mut previous = None;
+        self.commits
+            .iter()
+            .filter(|commit| {
+                let changed = previous != Some(commit.status);
+                previous = Some(commit.status);
+                changed
+            })
+            .collect()
+    }
+}
+
+/// Returns the git history of `adr`, renames included. The status of each commit is parsed from the committed content.
+pub fn get_history(adr: &Adr) -> io::Result<History> {
+    let full_path = fs::canonicalize(Path::new(&adr.base_path).join(&adr.file_path))?;
+    let root = fs::canonicalize(get_repository_root(full_path.parent().unwrap())?)?;
+    let relative = full_path.strip_prefix(&root).unwrap().display().to_string();
+
+    //one record per commit: the header fields, then the file name (as of the commit) after a blank line
+    let log = git(
+        &root,
+        &[
+            "log",
+            "--follow",
+            "--name-only",
+            "--date=short",
+            "--format=%x1e%H%x1f%an%x1f%ae%x1f%ad%x1f%s",
+            "--",
+            &relative,
+        ],
+    )?;
+
+    let mut commits = Vec::new();
+    for record in log.split('\x1e').filter(|r| !r.trim().is_empty()) {
+        let mut lines = record.lines().filter(|l| !l.trim().is_empty());
+        let fields: Vec<&str> = lines.next().unwrap_or_default().split('\x1f').collect();
+        if fields.len() < 5 {
+            warn!(get_logger(), "Unexpected git log record [{}]", record);
+            continue;
+        }
+        let path = lines.next().unwrap_or(&relative).to_string();
+        commits.push(Commit {
+            hash: fields[0].to_string(),
+            author: fields[1].to_string(),
+            email: fields[2].to_string(),
+            date: fields[3].to_string(),
+            subject: fields[4].to_string(),
+            path,
+            status: Status::NONE,
+        });
+    }
+
+    //the committed contents are read at once, none if the file has been removed by the commit
+    let objects: Vec<String> = commits.iter().map(|c| format!("{}:{}", c.hash, c.path)).collect();
+    let objects: Vec<&str> = objects.iter().map(|o| o.as_str()).collect();
+    for (commit, content) in commits.iter_mut().zip(cat_files(&root, &objects)?) {
+        if let Some(content) = content {
+            //only the status is used, not the id parsed from the file name
+            commit.status = Adr::from(adr.base_path.clone(), adr.file_path.clone(), content, "").status;
+        }
+    }
+    //--reverse does not work with --follow
+    commits.reverse();
+    debug!(get_logger(), "[{}] commit(s) found for [{}]", commits.len(), relative);
+
+    Ok(History { commits })
+}
+
+/// Replaces `{id}`, `{title}`, `{slug}`, `{status}` and `{action}` in `pattern` (cf. `commit_message` and `branch_pattern`)
+pub fn format_pattern(pattern: &str, adr: &Adr, action: &str) -> String {
+    pattern
+        .replace("{id}", &adr.file_id.to_string())
+        .replace("{title}", &adr.title)
+        .replace("{slug}", &crate::adr_repo::slugify(&adr.title))
+        .replace("{status}", adr.status.as_str())
+        .replace("{action}", action)
+}
+
+/// Stages and commits exactly `files` (whatever else is staged is left as is). Returns the short hash of the commit.
+pub fn commit(files: &[PathBuf], message: &str) -> io::Result<String> {
+    let files = files.iter().map(fs::canonicalize).collect::<io::Result<Vec<PathBuf>>>()?;
+    let first = files
+        .first()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Nothing to commit"))?;
+    let root = get_repository_root(first.parent().unwrap())?;
+
+    let paths: Vec<String> = files.iter().map(|f| f.display().to_string()).collect();
+    let mut args = vec!["add", "--"];
+    args.extend(paths.iter().map(|p| p.as_str()));
+    git(&root, &args)?;
+    let mut args = vec!["commit", "-q", "-m", message, "--only", "--"];
+    args.extend(paths.iter().map(|p| p.as_str()));
+    git(&root, &args)?;
+
+    let hash = git(&root, &["rev-parse", "--short", "HEAD"])?.trim().to_string();
+    info!(get_logger(), "[{}] committed as [{}] - [{}]", paths.join(", "), hash, message);
+
+    Ok(hash)
+}
+
+/// Creates and checks out the branch `branch_pattern` for `adr`, from the current HEAD. Returns the name of the branch.
+pub fn create_branch(cfg: &AdrToolConfig, adr: &Adr) -> io::Result<String> {
+    let root = get_repository_root(Path::new(&adr.base_path))?;
+    let name = format_pattern(&cfg.branch_pattern, adr, "");
+    git(&root, &["checkout", "-q", "-b", &name])?;
+    info!(get_logger(), "Branch [{}] created for [{}]", name, adr.path());
+
+    Ok(name)
+}
+
+/// The full hash of the commit `rev` (e.g. a tag, a branch or `HEAD~3`), NotFound if it does not exist
+fn resolve_revision(root: &Path, rev: &str) -> io::Result<String> {
+    match git(root, &["rev-parse", "--verify", "--quiet", &format!("{}^{{commit}}", rev)]) {
+        Ok(hash) => Ok(hash.trim().to_string()),
+        Err(_) => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("[{}] is not a known git revision", rev),
+        )),
+    }
+}
+
+/// Lists the Decision Records of `adr_src_dir` as they were at the git revision `rev`, read from the git object database
+/// (the working tree is left untouched). Hidden files and directories are ignored, as with `adr_repo::list_all_adr`.
+pub fn list_all_adr_at(cfg: &AdrToolConfig, rev: &str) -> io::Result<Vec<Adr>> {
+    let src_dir = fs::canonicalize(&cfg.adr_src_dir)?;
+    let root = fs::canonicalize(get_repository_root(&src_dir)?)?;
+    let hash = resolve_revision(&root, rev)?;
+    let prefix = src_dir.strip_prefix(&root).unwrap().display().to_string();
+
+    let mut args = vec!["ls-tree", "-r", "-z", &hash];
+    if !prefix.is_empty() {
+        args.extend(["--", &prefix]);
+    }
+    //<mode> blob <object>\t<file>
+    let files = git(&root, &args)?
+        .split('\0')
+        .filter_map(|entry| entry.split_once('\t'))
+        .filter(|(meta, _)| meta.contains(" blob "))
+        .map(|(meta, file)| (meta.rsplit(' ').next().unwrap().to_string(), file.to_string()))
+        .collect();
+    let adrs = read_adrs(&root, &src_dir, &prefix, files, &cfg.name_pattern)?;
+    debug!(get_logger(), "[{}] ADR(s) found at [{}]", adrs.len(), rev);
+
+    Ok(adrs)
+}
+
+/// Lists the Decision Records of `adr_src_dir` as they are in the index, i.e. as they would be committed: the untracked
+/// files are ignored and the staged content replaces the one of the working tree
+pub fn list_all_staged_adr(cfg: &AdrToolConfig) -> io::Result<Vec<Adr>> {
+    let src_dir = fs::canonicalize(&cfg.adr_src_dir)?;
+    let root = fs::canonicalize(get_repository_root(&src_dir)?)?;
+    let prefix = src_dir.strip_prefix(&root).unwrap().display().to_string();
+
+    let mut args = vec!["ls-files", "-s", "-z"];
+    if !prefix.is_empty() {
+        args.extend(["--", &prefix]);
+    }
+    //<mode> <object> <stage>\t<file>, the conflicting entries (stages 1 to 3) are ignored
+    let files = git(&root, &args)?
+        .split('\0')
+        .filter_map(|entry| entry.split_once('\t'))
+        .filter_map(|(meta, file)| match meta.split(' ').collect::<Vec<&str>>()[..] {
+            [_, object, "0"] => Some((object.to_string(), file.to_string())),
+            _ => None,
+        })
+        .collect();
+    let adrs = read_adrs(&root, &src_dir, &prefix, files, &cfg.name_pattern)?;
+    debug!(get_logger(), "[{}] ADR(s) found in the index", adrs.len());
+
+    Ok(adrs)
+}
+
+/// Builds the Decision Records from the git `files` ((object, path relative to `root`)) under `prefix`, the path of
+/// `src_dir` relative to `root`. Hidden files and directories are ignored, as with `adr_repo::list_all_adr`.
+fn read_adrs(
+    root: &Path,
+    src_dir: &Path,
+    prefix: &str,
+    files: Vec<(String, String)>,
+    name_pattern: &str,
+) -> io::Result<Vec<Adr>> {
+    let mut adr_files = Vec::new();
+    for (object, file) in files.into_iter().filter(|(_, file)| file.ends_with(".adoc")) {
+        let relative = match Path::new(&file).strip_prefix(prefix) {
+            Ok(relative) => relative.display().to_string(),
+            Err(_) => continue,
+        };
+        if relative.split('/').any(|part| part.starts_with('.')) {
+            continue;
+        }
+        adr_files.push((object, relative));
+    }
+
+    let objects: Vec<&str> = adr_files.iter().map(|(object, _)| object.as_str()).collect();
+    let mut adrs = Vec::new();
+    for ((object, relative), content) in adr_files.iter().zip(cat_files(root, &objects)?) {
+        let content = content.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, format!("the git object [{}] is missing", object))
+        })?;
+        adrs.push(Adr::from(src_dir.display().to_string(), relative.to_string(), content, name_pattern));
+    }
+    adrs.sort_by_key(|a| a.file_id);
+
+    Ok(adrs)
+}
+
+/// Reads the content of the git `objects` (e.g. blobs or `<rev>:<path>`) in `dir` with a single `git cat-file --batch`,
+/// None for the missing ones
+fn cat_files(dir: &Path, objects: &[&str]) -> io::Result<Vec<Option<String>>> {
+    if objects.is_empty() {
+        return Ok(Vec::new());
+    }
+    let input: String = objects.iter().map(|object| format!("{}\n", object)).collect();
+    let output = run_git(dir, &["cat-file", "--batch"], Some(input.into_bytes()))?;
+
+    //each object is written as `<object> <type> <size>\n<content>\n`, or `<object> missing\n`
+    let mut contents = Vec::new();
+    let mut rest = output.as_slice();
+    for object in objects {
+        let invalid = || {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unable to read the git object [{}]", object),
+            )
+        };
+        let eol = rest.iter().position(|b| *b == b'\n').ok_or_else(invalid)?;
+        let header = String::from_utf8_lossy(&rest[..eol]).to_string();
+        let start = eol + 1;
+        let size: usize = match header.rsplit(' ').collect::<Vec<&str>>()[..] {
+            ["missing", ..] | ["ambiguous", ..] => {
+                contents.push(None);
+                rest = &rest[start..];
+                continue;
+            }
+            [size, _, _, ..] => size.parse().map_err(|_| invalid())?,
+            _ => return Err(invalid()),
+        };
+        let content = rest.get(start..start + size).ok_or_else(invalid)?;
+        contents.push(Some(String::from_utf8_lossy(content).to_string()));
+        rest = rest.get(start + size + 1..).unwrap_or_default();
+    }
+
+    Ok(contents)
+}
+
+/// Finds a Decision Record, given its id or its path (relative to `adr_src_dir`), at the git revision `rev`
+pub fn find_adr_at(cfg: &AdrToolConfig, id_or_path: &str, rev: &str) -> io::Result<Adr> {
+    let adrs = list_all_adr_at(cfg, rev)?;
+    let found = match id_or_path.parse::<usize>() {
+        Ok(id) => adrs.into_iter().find(|adr| adr.file_id == id),
+        Err(_) => {
+            let path = Path::new(id_or_path);
+            let path = path.strip_prefix(&cfg.adr_src_dir).unwrap_or(path);
+            adrs.into_iter().find(|adr| Path::new(&adr.file_path) == path)
+        }
+    };
+
+    found.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("No ADR [{}] at [{}]", id_or_path, rev),
+        )
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Change {
+    ADDED,
+    REMOVED,
+    STATUS,
+    /// the content changed, not the status
+    EDITED,
+}
+
+impl Change {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Change::ADDED => "added",
+            Change::REMOVED => "removed",
+            Change::STATUS => "status",
+            Change::EDITED => "edited",
+        }
+    }
+}
+
+/// A Decision Record added, removed, edited or whose status changed between two revisions
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdrChange {
+    pub change: Change,
+    pub id: usize,
+    pub title: String,
+    pub path: String,
+    /// None if added
+    pub from: Option<Status>,
+    /// None if removed
+    pub to: Option<Status>,
+}
+
+/// Lists the Decision Records added, removed or whose status changed between `from_rev` and `to_rev`, sorted by id
+pub fn diff(cfg: &AdrToolConfig, from_rev: &str, to_rev: &str) -> io::Result<Vec<AdrChange>> {
+    let from = list_all_adr_at(cfg, from_rev)?;
+    let to = list_all_adr_at(cfg, to_rev)?;
+
+    let mut changes = compare(&from, &to);
+    changes.retain(|c| c.change != Change::EDITED);
+
+    Ok(changes)
+}
+
+/// Returns the Decision Record of `to` matching `old` of `from`: the one at the same path, otherwise (e.g. moved to
+/// another directory) the one with the same id, if no other ADR of `from` or `to` could match it
+fn find_match<'a>(old: &Adr, from: &[Adr], to: &'a [Adr]) -> Option<&'a Adr> {
+    if let Some(new) = to.iter().find(|adr| adr.file_path == old.file_path) {
+        return Some(new);
+    }
+    if old.file_id == 0 {
+        return None;
+    }
+
+    let is_unmatched =
+        |adr: &Adr, others: &[Adr]| adr.file_id == old.file_id && !others.iter().any(|o| o.file_path == adr.file_path);
+    let olds = from.iter().filter(|adr| is_unmatched(adr, to)).count();
+    let news: Vec<&Adr> = to.iter().filter(|adr| is_unmatched(adr, from)).collect();
+    match (olds, &news[..]) {
+        (1, [new]) => Some(*new),
+        _ => None,
+    }
+}
+
+/// Compares two sets of Decision Records (matched by path, then by id for the moved ones), the changes are sorted by id.
+/// A moved ADR whose status has not changed is EDITED.
+pub fn compare(from: &[Adr], to: &[Adr]) -> Vec<AdrChange> {
+    let mut changes = Vec::new();
+    let mut matched = Vec::new();
+    for old in from.iter() {
+        let found = find_match(old, from, to);
+        if let Some(new) = found {
+            matched.push(new.file_path.as_str());
+        }
+        match found {
+            None => changes.push(AdrChange {
+                change: Change::REMOVED,
+                id: old.file_id,
+                title: old.title.clone(),
+                path: old.file_path.clone(),
+                from: Some(old.status),
+                to: None,
+            }),
+            Some(new) if new.status != old.status => changes.push(AdrChange {
+                change: Change::STATUS,
+                id: new.file_id,
+                title: new.title.clone(),
+                path: new.file_path.clone(),
+                from: Some(old.status),
+                to: Some(new.status),
+            }),
+            Some(new) if new.content != old.content || new.file_path != old.file_path => changes.push(AdrChange {
+                change: Change::EDITED,
+                id: new.file_id,
+                title: new.title.clone(),
+                path: new.file_path.clone(),
+                from: Some(old.status),
+                to: Some(new.status),
+            }),
+            Some(_) => {}
+        }
+    }
+    for new in to.iter().filter(|adr| !matched.contains(&adr.file_path.as_str())) {
+        changes.push(AdrChange {
+            change: Change::ADDED,
+            id: new.file_id,
+            title: new.title.clone(),
+            path: new.file_path.clone(),
+            from: None,
+            to: Some(new.status),
+        });
+    }
+    changes.sort_by_key(|c| c.id);
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use adr_config::config::AdrToolConfig;
+    use tempdir::TempDir;
+
+    use crate::adr_repo::{Adr, Status};
+
+    fn init_repository(dir: &Path) {
+        super::git(dir, &["init", "-q"]).unwrap();
+    }
+
+    fn commit(dir: &Path, message: &str) {
+        super::git(dir, &["add", "-A"]).unwrap();
+        super::git(
+            dir,
+            &["-c", "user.name=John Doe", "-c", "user.email=john@doe.com", "commit", "-q", "-m", message],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_install_and_uninstall_hook() {
+        let dir = TempDir::new("my_git_folder").unwrap();
+        init_repository(dir.path());
+        fs::create_dir_all(dir.path().join("docs/adr")).unwrap();
+        let cfg = AdrToolConfig {
+            adr_src_dir: format!("{}", dir.path().join("docs/adr").display()),
+            ..Default::default()
+        };
+
+        let path = super::install_hook(&cfg).unwrap();
+        assert!(path.ends_with(".git/hooks/pre-commit"));
+        let script = fs::read_to_string(&path).unwrap();
+        assert!(script.contains("command -v adr"));
+        assert!(script.contains("exec adr hooks run"));
+        let exe = std::env::current_exe().unwrap();
+        assert!(script.contains(&format!("exec \"{}\" hooks run", exe.display())));
+        //can be installed again
+        super::install_hook(&cfg).unwrap();
+        assert!(super::uninstall_hook(&cfg).unwrap());
+        assert!(!path.exists());
+        assert!(!super::uninstall_hook(&cfg).unwrap());
+
+        //someone else's hook
+        fs::write(&path, "#!/bin/sh\nmake test\n").unwrap();
+        assert!(super::install_hook(&cfg).is_err());
+        assert!(super::uninstall_hook(&cfg).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "#!/bin/sh\nmake test\n");
+    }
+
+    #[test]
+    fn test_get_staged_adrs() {
+        let dir = TempDir::new("my_git_folder").unwrap();
+        init_repository(dir.path());
+        fs::create_dir_all(dir.path().join("docs/adr/data")).unwrap();
+        fs::write(dir.path().join("docs/adr/data/000001-use-kafka.adoc"), "== Use Kafka\n").unwrap();
+        fs::write(dir.path().join("docs/adr/000002-not-staged.adoc"), "== Not staged\n").unwrap();
+        fs::write(dir.path().join("README.adoc"), "== Not an ADR\n").unwrap();
+        super::git(dir.path(), &["add", "docs/adr/data/000001-use-kafka.adoc", "README.adoc"]).unwrap();
+        //only the staged content matters
+        fs::write(dir.path().join("docs/adr/data/000001-use-kafka.adoc"), "== Use Kafka Streams\n").unwrap();
+
+        let cfg = AdrToolConfig {
+            adr_src_dir: format!("{}", dir.path().join("docs/adr").display()),
+            ..Default::default()
+        };
+        let adrs = super::get_staged_adrs(&cfg).unwrap();
+        assert_eq!(1, adrs.len());
+        let adr = &adrs[0];
+        assert_eq!(adr.file_path, "data/000001-use-kafka.adoc");
+        assert_eq!(adr.title, "Use Kafka");
+    }
+
+    #[test]
+    fn test_get_history() {
+        let dir = TempDir::new("my_git_folder").unwrap();
+        init_repository(dir.path());
+        let src_dir = dir.path().join("docs/adr");
+        fs::create_dir_all(&src_dir).unwrap();
+        //long enough to be detected as renamed
+        let content = format!(
+            "== Use Kafka\n\n*Status:* {{wip}} *Date:* 2026-10-01\n\n{}",
+            "=== Context and Problem Statement\n\nWe need a message broker.\n".repeat(5)
+        );
+        fs::write(src_dir.join("000001-use-kafka.adoc"), &content).unwrap();
+        commit(dir.path(), "Propose Kafka");
+        fs::write(src_dir.join("000001-use-kafka.adoc"), format!("{}\nMore context\n", content)).unwrap();
+        commit(dir.path(), "Add context");
+        fs::rename(src_dir.join("000001-use-kafka.adoc"), src_dir.join("000001-use-kafka-streams.adoc")).unwrap();
+        fs::write(
+            src_dir.join("000001-use-kafka-streams.adoc"),
+            content.replace("{wip}", "{decided}"),
+        )
+        .unwrap();
+        commit(dir.path(), "Decide Kafka");
+
+        let adr = crate::adr_repo::build_adr(&src_dir, &src_dir.join("000001-use-kafka-streams.adoc")).unwrap();
+        let history = super::get_history(&adr).unwrap();
+        assert_eq!(history.commits.len(), 3);
+        let created = history.created().unwrap();
+        assert_eq!(created.subject, "Propose Kafka");
+        assert_eq!(created.author, "John Doe");
+        assert_eq!(created.path, "docs/adr/000001-use-kafka.adoc");
+        assert_eq!(history.last_modified().unwrap().subject, "Decide Kafka");
+        let changes: Vec<(&str, Status)> = history
+            .status_changes()
+            .iter()
+            .map(|c| (c.subject.as_str(), c.status))
+            .collect();
+        assert_eq!(changes, vec![("Propose Kafka", Status::WIP), ("Decide Kafka", Status::DECIDED)]);
+
+        //removed then restored, there is no content in the removal commit
+        let decided = content.replace("{wip}", "{decided}");
+        fs::remove_file(src_dir.join("000001-use-kafka-streams.adoc")).unwrap();
+        commit(dir.path(), "Remove Kafka");
+        fs::write(src_dir.join("000001-use-kafka-streams.adoc"), &decided).unwrap();
+        commit(dir.path(), "Restore Kafka");
+        let history = super::get_history(&adr).unwrap();
+        let commits: Vec<(&str, Status)> =
+            history.commits.iter().skip(3).map(|c| (c.subject.as_str(), c.status)).collect();
+        assert_eq!(commits, vec![("Remove Kafka", Status::NONE), ("Restore Kafka", Status::DECIDED)]);
+
+        //not committed yet
+        fs::write(src_dir.join("000002-new.adoc"), &content).unwrap();
+        let adr = crate::adr_repo::build_adr(&src_dir, &src_dir.join("000002-new.adoc")).unwrap();
+        assert!(super::get_history(&adr).unwrap().created().is_none());
+    }
+
+    #[test]
+    fn test_commit_and_create_branch() {
+        let dir = TempDir::new("my_git_folder").unwrap();
+        init_repository(dir.path());
+        super::git(dir.path(), &["config", "user.name", "John Doe"]).unwrap();
+        super::git(dir.path(), &["config", "user.email", "john@doe.com"]).unwrap();
+        let src_dir = dir.path().join("docs/adr");
+        fs::create_dir_all(&src_dir).unwrap();
+        fs::write(src_dir.join("000042-use-kafka.adoc"), "== Use Kafka\n\n*Status:* {superseded}\n").unwrap();
+        fs::write(src_dir.join("000057-use-pulsar.adoc"), "== Use Pulsar\n\n*Status:* {supersedes}\n").unwrap();
+        fs::write(dir.path().join("README.adoc"), "== Staged but not committed\n").unwrap();
+        super::git(dir.path(), &["add", "README.adoc"]).unwrap();
+
+        let cfg = AdrToolConfig {
+            adr_src_dir: format!("{}", src_dir.display()),
+            ..Default::default()
+        };
+        let adr = crate::adr_repo::build_adr(&src_dir, &src_dir.join("000042-use-kafka.adoc")).unwrap();
+        let message = super::format_pattern(&cfg.commit_message, &adr, "superseded by ADR-57");
+        assert_eq!(message, "ADR-42: superseded by ADR-57");
+
+        let branch = super::create_branch(&cfg, &adr).unwrap();
+        assert_eq!(branch, "adr/42-use-kafka");
+        super::commit(
+            &[src_dir.join("000042-use-kafka.adoc"), src_dir.join("000057-use-pulsar.adoc")],
+            &message,
+        )
+        .unwrap();
+
+        let committed = super::git(dir.path(), &["show", "--name-only", "--format=%s", "HEAD"]).unwrap();
+        assert_eq!(
+            committed.lines().filter(|l| !l.is_empty()).collect::<Vec<&str>>(),
+            vec![
+                "ADR-42: superseded by ADR-57",
+                "docs/adr/000042-use-kafka.adoc",
+                "docs/adr/000057-use-pulsar.adoc"
+            ]
+        );
+        assert_eq!(super::git(dir.path(), &["branch", "--show-current"]).unwrap().trim(), "adr/42-use-kafka");
+        //still staged
+        assert_eq!(super::git(dir.path(), &["diff", "--cached", "--name-only"]).unwrap().trim(), "README.adoc");
+    }
+
+    #[test]
+    fn test_list_at_and_diff() {
+        let dir = TempDir::new("my_git_folder").unwrap();
+        init_repository(dir.path());
+        let src_dir = dir.path().join("docs/adr");
+        fs::create_dir_all(src_dir.join("data")).unwrap();
+        fs::write(src_dir.join("000001-use-kafka.adoc"), "== Use Kafka\n\n*Status:* {wip}\n").unwrap();
+        fs::write(src_dir.join("data/000002-use-postgres.adoc"), "== Use Postgres\n\n*Status:* {decided}\n").unwrap();
+        commit(dir.path(), "First decisions");
+        super::git(dir.path(), &["tag", "4.2"]).unwrap();
+        fs::write(src_dir.join("000001-use-kafka.adoc"), "== Use Kafka\n\n*Status:* {decided}\n").unwrap();
+        fs::remove_file(src_dir.join("data/000002-use-postgres.adoc")).unwrap();
+        fs::write(src_dir.join("000003-use-redis.adoc"), "== Use Redis\n\n*Status:* {wip}\n").unwrap();
+        commit(dir.path(), "More decisions");
+        //not committed
+        fs::write(src_dir.join("000004-use-mongo.adoc"), "== Use Mongo\n").unwrap();
+
+        let cfg = AdrToolConfig {
+            adr_src_dir: format!("{}", src_dir.display()),
+            ..Default::default()
+        };
+        let adrs = super::list_all_adr_at(&cfg, "4.2").unwrap();
+        let adrs: Vec<(&str, Status)> = adrs.iter().map(|a| (a.file_path.as_str(), a.status)).collect();
+        assert_eq!(
+            adrs,
+            vec![("000001-use-kafka.adoc", Status::WIP), ("data/000002-use-postgres.adoc", Status::DECIDED)]
+        );
+        assert_eq!(super::list_all_adr_at(&cfg, "HEAD").unwrap().len(), 2);
+        assert!(super::list_all_adr_at(&cfg, "4.3").is_err());
+        assert_eq!(super::find_adr_at(&cfg, "2", "4.2").unwrap().title, "Use Postgres");
+        assert_eq!(super::find_adr_at(&cfg, "000001-use-kafka.adoc", "4.2").unwrap().status, Status::WIP);
+        assert!(super::find_adr_at(&cfg, "3", "4.2").is_err());
+
+        let changes = super::diff(&cfg, "4.2", "HEAD").unwrap();
+        let changes: Vec<(super::Change, usize, Option<Status>, Option<Status>)> =
+            changes.iter().map(|c| (c.change, c.id, c.from, c.to)).collect();
+        assert_eq!(
+            changes,
+            vec![
+                (super::Change::STATUS, 1, Some(Status::WIP), Some(Status::DECIDED)),
+                (super::Change::REMOVED, 2, Some(Status::DECIDED), None),
+                (super::Change::ADDED, 3, None, Some(Status::WIP)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cat_files() {
+        let dir = TempDir::new("my_git_folder").unwrap();
+        init_repository(dir.path());
+        let mut objects = Vec::new();
+        for (name, content) in [("a.adoc", "== Use Kafka\n\nline\n"), ("b.adoc", ""), ("c.adoc", "no new line")] {
+            fs::write(dir.path().join(name), content).unwrap();
+            objects.push(super::git(dir.path(), &["hash-object", "-w", name]).unwrap().trim().to_string());
+        }
+        let objects: Vec<&str> = objects.iter().map(|o| o.as_str()).collect();
+
+        assert_eq!(
+            super::cat_files(dir.path(), &objects).unwrap(),
+            vec![Some(String::from("== Use Kafka\n\nline\n")), Some(String::new()), Some(String::from("no new line"))]
+        );
+        assert!(super::cat_files(dir.path(), &[]).unwrap().is_empty());
+        assert_eq!(
+            super::cat_files(dir.path(), &["0123456789012345678901234567890123456789", objects[2]]).unwrap(),
+            vec![None, Some(String::from("no new line"))]
+        );
+    }
+
+    #[test]
+    fn test_compare_moved() {
+        let adr = |path: &str, content: &str| Adr::from(String::from("/adr"), path.to_string(), content.to_string(), "");
+        let from = vec![
+            adr("000001-use-kafka.adoc", "== Use Kafka\n\n*Status:* {decided}\n"),
+            adr("000002-use-redis.adoc", "== Use Redis\n\n*Status:* {wip}\n"),
+            adr("000004-use-avro.adoc", "== Use Avro\n\n*Status:* {wip}\n"),
+        ];
+        let to = vec![
+            //moved and rewritten
+            adr("data/000001-use-kafka.adoc", "== Use Kafka Streams\n\n*Status:* {decided}\n"),
+            adr("archive/000002-use-redis.adoc", "== Use Redis\n\n*Status:* {decided}\n"),
+            //which one is the moved ADR?
+            adr("data/000004-use-avro.adoc", "== Use Avro\n\n*Status:* {wip}\n"),
+            adr("data/000004-use-protobuf.adoc", "== Use Protobuf\n\n*Status:* {wip}\n"),
+        ];
+
+        let changes = super::compare(&from, &to);
+        let changes: Vec<(super::Change, usize, &str)> =
+            changes.iter().map(|c| (c.change, c.id, c.path.as_str())).collect();
+        assert_eq!(
+            changes,
+            vec![
+                (super::Change::EDITED, 1, "data/000001-use-kafka.adoc"),
+                (super::Change::STATUS, 2, "archive/000002-use-redis.adoc"),
+                (super::Change::REMOVED, 4, "000004-use-avro.adoc"),
+                (super::Change::ADDED, 4, "data/000004-use-avro.adoc"),
+                (super::Change::ADDED, 4, "data/000004-use-protobuf.adoc"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,356 @@
+use std::io::{self};
+
+extern crate regex;
+use regex::Regex;
+
+use serde::Serializer;
+
+use crate::adr_repo::{Adr, Status, ATTR_REFERENCES};
+
+#[derive(Debug, PartialEq, Copy, Clone, Serialize)]
+pub enum EdgeKind {
+    #[serde(rename = "supersedes")]
+    SUPERSEDES,
+    #[serde(rename = "completes")]
+    COMPLETES,
+}
+
+impl EdgeKind {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            EdgeKind::SUPERSEDES => "supersedes",
+            EdgeKind::COMPLETES => "completes",
+        }
+    }
+}
+
+/// A Decision Record, identified by its path relative to `adr_src_dir` (e.g. `data/000002-use-postgres.adoc`)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Node {
+    pub id: usize,
+    pub path: String,
+    pub title: String,
+    #[serde(serialize_with = "serialize_status")]
+    pub status: Status,
+    pub date: String,
+    pub tags: Vec<String>,
+}
+
+/// `from` supersedes / completes `to` (i.e. `to` is superseded / completed by `from`)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Edge {
+    pub from: String,
+    pub to: String,
+    pub kind: EdgeKind,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Graph {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+}
+
+fn serialize_status<S: Serializer>(status: &Status, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(status.as_str())
+}
+
+/// The colours of the status labels (see `templates/theme.css`)
+pub fn get_status_color(status: Status) -> &'static str {
+    match status {
+        Status::WIP => "#dfa019",
+        Status::DECIDED | Status::COMPLETED | Status::COMPLETES | Status::SUPERSEDES => "#13911e",
+        Status::SUPERSEDED | Status::CANCELLED => "#9c0d0d",
+        Status::NONE => "#aaa6a6",
+    }
+}
+
+/// Returns the `{status} file-name.adoc` (or `:adr-superseded-by: file-name.adoc`...) references added by `transition_to`
+/// (see `Adr::add_reference`),
+/// e.g. `(SUPERSEDED, "000003-use-kafka.adoc")`
+pub fn get_references(adr: &Adr) -> Vec<(Status, String)> {
+    lazy_static! {
+        static ref RE_REFERENCES: Regex =
+            Regex::new(r"\{(supersedes|superseded|completes|completed)\}((?:\s+[^\s{}*]+\.adoc)+)").unwrap();
+    }
+
+    let mut references = Vec::new();
+    for cap in RE_REFERENCES.captures_iter(&adr.content) {
+        for target in cap[2].split_whitespace() {
+            references.push((Status::from_str(cap[1].to_string()), target.to_string()));
+        }
+    }
+    for (status, attribute) in ATTR_REFERENCES.iter() {
+        if let Some(targets) = adr.attributes.get(*attribute) {
+            for target in targets.split_whitespace() {
+                references.push((*status, target.to_string()));
+            }
+        }
+    }
+
+    references
+}
+
+/// Builds the graph of the Decision Records, the edges coming from the references of both sides
+/// (e.g. `{supersedes} b.adoc` in a.adoc and `{superseded} a.adoc` in b.adoc give a single edge). References to unknown
+/// Decision Records are ignored.
+pub fn build_graph(adrs: &[Adr]) -> Graph {
+    let mut graph = Graph::default();
+    for adr in adrs.iter() {
+        graph.nodes.push(Node {
+            id: adr.file_id,
+            path: adr.file_path.replace('\\', "/"),
+            title: adr.title.clone(),
+            status: adr.status,
+            date: adr.date.clone(),
+            tags: adr.tags_array.iter().map(|t| t.trim().to_string()).collect(),
+        });
+    }
+
+    //references use the file names
+    let find = |name: &str| adrs.iter().find(|adr| adr.file_name == name).map(|adr| adr.file_path.replace('\\', "/"));
+    for adr in adrs.iter() {
+        let path = adr.file_path.replace('\\', "/");
+        for (status, target) in get_references(adr) {
+            let target = match find(&target) {
+                Some(target) => target,
+                None => continue,
+            };
+            let edge = match status {
+                Status::SUPERSEDES => Edge { from: path.clone(), to: target, kind: EdgeKind::SUPERSEDES },
+                Status::SUPERSEDED => Edge { from: target, to: path.clone(), kind: EdgeKind::SUPERSEDES },
+                Status::COMPLETES => Edge { from: path.clone(), to: target, kind: EdgeKind::COMPLETES },
+                _ => Edge { from: target, to: path.clone(), kind: EdgeKind::COMPLETES },
+            };
+            if !graph.edges.contains(&edge) {
+                graph.edges.push(edge);
+            }
+        }
+    }
+
+    graph
+}
+
+impl Graph {
+    /// Keeps the Decision Records having the tag `tag` and/or being in the sub directory `dir`, and the edges between them
+    pub fn filter(&self, tag: Option<&str>, dir: Option<&str>) -> Graph {
+        let dir = dir.map(|dir| format!("{}/", dir.trim_matches('/')));
+        let nodes: Vec<Node> = self
+            .nodes
+            .iter()
+            .filter(|node| match tag {
+                Some(tag) => node.tags.iter().any(|t| t.eq_ignore_ascii_case(tag.trim())),
+                None => true,
+            })
+            .filter(|node| match &dir {
+                Some(dir) => node.path.starts_with(dir.as_str()),
+                None => true,
+            })
+            .cloned()
+            .collect();
+        let edges = self
+            .edges
+            .iter()
+            .filter(|edge| {
+                nodes.iter().any(|n| n.path == edge.from) && nodes.iter().any(|n| n.path == edge.to)
+            })
+            .cloned()
+            .collect();
+
+        Graph { nodes, edges }
+    }
+
+    /// The Decision Records superseded / completed by `path` and the ones superseding / completing it,
+    /// with the status to display in front of each of them (e.g. `(SUPERSEDES, node)`)
+    pub fn get_related(&self, path: &str) -> Vec<(Status, &Node)> {
+        let find = |path: &str| self.nodes.iter().find(|n| n.path == path);
+        let mut related = Vec::new();
+        for edge in self.edges.iter() {
+            let (status, other) = match edge.kind {
+                EdgeKind::SUPERSEDES if edge.from == path => (Status::SUPERSEDES, &edge.to),
+                EdgeKind::SUPERSEDES if edge.to == path => (Status::SUPERSEDED, &edge.from),
+                EdgeKind::COMPLETES if edge.from == path => (Status::COMPLETES, &edge.to),
+                EdgeKind::COMPLETES if edge.to == path => (Status::COMPLETED, &edge.from),
+                _ => continue,
+            };
+            if let Some(node) = find(other) {
+                related.push((status, node));
+            }
+        }
+
+        related
+    }
+
+    /// Graphviz format, e.g. `adr graph | dot -Tsvg > decisions.svg`
+    pub fn to_dot(&self) -> String {
+        let escape = |val: &str| val.replace('\\', "\\\\").replace('"', "\\\"");
+
+        let mut dot = String::from(
+            "digraph adr {\n    rankdir=LR;\n    node [shape=box, style=\"rounded,filled\", fontcolor=white];\n",
+        );
+        for node in self.nodes.iter() {
+            dot.push_str(&format!(
+                "    \"{}\" [label=\"{}. {}\\n({})\", fillcolor=\"{}\"];\n",
+                escape(&node.path),
+                node.id,
+                escape(&node.title),
+                node.status.as_str(),
+                get_status_color(node.status)
+            ));
+        }
+        for edge in self.edges.iter() {
+            dot.push_str(&format!(
+                "    \"{}\" -> \"{}\" [label=\"{}\"];\n",
+                escape(&edge.from),
+                escape(&edge.to),
+                edge.kind.as_str()
+            ));
+        }
+        dot.push_str("}\n");
+
+        dot
+    }
+
+    /// Mermaid flowchart, e.g. to be embedded in a Markdown document
+    pub fn to_mermaid(&self) -> String {
+        let id = |path: &str| format!("adr{}", self.nodes.iter().position(|n| n.path == path).unwrap_or(0));
+
+        let mut mermaid = String::from("graph LR\n");
+        for (index, node) in self.nodes.iter().enumerate() {
+            mermaid.push_str(&format!(
+                "    adr{}[\"{}. {}\"]:::{}\n",
+                index,
+                node.id,
+                node.title.replace('"', "#quot;"),
+                node.status.as_str()
+            ));
+        }
+        for edge in self.edges.iter() {
+            mermaid.push_str(&format!(
+                "    {} -->|{}| {}\n",
+                id(&edge.from),
+                edge.kind.as_str(),
+                id(&edge.to)
+            ));
+        }
+        let statuses: Vec<Status> = self.nodes.iter().map(|n| n.status).collect();
+        for status in [
+            Status::WIP,
+            Status::DECIDED,
+            Status::COMPLETED,
+            Status::COMPLETES,
+            Status::SUPERSEDED,
+            Status::SUPERSEDES,
+            Status::CANCELLED,
+            Status::NONE,
+        ] {
+            if statuses.contains(&status) {
+                mermaid.push_str(&format!(
+                    "    classDef {} fill:{},color:#fff\n",
+                    status.as_str(),
+                    get_status_color(status)
+                ));
+            }
+        }
+
+        mermaid
+    }
+
+    pub fn to_json(&self) -> io::Result<String> {
+        serde_json::to_string_pretty(self).map_err(io::Error::other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adr_repo::{Adr, Status};
+
+    fn adr(file_path: &str, content: &str) -> Adr {
+        Adr::from(String::from("/tmp/adrs"), String::from(file_path), String::from(content), "")
+    }
+
+    fn get_adrs() -> Vec<Adr> {
+        vec![
+            adr(
+                "000001-use-postgres.adoc",
+                ":adr-status: superseded\n\n== Use \"Postgres\"\n\n*Status:* {superseded} 000002-use-kafka.adoc\n\n[tags]#storage#",
+            ),
+            adr(
+                "data/000002-use-kafka.adoc",
+                ":adr-status: supersedes\n\n== Use Kafka\n\n*Status:* {supersedes} 000001-use-postgres.adoc 000009-unknown.adoc\n\n[tags]#storage# [tags]#streaming#",
+            ),
+            adr(
+                "data/000003-use-avro.adoc",
+                ":adr-status: completes\n\n== Use Avro\n\n*Status:* {completes} 000002-use-kafka.adoc\n\n[tags]#streaming#",
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_build_graph() {
+        let graph = super::build_graph(&get_adrs());
+        assert_eq!(3, graph.nodes.len());
+        assert_eq!(graph.nodes[1].tags, vec!["storage", "streaming"]);
+        //both sides of the supersede reference give one edge, the unknown reference is ignored
+        assert_eq!(
+            graph.edges,
+            vec![
+                super::Edge {
+                    from: String::from("data/000002-use-kafka.adoc"),
+                    to: String::from("000001-use-postgres.adoc"),
+                    kind: super::EdgeKind::SUPERSEDES
+                },
+                super::Edge {
+                    from: String::from("data/000003-use-avro.adoc"),
+                    to: String::from("data/000002-use-kafka.adoc"),
+                    kind: super::EdgeKind::COMPLETES
+                },
+            ]
+        );
+
+        let related: Vec<(Status, usize)> = graph
+            .get_related("data/000002-use-kafka.adoc")
+            .iter()
+            .map(|(status, node)| (*status, node.id))
+            .collect();
+        assert_eq!(related, vec![(Status::SUPERSEDES, 1), (Status::COMPLETED, 3)]);
+
+        let filtered = graph.filter(Some("Streaming"), None);
+        assert_eq!(2, filtered.nodes.len());
+        assert_eq!(1, filtered.edges.len());
+        let filtered = graph.filter(None, Some("data/"));
+        assert_eq!(2, filtered.nodes.len());
+        assert_eq!(super::EdgeKind::COMPLETES, filtered.edges[0].kind);
+    }
+
+    #[test]
+    fn test_graph_formats() {
+        let graph = super::build_graph(&get_adrs()).filter(Some("storage"), None);
+
+        assert_eq!(
+            graph.to_dot(),
+            "digraph adr {
+    rankdir=LR;
+    node [shape=box, style=\"rounded,filled\", fontcolor=white];
+    \"000001-use-postgres.adoc\" [label=\"1. Use \\\"Postgres\\\"\\n(superseded)\", fillcolor=\"#9c0d0d\"];
+    \"data/000002-use-kafka.adoc\" [label=\"2. Use Kafka\\n(supersedes)\", fillcolor=\"#13911e\"];
+    \"data/000002-use-kafka.adoc\" -> \"000001-use-postgres.adoc\" [label=\"supersedes\"];
+}
+"
+        );
+        assert_eq!(
+            graph.to_mermaid(),
+            "graph LR
+    adr0[\"1. Use #quot;Postgres#quot;\"]:::superseded
+    adr1[\"2. Use Kafka\"]:::supersedes
+    adr1 -->|supersedes| adr0
+    classDef superseded fill:#9c0d0d,color:#fff
+    classDef supersedes fill:#13911e,color:#fff
+"
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&graph.to_json().unwrap()).unwrap();
+        assert_eq!(json["nodes"][1]["status"], "supersedes");
+        assert_eq!(json["edges"][0]["kind"], "supersedes");
+        assert_eq!(json["edges"][0]["to"], "000001-use-postgres.adoc");
+    }
+}
@@ -0,0 +1,1000 @@
+extern crate slog;
+extern crate slog_term;
+use slog::*;
+
+use std::collections::HashMap;
+use std::fs::{self};
+use std::io::{self};
+use std::path::{Component, Path, PathBuf};
+
+extern crate regex;
+use regex::{Captures, Regex};
+
+extern crate adr_config;
+use adr_config::config::AdrToolConfig;
+
+use chrono::prelude::*;
+
+use walkdir::WalkDir;
+
+use crate::adr_repo::{
+    format_decision_name_with_id, list_all_adr, Status, ATTR_DECIDERS, ATTR_EXPERTS, ATTR_STAKEHOLDERS,
+};
+use crate::adr_template;
+
+fn get_logger() -> slog::Logger {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+
+    let decorator = slog_term::TermDecorator::new().build();
+    let drain = slog_term::FullFormat::new(decorator).build().fuse();
+    let drain = slog_async::Async::new(drain).build().fuse();
+    let drain = slog::LevelFilter::new(
+        drain,
+        Level::from_usize(cfg.log_level).unwrap_or(Level::Debug),
+    )
+    .fuse();
+
+    slog::Logger::root(drain, o!())
+}
+
+/// The outcome of the import of a record
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedAdr {
+    pub source: String,
+    pub target: String,
+    pub id: usize,
+    pub title: String,
+    pub status: Status,
+    /// false if the target file already exists (it is then left untouched). In dry-run mode, true if it would be created
+    pub created: bool,
+    /// what could not be mapped to adrust's format
+    pub warnings: Vec<String>,
+}
+
+/// A link between records, e.g. `Superseded by [3. Use Kafka](0003-use-kafka.md)`
+#[derive(Debug, Clone, PartialEq)]
+struct Link {
+    kind: String,
+    text: String,
+    target: String,
+}
+
+/// A record parsed from its source format, before being converted
+#[derive(Debug, Clone, Default)]
+struct SourceRecord {
+    source: PathBuf,
+    /// the directory of the record, relative to the imported directory (kept under `adr_src_dir`)
+    relative_dir: PathBuf,
+    /// 0 if the record has no id (a new one is then given)
+    id: usize,
+    title: String,
+    date: Option<String>,
+    status: Option<String>,
+    links: Vec<Link>,
+    tags: Vec<String>,
+    deciders: Vec<String>,
+    experts: Vec<String>,
+    stakeholders: Vec<String>,
+    body: String,
+}
+
+/// Imports the npryce adr-tools records (e.g. `doc/adr/0001-record-architecture-decisions.md`) available in `dir`
+/// into `adr_src_dir`. IDs, dates, statuses and supersede / amend links are kept.
+/// With `dry_run`, nothing is written and the planned operations are returned.
+pub fn import_adr_tools(cfg: &AdrToolConfig, dir: &Path, dry_run: bool) -> io::Result<Vec<ImportedAdr>> {
+    lazy_static! {
+        static ref RE_FILE: Regex = Regex::new(r"^(\d+)-.+\.md$").unwrap();
+    }
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .file_name()
+                    .map(|name| RE_FILE.is_match(&name.to_string_lossy()))
+                    .unwrap_or(false)
+        })
+        .collect();
+    paths.sort();
+
+    let mut records = Vec::new();
+    for path in paths {
+        let content = fs::read_to_string(&path)?;
+        let id = RE_FILE.captures(&path.file_name().unwrap().to_string_lossy()).unwrap()[1]
+            .parse()
+            .unwrap_or(0);
+        records.push(parse_adr_tools(&path, id, &content));
+    }
+
+    write_records(cfg, records, dry_run)
+}
+
+/// Imports the MADR records (e.g. `docs/decisions/0001-use-markdown-architectural-decision-records.md`) available in `dir`
+/// (and its sub directories, whose structure is kept under `adr_src_dir`). See `import_madr_like`.
+pub fn import_madr(cfg: &AdrToolConfig, dir: &Path, dry_run: bool) -> io::Result<Vec<ImportedAdr>> {
+    lazy_static! {
+        static ref RE_FILE: Regex = Regex::new(r"^\d+-.+\.md$").unwrap();
+    }
+
+    import_madr_like(cfg, dir, dry_run, |name| RE_FILE.is_match(name))
+}
+
+/// Imports the log4brains records (e.g. `docs/adr/backend/20200101-use-kafka.md`) available in `dir` (and its sub
+/// directories, e.g. the packages, whose structure is kept under `adr_src_dir`). See `import_madr_like`.
+pub fn import_log4brains(cfg: &AdrToolConfig, dir: &Path, dry_run: bool) -> io::Result<Vec<ImportedAdr>> {
+    import_madr_like(cfg, dir, dry_run, |name| {
+        name.ends_with(".md") && !["index.md", "readme.md", "template.md"].contains(&name.to_lowercase().as_str())
+    })
+}
+
+/// MADR and log4brains share the same layout: metadata as YAML front-matter (`status:`, `date:`, `deciders:`, `tags:`...)
+/// or as a list right after the title (`* Status: accepted`). Records without id (e.g. `20200101-use-kafka.md`) get the next available one.
+fn import_madr_like(
+    cfg: &AdrToolConfig,
+    dir: &Path,
+    dry_run: bool,
+    is_record: impl Fn(&str) -> bool,
+) -> io::Result<Vec<ImportedAdr>> {
+    lazy_static! {
+        static ref RE_ID: Regex = Regex::new(r"^(\d+)-").unwrap();
+    }
+
+    let mut records = Vec::new();
+    for entry in WalkDir::new(dir).follow_links(true).sort_by_file_name() {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !entry.file_type().is_file() || name.starts_with('.') || !is_record(&name) {
+            continue;
+        }
+
+        let content = fs::read_to_string(entry.path())?;
+        let mut record = parse_madr(entry.path(), &content);
+        record.relative_dir = entry
+            .path()
+            .parent()
+            .and_then(|parent| parent.strip_prefix(dir).ok())
+            .map(|parent| parent.to_path_buf())
+            .unwrap_or_default();
+        if let Some(cap) = RE_ID.captures(&name) {
+            //a YYYYMMDD prefix is a date, not an id
+            match NaiveDate::parse_from_str(&cap[1], "%Y%m%d") {
+                Ok(date) => {
+                    if record.date.is_none() {
+                        record.date = Some(date.format("%Y-%m-%d").to_string());
+                    }
+                }
+                Err(_) => record.id = cap[1].parse().unwrap_or(0),
+            }
+        }
+        records.push(record);
+    }
+
+    //records without id get the next ones, the oldest first
+    let mut next_id = list_all_adr(Path::new(&cfg.adr_src_dir))?
+        .iter()
+        .map(|adr| adr.file_id)
+        .chain(records.iter().map(|r| r.id))
+        .max()
+        .unwrap_or(0);
+    let mut without_id: Vec<&mut SourceRecord> = records.iter_mut().filter(|r| r.id == 0).collect();
+    without_id.sort_by(|a, b| a.date.cmp(&b.date).then(a.source.cmp(&b.source)));
+    for record in without_id {
+        next_id += 1;
+        record.id = next_id;
+    }
+
+    write_records(cfg, records, dry_run)
+}
+
+fn parse_madr(path: &Path, content: &str) -> SourceRecord {
+    lazy_static! {
+        static ref RE_TITLE: Regex = Regex::new(r"^#\s+(?:(?:ADR[-\s]?)?\d+[.:]?\s+)?(.+)$").unwrap();
+        static ref RE_METADATA: Regex =
+            Regex::new(r"^[*-]\s+([A-Za-z][A-Za-z ]*?)\s*:\s*(.*)$").unwrap();
+        static ref RE_LINK: Regex = Regex::new(r"\[([^\]]*)\]\(([^)]+)\)").unwrap();
+        static ref RE_DATE: Regex = Regex::new(r"^\d{4}-\d{2}-\d{2}$").unwrap();
+    }
+
+    let mut record = SourceRecord {
+        source: path.to_path_buf(),
+        ..Default::default()
+    };
+
+    let (metadata, body) = split_front_matter(content);
+    let mut metadata = metadata;
+    let mut lines = Vec::new();
+    let mut in_metadata = true;
+    for line in body.lines() {
+        if record.title.is_empty() {
+            if let Some(cap) = RE_TITLE.captures(line) {
+                record.title = cap[1].trim().to_string();
+                continue;
+            }
+        }
+        //MADR 2 / log4brains list of metadata right after the title
+        if in_metadata && !record.title.is_empty() {
+            if let Some(cap) = RE_METADATA.captures(line) {
+                let key = cap[1].trim().to_lowercase();
+                if ["status", "date", "deciders", "tags", "consulted", "informed"].contains(&key.as_str()) {
+                    metadata.insert(key, cap[2].trim().to_string());
+                    continue;
+                }
+            }
+            in_metadata = line.trim().is_empty() || RE_METADATA.is_match(line);
+        }
+        lines.push(line);
+    }
+    record.body = lines.join("\n").trim().to_string();
+
+    if let Some(status) = metadata.get("status") {
+        //e.g. superseded by [ADR-0005](0005-example.md)
+        for cap in RE_LINK.captures_iter(status) {
+            let kind = status[..cap.get(0).unwrap().start()].trim().to_lowercase();
+            record.links.push(Link {
+                kind,
+                text: cap[1].to_string(),
+                target: cap[2].to_string(),
+            });
+        }
+        let status = RE_LINK.replace_all(status, "").trim().to_string();
+        if !status.is_empty() {
+            record.status = Some(status);
+        }
+    }
+    record.date = metadata
+        .get("date")
+        .map(|date| date.trim_matches(|c| c == '"' || c == '\'').to_string())
+        .filter(|date| RE_DATE.is_match(date));
+    record.tags = split_values(metadata.get("tags"), true);
+    record.deciders = split_values(metadata.get("deciders"), false);
+    record.experts = split_values(metadata.get("consulted"), false);
+    record.stakeholders = split_values(metadata.get("informed"), false);
+
+    record
+}
+
+/// Returns the `key: value` entries of the YAML front-matter (lists are joined with `, `) and the rest of the content.
+fn split_front_matter(content: &str) -> (HashMap<String, String>, String) {
+    lazy_static! {
+        static ref RE_ENTRY: Regex = Regex::new(r"^([\w-]+)\s*:\s*(.*)$").unwrap();
+        static ref RE_ITEM: Regex = Regex::new(r"^\s+-\s+(.*)$").unwrap();
+    }
+
+    let mut metadata: HashMap<String, String> = HashMap::new();
+    let mut lines = content.lines();
+    if lines.next().map(|l| l.trim()) != Some("---") {
+        return (metadata, content.to_string());
+    }
+
+    let mut key = String::new();
+    let mut body = Vec::new();
+    let mut in_front_matter = true;
+    for line in lines {
+        if !in_front_matter {
+            body.push(line);
+        } else if line.trim() == "---" {
+            in_front_matter = false;
+        } else if let Some(cap) = RE_ENTRY.captures(line) {
+            key = cap[1].to_lowercase();
+            let value = cap[2].trim().trim_start_matches('[').trim_end_matches(']');
+            metadata.insert(key.clone(), value.to_string());
+        } else if let Some(cap) = RE_ITEM.captures(line) {
+            let value = metadata.entry(key.clone()).or_default();
+            if !value.is_empty() {
+                value.push_str(", ");
+            }
+            value.push_str(cap[1].trim());
+        }
+    }
+
+    (metadata, body.join("\n"))
+}
+
+/// Splits a list of values (comma separated, and also space separated for tags), placeholders such as `{list...}` are ignored
+fn split_values(val: Option<&String>, split_on_spaces: bool) -> Vec<String> {
+    let val = match val {
+        Some(val) if !val.trim().starts_with('{') => val,
+        _ => return Vec::new(),
+    };
+
+    val.split(|c: char| c == ',' || (split_on_spaces && c.is_whitespace()))
+        .map(|s| s.trim().trim_matches(|c| c == '"' || c == '\'' || c == '[' || c == ']').trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .collect()
+}
+
+fn parse_adr_tools(path: &Path, id: usize, content: &str) -> SourceRecord {
+    lazy_static! {
+        static ref RE_TITLE: Regex = Regex::new(r"^#\s+(?:\d+\.\s+)?(.+)$").unwrap();
+        static ref RE_DATE: Regex = Regex::new(r"^Date:\s*(\d{4}-\d{2}-\d{2})").unwrap();
+        static ref RE_SECTION: Regex = Regex::new(r"^##\s+(.+)$").unwrap();
+        static ref RE_LINK: Regex =
+            Regex::new(r"^([A-Za-z][A-Za-z ]*?)\s+\[([^\]]*)\]\(([^)]+)\)\s*$").unwrap();
+    }
+
+    let mut record = SourceRecord {
+        source: path.to_path_buf(),
+        id,
+        ..Default::default()
+    };
+
+    let mut in_status = false;
+    let mut body = Vec::new();
+    for line in content.lines() {
+        if record.title.is_empty() {
+            if let Some(cap) = RE_TITLE.captures(line) {
+                record.title = cap[1].trim().to_string();
+                continue;
+            }
+        }
+        if let Some(cap) = RE_SECTION.captures(line) {
+            in_status = cap[1].trim().eq_ignore_ascii_case("status");
+            if in_status {
+                continue;
+            }
+        }
+        if in_status {
+            let line = line.trim();
+            if let Some(cap) = RE_LINK.captures(line) {
+                record.links.push(Link {
+                    kind: cap[1].to_lowercase(),
+                    text: cap[2].to_string(),
+                    target: cap[3].to_string(),
+                });
+            } else if !line.is_empty() {
+                record.status = match record.status.take() {
+                    Some(status) => Some(format!("{} {}", status, line)),
+                    None => Some(line.to_string()),
+                };
+            }
+            continue;
+        }
+        if record.date.is_none() {
+            if let Some(cap) = RE_DATE.captures(line) {
+                record.date = Some(cap[1].to_string());
+                continue;
+            }
+        }
+        body.push(line);
+    }
+    record.body = body.join("\n").trim().to_string();
+
+    record
+}
+
+/// Maps a status (and the links of the record) onto adrust's `Status`. Returns the status, the links used as references
+/// and the links which have no equivalent
+fn map_status(
+    status: Option<&str>,
+    links: &[Link],
+    warnings: &mut Vec<String>,
+) -> (Status, Vec<Link>, Vec<Link>) {
+    let of_kind = |kind: &str| -> Vec<Link> {
+        links.iter().filter(|l| l.kind == kind).cloned().collect()
+    };
+    let unmapped: Vec<Link> = links
+        .iter()
+        .filter(|l| !["superseded by", "supersedes", "amended by", "amends"].contains(&l.kind.as_str()))
+        .cloned()
+        .collect();
+
+    for (kind, status) in [
+        ("superseded by", Status::SUPERSEDED),
+        ("amended by", Status::COMPLETED),
+    ] {
+        let references = of_kind(kind);
+        if !references.is_empty() {
+            return (status, references, unmapped);
+        }
+    }
+
+    let word = status.unwrap_or("").trim().to_lowercase();
+    let word = word.split_whitespace().next().unwrap_or("");
+    let status = match word {
+        "accepted" | "approved" | "decided" => {
+            for (kind, status) in [("supersedes", Status::SUPERSEDES), ("amends", Status::COMPLETES)] {
+                let references = of_kind(kind);
+                if !references.is_empty() {
+                    return (status, references, unmapped);
+                }
+            }
+            Status::DECIDED
+        }
+        "proposed" | "draft" | "wip" => Status::WIP,
+        "deprecated" | "rejected" | "obsolete" | "obsoleted" => Status::CANCELLED,
+        "superseded" => {
+            warnings.push(String::from("Status [superseded] without any link to the superseding record, imported as obsoleted"));
+            Status::CANCELLED
+        }
+        "" => {
+            warnings.push(String::from("No status found, imported as wip"));
+            Status::WIP
+        }
+        _ => {
+            warnings.push(format!("Unknown status [{}], imported as wip", status.unwrap_or("").trim()));
+            Status::WIP
+        }
+    };
+
+    (status, Vec::new(), unmapped)
+}
+
+/// Converts the records and writes them in `adr_src_dir` (existing files are not overwritten)
+fn write_records(cfg: &AdrToolConfig, records: Vec<SourceRecord>, dry_run: bool) -> io::Result<Vec<ImportedAdr>> {
+    let src_dir = Path::new(&cfg.adr_src_dir);
+
+    //first, the new paths (relative to adr_src_dir) by source path, so links can be rewritten
+    let mut paths: HashMap<String, PathBuf> = HashMap::new();
+    for record in records.iter() {
+        let id = match cfg.use_id_prefix {
+            true => Some(record.id),
+            false => None,
+        };
+        let name = format!("{}.adoc", format_decision_name_with_id(cfg, &record.title, id));
+        paths.insert(get_source_key(record), record.relative_dir.join(name));
+    }
+
+    let mut results = Vec::new();
+    for record in records {
+        let target = src_dir.join(&paths[&get_source_key(&record)]);
+        //xrefs are relative to the directory of the record
+        let names: HashMap<String, String> = paths
+            .iter()
+            .map(|(source, path)| (source.clone(), relative_path(path, &record.relative_dir)))
+            .collect();
+
+        let mut warnings = Vec::new();
+        let (status, references, unmapped) =
+            map_status(record.status.as_deref(), &record.links, &mut warnings);
+
+        let mut status_line = format!("*Status:* {{{}}}", status.as_str());
+        for reference in references.iter() {
+            match resolve_link(&paths, &reference.target, &record.relative_dir) {
+                Some(path) => {
+                    status_line.push(' ');
+                    status_line.push_str(&path.file_name().unwrap().to_string_lossy());
+                }
+                None => warnings.push(format!(
+                    "Link [{} {}] targets a record which is not imported",
+                    reference.kind, reference.target
+                )),
+            }
+        }
+        status_line.push_str("  *Date:* {adr-date}");
+
+        let date = match record.date {
+            Some(date) => date,
+            None => {
+                warnings.push(String::from("No date found, imported with today's date"));
+                Utc::now().format("%Y-%m-%d").to_string()
+            }
+        };
+
+        let mut content = adr_template::get_status_labels();
+        content.push_str(&format!(
+            ":adr-status: {}\n:adr-date: {}\n:adr-id: {}\n",
+            status.as_str(),
+            date,
+            record.id
+        ));
+        for (attribute, people) in [
+            (ATTR_DECIDERS, &record.deciders),
+            (ATTR_EXPERTS, &record.experts),
+            (ATTR_STAKEHOLDERS, &record.stakeholders),
+        ] {
+            if !people.is_empty() {
+                content.push_str(&format!(":{}: {}\n", attribute, people.join(", ")));
+            }
+        }
+        content.push_str(&format!("\n== {}\n\n{}\n\n", record.title, status_line));
+        for link in unmapped.iter() {
+            warnings.push(format!(
+                "Link [{}] has no adrust equivalent, kept as text",
+                link.kind
+            ));
+            content.push_str(&markdown_to_asciidoc(
+                format!("{} [{}]({})", capitalize(&link.kind), link.text, link.target).trim_start(),
+                &names,
+                &record.relative_dir,
+                &mut warnings,
+            ));
+            content.push_str("\n\n");
+        }
+        content.push_str(&markdown_to_asciidoc(&record.body, &names, &record.relative_dir, &mut warnings));
+        content.push('\n');
+        if !record.tags.is_empty() {
+            let tags: Vec<String> = record.tags.iter().map(|tag| format!("[tags]#{}#", tag)).collect();
+            content.push_str(&format!("\n{}\n", tags.join(" ")));
+        }
+
+        let created = !target.exists();
+        match (created, dry_run) {
+            (true, false) => {
+                fs::create_dir_all(target.parent().unwrap())?;
+                fs::write(&target, content)?;
+                info!(get_logger(), "Imported [{}] as [{}]", record.source.display(), target.display());
+            }
+            (true, true) => {}
+            (false, _) => warnings.push(String::from("Target file already exists, not imported")),
+        }
+
+        results.push(ImportedAdr {
+            source: record.source.display().to_string(),
+            target: target.display().to_string(),
+            id: record.id,
+            title: record.title,
+            status,
+            created,
+            warnings,
+        });
+    }
+
+    Ok(results)
+}
+
+/// `path` relative to `from_dir` (both being relative to `adr_src_dir`), e.g. `../backend/000002-use-kafka.adoc`
+fn relative_path(path: &Path, from_dir: &Path) -> String {
+    let path: Vec<_> = path.components().collect();
+    let from_dir: Vec<_> = from_dir.components().collect();
+    let common = path.iter().zip(from_dir.iter()).take_while(|(a, b)| a == b).count();
+
+    let mut relative = PathBuf::new();
+    for _ in common..from_dir.len() {
+        relative.push("..");
+    }
+    for component in &path[common..] {
+        relative.push(component);
+    }
+
+    relative.to_string_lossy().replace('\\', "/")
+}
+
+/// e.g. `Clarifies` for `clarifies`
+fn capitalize(val: &str) -> String {
+    let mut chars = val.chars();
+    match chars.next() {
+        Some(first) => format!("{}{}", first.to_uppercase(), chars.as_str()),
+        None => String::new(),
+    }
+}
+
+/// The path of the record relative to the imported directory, e.g. `backend/0002-use-kafka.md`
+fn get_source_key(record: &SourceRecord) -> String {
+    let name = record.source.file_name().unwrap_or_default();
+    record.relative_dir.join(name).to_string_lossy().replace('\\', "/")
+}
+
+/// The record (by source path, cf. `get_source_key`) targeted by a link of a record of `from_dir`. If there is no such
+/// path, the file name is enough when a single record has it
+fn resolve_link<'a, T>(records: &'a HashMap<String, T>, target: &str, from_dir: &Path) -> Option<&'a T> {
+    let mut path = PathBuf::new();
+    for component in from_dir.join(target.split('#').next().unwrap_or(target)).components() {
+        match component {
+            Component::ParentDir => {
+                path.pop();
+            }
+            Component::Normal(name) => path.push(name),
+            _ => {}
+        }
+    }
+    if let Some(record) = records.get(&path.to_string_lossy().replace('\\', "/")) {
+        return Some(record);
+    }
+
+    let name = link_file_name(target);
+    let mut candidates = records.iter().filter(|(source, _)| link_file_name(source) == name);
+    match (candidates.next(), candidates.next()) {
+        (Some((_, record)), None) => Some(record),
+        _ => None,
+    }
+}
+
+fn link_file_name(target: &str) -> &str {
+    let target = target.split('#').next().unwrap_or(target);
+    target.rsplit('/').next().unwrap_or(target)
+}
+
+/// Converts the (commonly used subset of) markdown to asciidoc: headings (one level down as the title is `==`), lists,
+/// emphasis, links, images and code blocks. Links to imported records become `xref`s given `names` (source path -> new file name,
+/// cf. `resolve_link`), `from_dir` being the directory of the record.
+/// Unsupported constructs (e.g. tables, html) are kept as is and reported in `warnings`.
+pub fn markdown_to_asciidoc(
+    md: &str,
+    names: &HashMap<String, String>,
+    from_dir: &Path,
+    warnings: &mut Vec<String>,
+) -> String {
+    lazy_static! {
+        static ref RE_HEADING: Regex = Regex::new(r"^(#{1,5})\s+(.+?)\s*#*$").unwrap();
+        static ref RE_FENCE: Regex = Regex::new(r"^\s*(```|~~~)\s*([\w+-]*)").unwrap();
+        static ref RE_UNORDERED: Regex = Regex::new(r"^(\s*)[-*+]\s+(.*)$").unwrap();
+        static ref RE_ORDERED: Regex = Regex::new(r"^(\s*)\d+[.)]\s+(.*)$").unwrap();
+        static ref RE_TABLE: Regex = Regex::new(r"^\s*\|.*\|\s*$").unwrap();
+        static ref RE_HTML: Regex = Regex::new(r"<(/?[a-zA-Z][a-zA-Z0-9]*|!--)[^>]*>").unwrap();
+    }
+
+    let mut lines = Vec::new();
+    let mut fence: Option<String> = None;
+    let mut has_table = false;
+    let mut has_html = false;
+    for line in md.lines() {
+        if let Some(marker) = &fence {
+            if line.trim_start().starts_with(marker.as_str()) {
+                fence = None;
+                lines.push(String::from("----"));
+            } else {
+                lines.push(line.to_string());
+            }
+            continue;
+        }
+        if let Some(cap) = RE_FENCE.captures(line) {
+            fence = Some(cap[1].to_string());
+            if !cap[2].is_empty() {
+                lines.push(format!("[source,{}]", &cap[2]));
+            }
+            lines.push(String::from("----"));
+            continue;
+        }
+
+        if RE_TABLE.is_match(line) {
+            has_table = true;
+            lines.push(line.to_string());
+            continue;
+        }
+        if RE_HTML.is_match(line) {
+            has_html = true;
+        }
+
+        let converted = if let Some(cap) = RE_HEADING.captures(line) {
+            format!("{} {}", "=".repeat(cap[1].len() + 1), convert_inline(&cap[2], names, from_dir))
+        } else if let Some(cap) = RE_UNORDERED.captures(line) {
+            format!("{} {}", "*".repeat(cap[1].len() / 2 + 1), convert_inline(&cap[2], names, from_dir))
+        } else if let Some(cap) = RE_ORDERED.captures(line) {
+            format!("{} {}", ".".repeat(cap[1].len() / 2 + 1), convert_inline(&cap[2], names, from_dir))
+        } else {
+            convert_inline(line, names, from_dir)
+        };
+        lines.push(converted);
+    }
+    if fence.is_some() {
+        lines.push(String::from("----"));
+    }
+
+    if has_table {
+        warnings.push(String::from("Markdown table kept as is"));
+    }
+    if has_html {
+        warnings.push(String::from("HTML kept as is"));
+    }
+
+    lines.join("\n")
+}
+
+fn convert_inline(line: &str, names: &HashMap<String, String>, from_dir: &Path) -> String {
+    lazy_static! {
+        static ref RE_IMAGE: Regex = Regex::new(r"!\[([^\]]*)\]\(([^)\s]+)[^)]*\)").unwrap();
+        static ref RE_LINK: Regex = Regex::new(r"\[([^\]]+)\]\(([^)\s]+)[^)]*\)").unwrap();
+        static ref RE_BOLD: Regex = Regex::new(r"(\*\*|__)([^*_]+?)(\*\*|__)").unwrap();
+        static ref RE_ITALIC: Regex = Regex::new(r"\*([^*\s][^*]*?)\*").unwrap();
+    }
+
+    //bold first (as a placeholder) so single * can be turned into italic
+    let line = RE_BOLD.replace_all(line, "\u{0}$2\u{0}");
+    let line = RE_ITALIC.replace_all(&line, "_${1}_");
+    let line = line.replace('\u{0}', "*");
+
+    let line = RE_IMAGE.replace_all(&line, "image:$2[$1]");
+    let line = RE_LINK.replace_all(&line, |cap: &Captures| {
+        let target = &cap[2];
+        match resolve_link(names, target, from_dir) {
+            Some(name) => format!("xref:{}[{}]", name, &cap[1]),
+            None if target.starts_with("http://") || target.starts_with("https://") => {
+                format!("{}[{}]", target, &cap[1])
+            }
+            None => format!("link:{}[{}]", target, &cap[1]),
+        }
+    });
+
+    line.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::fs;
+    use std::path::Path;
+
+    use adr_config::config::AdrToolConfig;
+    use tempdir::TempDir;
+
+    use crate::adr_repo::{build_adr, Status};
+
+    const ADR_TOOLS_1: &str = "# 1. Record architecture decisions
+
+Date: 2018-02-01
+
+## Status
+
+Accepted
+
+## Context
+
+We need to record the architectural decisions made on this project.
+
+## Decision
+
+We will use Architecture Decision Records, as [described by Michael Nygard](http://thinkrelevance.com/blog/2011/11/15/documenting-architecture-decisions).
+
+## Consequences
+
+See Michael Nygard's article, linked above. For a lightweight ADR toolset, see Nat Pryce's *adr-tools*.
+";
+
+    const ADR_TOOLS_2: &str = "# 2. Use PostgreSQL
+
+Date: 2018-03-04
+
+## Status
+
+Superseded by [3. Use Kafka](0003-use-kafka.md)
+
+## Context
+
+* **reliable** storage
+* cheap
+";
+
+    const ADR_TOOLS_3: &str = "# 3. Use Kafka
+
+Date: 2018-05-06
+
+## Status
+
+Accepted
+
+Supersedes [2. Use PostgreSQL](0002-use-postgresql.md)
+
+Clarifies [1. Record architecture decisions](0001-record-architecture-decisions.md)
+
+## Context
+
+| Option | Cost |
+|--------|------|
+| Kafka  | $$   |
+
+```java
+class Producer {}
+```
+";
+
+    const ADR_TOOLS_4: &str = "# 4. Use Redis
+
+## Status
+
+Pending review
+";
+
+    #[test]
+    fn test_import_adr_tools() {
+        let from = TempDir::new("my_adr_tools_folder").unwrap();
+        fs::write(from.path().join("0001-record-architecture-decisions.md"), ADR_TOOLS_1).unwrap();
+        fs::write(from.path().join("0002-use-postgresql.md"), ADR_TOOLS_2).unwrap();
+        fs::write(from.path().join("0003-use-kafka.md"), ADR_TOOLS_3).unwrap();
+        fs::write(from.path().join("0004-use-redis.md"), ADR_TOOLS_4).unwrap();
+        fs::write(from.path().join("README.md"), "# ADRs").unwrap();
+
+        let src = TempDir::new("my_src_folder").unwrap();
+        let cfg = AdrToolConfig {
+            adr_src_dir: format!("{}", src.path().display()),
+            id_prefix_width: 4,
+            ..Default::default()
+        };
+
+        let imported = super::import_adr_tools(&cfg, from.path(), false).unwrap();
+        assert_eq!(4, imported.len());
+        assert!(imported.iter().all(|i| i.created));
+        let statuses: Vec<Status> = imported.iter().map(|i| i.status).collect();
+        assert_eq!(
+            statuses,
+            vec![Status::DECIDED, Status::SUPERSEDED, Status::SUPERSEDES, Status::WIP]
+        );
+        assert!(imported[0].warnings.is_empty());
+        assert!(imported[1].warnings.is_empty());
+        assert_eq!(2, imported[2].warnings.len()); //clarifies and table
+        assert_eq!(2, imported[3].warnings.len()); //unknown status and no date
+
+        let adr = build_adr(src.path(), &src.path().join("0002-use-postgresql.adoc")).unwrap();
+        assert_eq!(adr.file_id, 2);
+        assert_eq!(adr.title, "Use PostgreSQL");
+        assert_eq!(adr.status, Status::SUPERSEDED);
+        assert_eq!(adr.date, "2018-03-04");
+        assert!(adr.content.contains("*Status:* {superseded} 0003-use-kafka.adoc  *Date:* {adr-date}"));
+        assert!(adr.content.contains("=== Context\n\n* *reliable* storage\n* cheap"));
+
+        let adr = build_adr(src.path(), &src.path().join("0003-use-kafka.adoc")).unwrap();
+        assert_eq!(adr.status, Status::SUPERSEDES);
+        assert!(adr.content.contains("{supersedes} 0002-use-postgresql.adoc"));
+        assert!(adr
+            .content
+            .contains("Clarifies xref:0001-record-architecture-decisions.adoc[1. Record architecture decisions]"));
+        assert!(adr.content.contains("[source,java]\n----\nclass Producer {}\n----"));
+
+        let adr = build_adr(src.path(), &src.path().join("0001-record-architecture-decisions.adoc")).unwrap();
+        assert!(adr.content.contains(
+            "http://thinkrelevance.com/blog/2011/11/15/documenting-architecture-decisions[described by Michael Nygard]"
+        ));
+        assert!(adr.content.contains("Nat Pryce's _adr-tools_."));
+
+        //already imported
+        let imported = super::import_adr_tools(&cfg, from.path(), false).unwrap();
+        assert!(imported.iter().all(|i| !i.created));
+    }
+
+    const MADR_1: &str = "---
+status: accepted
+date: 2022-04-01
+deciders:
+  - Alice
+  - Bob
+consulted: Carol
+tags: [security, \"deployment view\"]
+---
+# Use OAuth2
+
+## Context and Problem Statement
+
+We need to secure the API.
+";
+
+    const MADR_2: &str = "# Use Markdown Any Decision Records
+
+* Status: superseded by [ADR-0003](../0003-use-adrust.md)
+* Deciders: Alice, Dan
+* Date: 2021-01-02
+
+Technical Story: none
+
+## Decision Outcome
+";
+
+    #[test]
+    fn test_import_madr() {
+        let from = TempDir::new("my_madr_folder").unwrap();
+        fs::create_dir_all(from.path().join("security")).unwrap();
+        fs::write(from.path().join("security/0001-use-oauth2.md"), MADR_1).unwrap();
+        fs::write(from.path().join("security/0002-use-madr.md"), MADR_2).unwrap();
+        fs::write(from.path().join("0003-use-adrust.md"), "# Use adrust\n\n## Context\n").unwrap();
+        fs::write(from.path().join("adr-template.md"), "# {title}").unwrap();
+
+        let src = TempDir::new("my_src_folder").unwrap();
+        let cfg = AdrToolConfig {
+            adr_src_dir: format!("{}", src.path().display()),
+            id_prefix_width: 4,
+            ..Default::default()
+        };
+
+        //dry run, nothing is written
+        let imported = super::import_madr(&cfg, from.path(), true).unwrap();
+        assert_eq!(3, imported.len());
+        assert!(imported.iter().all(|i| i.created));
+        assert_eq!(0, fs::read_dir(src.path()).unwrap().count());
+
+        let imported = super::import_madr(&cfg, from.path(), false).unwrap();
+        let statuses: Vec<Status> = imported.iter().map(|i| i.status).collect();
+        assert_eq!(statuses, vec![Status::WIP, Status::DECIDED, Status::SUPERSEDED]);
+
+        let adr = build_adr(src.path(), &src.path().join("security/0001-use-oauth2.adoc")).unwrap();
+        assert_eq!(adr.title, "Use OAuth2");
+        assert_eq!(adr.date, "2022-04-01");
+        assert_eq!(adr.deciders, vec!["Alice", "Bob"]);
+        assert_eq!(adr.experts, vec!["Carol"]);
+        assert_eq!(adr.tags_array, vec!["security ", "deployment ", "view "]);
+        assert!(adr.content.contains("=== Context and Problem Statement"));
+
+        let adr = build_adr(src.path(), &src.path().join("security/0002-use-markdown-any-decision-records.adoc")).unwrap();
+        assert_eq!(adr.status, Status::SUPERSEDED);
+        assert_eq!(adr.date, "2021-01-02");
+        assert_eq!(adr.deciders, vec!["Alice", "Dan"]);
+        assert!(adr.content.contains("{superseded} 0003-use-adrust.adoc"));
+        assert!(!adr.content.contains("* Deciders"));
+        assert!(adr.content.contains("Technical Story: none"));
+    }
+
+    #[test]
+    fn test_import_log4brains() {
+        let from = TempDir::new("my_log4brains_folder").unwrap();
+        fs::create_dir_all(from.path().join("backend")).unwrap();
+        fs::write(
+            from.path().join("backend/20200301-use-kafka.md"),
+            "# Use Kafka\n\n- Status: proposed\n- Tags: backend, messaging\n\nSee [Postgres](../20200101-use-postgres.md).\n",
+        )
+        .unwrap();
+        fs::write(
+            from.path().join("20200101-use-postgres.md"),
+            "# Use Postgres\n\n- Status: deprecated\n",
+        )
+        .unwrap();
+        fs::write(from.path().join("index.md"), "# Architecture knowledge base").unwrap();
+
+        let src = TempDir::new("my_src_folder").unwrap();
+        fs::write(src.path().join("000004-existing.adoc"), "== Existing\n").unwrap();
+        let cfg = AdrToolConfig {
+            adr_src_dir: format!("{}", src.path().display()),
+            ..Default::default()
+        };
+
+        let imported = super::import_log4brains(&cfg, from.path(), false).unwrap();
+        assert_eq!(2, imported.len());
+        //the date prefix is not an id, the oldest record gets the next id
+        let postgres = imported.iter().find(|i| i.title == "Use Postgres").unwrap();
+        assert_eq!(postgres.id, 5);
+        assert_eq!(postgres.status, Status::CANCELLED);
+
+        let adr = build_adr(src.path(), &src.path().join("backend/000006-use-kafka.adoc")).unwrap();
+        assert_eq!(adr.status, Status::WIP);
+        assert_eq!(adr.date, "2020-03-01");
+        assert_eq!(adr.tags_array, vec!["backend ", "messaging "]);
+        assert!(adr.content.contains("See xref:../000005-use-postgres.adoc[Postgres]."));
+    }
+
+    #[test]
+    fn test_import_same_file_names() {
+        let from = TempDir::new("my_log4brains_folder").unwrap();
+        fs::create_dir_all(from.path().join("a")).unwrap();
+        fs::create_dir_all(from.path().join("b")).unwrap();
+        fs::write(from.path().join("a/20200101-use-db.md"), "# Use Postgres\n\n- Status: accepted\n").unwrap();
+        fs::write(from.path().join("b/20200101-use-db.md"), "# Use MongoDB\n\n- Status: accepted\n").unwrap();
+        fs::write(
+            from.path().join("b/20200201-use-replicas.md"),
+            "# Use replicas\n\n- Status: proposed\n\nSee [DB](20200101-use-db.md) and [the other DB](../a/20200101-use-db.md).\n",
+        )
+        .unwrap();
+
+        let src = TempDir::new("my_src_folder").unwrap();
+        let cfg = AdrToolConfig {
+            adr_src_dir: format!("{}", src.path().display()),
+            ..Default::default()
+        };
+
+        let imported = super::import_log4brains(&cfg, from.path(), false).unwrap();
+        assert_eq!(3, imported.len());
+        assert!(imported.iter().all(|i| i.created));
+
+        let name = |title: &str| {
+            let imported = imported.iter().find(|i| i.title == title).unwrap();
+            Path::new(&imported.target).file_name().unwrap().to_string_lossy().to_string()
+        };
+        let replicas = imported.iter().find(|i| i.title == "Use replicas").unwrap();
+        let content = fs::read_to_string(&replicas.target).unwrap();
+        assert!(content.contains(&format!("See xref:{}[DB]", name("Use MongoDB"))));
+        assert!(content.contains(&format!("xref:../a/{}[the other DB]", name("Use Postgres"))));
+    }
+
+    #[test]
+    fn test_capitalize() {
+        assert_eq!(super::capitalize("clarifies"), "Clarifies");
+        assert_eq!(super::capitalize("éclaire"), "Éclaire");
+        assert_eq!(super::capitalize(""), "");
+    }
+
+    #[test]
+    fn test_markdown_to_asciidoc() {
+        let mut names = HashMap::new();
+        names.insert(String::from("0002-b.md"), String::from("000002-b.adoc"));
+        let mut warnings = Vec::new();
+
+        let adoc = super::markdown_to_asciidoc(
+            "## Options\n\n1. first\n   * nested **bold**\n2. see [B](../adr/0002-b.md#context) and ![diagram](img/a.png)\n```\nraw *text*\n```",
+            &names,
+            Path::new(""),
+            &mut warnings,
+        );
+        assert_eq!(
+            adoc,
+            "=== Options\n\n. first\n** nested *bold*\n. see xref:000002-b.adoc[B] and image:img/a.png[diagram]\n----\nraw *text*\n----"
+        );
+        assert!(warnings.is_empty());
+    }
+}
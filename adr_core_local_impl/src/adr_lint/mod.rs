@@ -0,0 +1,757 @@
+use std::fs::{self};
+use std::io::{self};
+use std::path::Path;
+
+extern crate regex;
+use regex::Regex;
+
+use chrono::prelude::*;
+
+extern crate adr_config;
+use adr_config::config::AdrToolConfig;
+
+use crate::adr_git;
+use crate::adr_graph::get_references;
+use crate::adr_repo::{get_seq_id_from_name, list_all_adr, Adr, Status, ATTR_DATE, ATTR_STATUS, ATTR_TAGS};
+
+pub const RULE_OUTCOME_OPTION: &str = "outcome-names-option";
+pub const RULE_TITLE: &str = "title";
+pub const RULE_STATUS: &str = "status";
+pub const RULE_DATE: &str = "date";
+pub const RULE_LINK_MISSING: &str = "link-missing-target";
+pub const RULE_LINK_ONE_WAY: &str = "link-one-way";
+pub const RULE_LINK_STATUS: &str = "link-status";
+pub const RULE_REQUIRED_SECTION: &str = "required-section";
+pub const RULE_PLACEHOLDER: &str = "placeholder";
+pub const RULE_TITLE_LENGTH: &str = "title-length";
+pub const RULE_TAG_ALLOWED: &str = "tag-allowed";
+pub const RULE_TRAILING_WHITESPACE: &str = "trailing-whitespace";
+pub const RULE_DUPLICATE_ID: &str = "duplicate-id";
+
+#[derive(Debug, PartialEq, Copy, Clone, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    ERROR,
+    WARNING,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Severity::ERROR => "error",
+            Severity::WARNING => "warning",
+        }
+    }
+}
+
+/// A problem found in an ADR. `line` starts at 1 (0 if the problem is not related to a given line)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LintIssue {
+    pub file: String,
+    pub line: usize,
+    pub rule: String,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// The configurable rules (see the `lint_*` configuration items). The default has none of them, only the built-in rules are run.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LintRules {
+    pub required_sections: Vec<String>,
+    pub forbidden_strings: Vec<String>,
+    /// 0 means no limit
+    pub max_title_length: usize,
+    /// empty means any tag is allowed
+    pub allowed_tags: Vec<String>,
+    pub disabled_rules: Vec<String>,
+}
+
+impl LintRules {
+    pub fn from_config(cfg: &AdrToolConfig) -> LintRules {
+        let split = |val: &str| -> Vec<String> {
+            val.split(',')
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string())
+                .collect()
+        };
+
+        LintRules {
+            required_sections: split(&cfg.lint_required_sections),
+            forbidden_strings: split(&cfg.lint_forbidden_strings),
+            max_title_length: cfg.lint_max_title_length,
+            allowed_tags: split(&cfg.lint_allowed_tags),
+            disabled_rules: split(&cfg.lint_disabled_rules),
+        }
+    }
+
+    /// The allowed tag matching `tag` whatever its case, if `tag` is not allowed as is
+    fn allowed_tag_for(&self, tag: &str) -> Option<&String> {
+        match self.allowed_tags.iter().any(|t| t == tag) {
+            true => None,
+            false => self.allowed_tags.iter().find(|t| t.eq_ignore_ascii_case(tag)),
+        }
+    }
+}
+
+/// Run all the rules against the ADRs available in `base_path`
+pub fn lint_all_adr(base_path: &Path, rules: &LintRules) -> io::Result<Vec<LintIssue>> {
+    let mut issues = Vec::new();
+    for adr in list_all_adr(base_path)? {
+        issues.extend(lint_adr(&adr, rules));
+    }
+
+    Ok(issues)
+}
+
+pub fn lint_adr(adr: &Adr, rules: &LintRules) -> Vec<LintIssue> {
+    let mut issues = check_markers(adr);
+    if let Some(issue) = check_outcome_names_option(adr) {
+        issues.push(issue);
+    }
+    issues.extend(check_content(adr, rules));
+    issues.retain(|issue| !rules.disabled_rules.contains(&issue.rule));
+
+    issues
+}
+
+/// Checks the configurable rules: required sections, placeholders, title length, allowed tags (and trailing whitespace)
+fn check_content(adr: &Adr, rules: &LintRules) -> Vec<LintIssue> {
+    let issue = |rule: &str, line: usize, severity: Severity, message: String| LintIssue {
+        file: adr.path(),
+        line,
+        rule: rule.to_string(),
+        severity,
+        message,
+    };
+
+    let mut issues = Vec::new();
+    for section in rules.required_sections.iter() {
+        if Adr::get_section(&adr.content, section).is_none() {
+            issues.push(issue(
+                RULE_REQUIRED_SECTION,
+                0,
+                Severity::ERROR,
+                format!("Section [{}] is missing", section),
+            ));
+        }
+    }
+
+    let mut trailing_whitespaces = Vec::new();
+    for (index, line) in adr.content.lines().enumerate() {
+        let lowercase = line.to_lowercase();
+        for placeholder in rules.forbidden_strings.iter() {
+            if lowercase.contains(&placeholder.to_lowercase()) {
+                issues.push(issue(
+                    RULE_PLACEHOLDER,
+                    index + 1,
+                    Severity::WARNING,
+                    format!("Placeholder [{}] has not been replaced", placeholder),
+                ));
+            }
+        }
+        if line.ends_with([' ', '\t']) {
+            trailing_whitespaces.push(index + 1);
+        }
+    }
+    if let Some(first) = trailing_whitespaces.first() {
+        issues.push(issue(
+            RULE_TRAILING_WHITESPACE,
+            *first,
+            Severity::WARNING,
+            format!("{} line(s) with trailing whitespace (fixable)", trailing_whitespaces.len()),
+        ));
+    }
+
+    let length = adr.title.chars().count();
+    if rules.max_title_length > 0 && length > rules.max_title_length {
+        issues.push(issue(
+            RULE_TITLE_LENGTH,
+            find_line(&adr.content, &[&adr.title]),
+            Severity::WARNING,
+            format!("Title is {} characters long (max {})", length, rules.max_title_length),
+        ));
+    }
+
+    if !rules.allowed_tags.is_empty() {
+        for tag in adr.tags_array.iter().map(|t| t.trim()) {
+            if rules.allowed_tags.iter().any(|t| t == tag) {
+                continue;
+            }
+            let hint = match rules.allowed_tag_for(tag) {
+                Some(allowed) => format!(", use [{}] (fixable)", allowed),
+                None => format!(", allowed tags are [{}]", rules.allowed_tags.join(", ")),
+            };
+            issues.push(issue(
+                RULE_TAG_ALLOWED,
+                find_line(&adr.content, &[&format!("#{}#", tag), &format!(":{}:", ATTR_TAGS)]),
+                Severity::ERROR,
+                format!("Tag [{}] is not allowed{}", tag, hint),
+            ));
+        }
+    }
+
+    issues
+}
+
+/// Applies the safe fixes to the ADR: trailing whitespace, case of the allowed tags and missing date (set to today).
+/// Returns the fixed issues.
+pub fn fix_adr(adr: &mut Adr, rules: &LintRules) -> Vec<LintIssue> {
+    let file = adr.path();
+    let mut fixed = Vec::new();
+    let mut fix = |rule: &str, message: String| {
+        if !rules.disabled_rules.iter().any(|r| r == rule) {
+            fixed.push(LintIssue {
+                file: file.clone(),
+                line: 0,
+                rule: rule.to_string(),
+                severity: Severity::WARNING,
+                message,
+            });
+            true
+        } else {
+            false
+        }
+    };
+
+    let trimmed: Vec<&str> = adr.content.lines().map(|l| l.trim_end_matches([' ', '\t'])).collect();
+    let mut content = trimmed.join("\n");
+    if adr.content.ends_with('\n') {
+        content.push('\n');
+    }
+    if content != adr.content && fix(RULE_TRAILING_WHITESPACE, String::from("Trailing whitespace removed")) {
+        adr.content = content;
+    }
+
+    let tags: Vec<String> = adr.tags_array.iter().map(|t| t.trim().to_string()).collect();
+    for tag in tags.iter() {
+        if let Some(allowed) = rules.allowed_tag_for(tag) {
+            if fix(RULE_TAG_ALLOWED, format!("Tag [{}] renamed as [{}]", tag, allowed)) {
+                adr.content = adr
+                    .content
+                    .replace(&format!("[tags]#{}#", tag), &format!("[tags]#{}#", allowed));
+                if let Some(val) = adr.attributes.get(ATTR_TAGS).cloned() {
+                    let val: Vec<String> = val
+                        .split(',')
+                        .map(|t| match t.trim() == tag {
+                            true => allowed.to_string(),
+                            false => t.trim().to_string(),
+                        })
+                        .collect();
+                    adr.set_attribute(ATTR_TAGS, &val.join(", "));
+                }
+            }
+        }
+    }
+
+    if adr.date == "None" && fix(RULE_DATE, String::from("Date set to today")) {
+        adr.set_attribute(ATTR_DATE, &Utc::now().format("%Y-%m-%d").to_string());
+    }
+
+    fixed
+}
+
+/// Applies the safe fixes (see `fix_adr`) to the ADRs available in `base_path`, returns the fixed issues
+pub fn fix_all_adr(base_path: &Path, rules: &LintRules) -> io::Result<Vec<LintIssue>> {
+    let mut fixed = Vec::new();
+    for mut adr in list_all_adr(base_path)? {
+        let issues = fix_adr(&mut adr, rules);
+        if !issues.is_empty() {
+            fs::write(adr.path(), &adr.content)?;
+            fixed.extend(issues);
+        }
+    }
+
+    Ok(fixed)
+}
+
+/// Checks the title, status and date markers (or attributes) can be parsed, e.g. after the ADR has been edited
+pub fn check_markers(adr: &Adr) -> Vec<LintIssue> {
+    lazy_static! {
+        static ref RE_TITLE: Regex = Regex::new(r"(?m)^\s*=+ (.+)$").unwrap();
+        static ref RE_DATE: Regex = Regex::new(r"^[0-9]{4}-[0-9]{2}-[0-9]{2}$").unwrap();
+    }
+
+    let issue = |rule: &str, needles: &[&str], message: String| LintIssue {
+        file: adr.path(),
+        line: find_line(&adr.content, needles),
+        rule: rule.to_string(),
+        severity: Severity::ERROR,
+        message,
+    };
+
+    let mut issues = Vec::new();
+    match RE_TITLE.captures(&adr.content) {
+        Some(cap) if !cap[1].contains("{{") => {}
+        Some(cap) => issues.push(issue(
+            RULE_TITLE,
+            &[cap[0].trim()],
+            format!("Title [{}] has not been set", cap[1].trim()),
+        )),
+        None => issues.push(issue(RULE_TITLE, &[], String::from("No title (e.g. == my decision) found"))),
+    }
+    if adr.status == Status::NONE {
+        issues.push(issue(
+            RULE_STATUS,
+            &[&format!(":{}:", ATTR_STATUS), "*Status:*"],
+            String::from("Unable to get a known status (e.g. :adr-status: wip or *Status:* {wip})"),
+        ));
+    }
+    if !RE_DATE.is_match(&adr.date) {
+        issues.push(issue(
+            RULE_DATE,
+            &[&format!(":{}:", ATTR_DATE), "*Date:*"],
+            format!("Unable to get a date formatted as YYYY-MM-DD (got [{}])", adr.date),
+        ));
+    }
+
+    issues
+}
+
+/// Validates the staged Decision Records (see the pre-commit hook installed by `adr hooks install`): their title, status
+/// and date must be parsed, their id must be unique and their references must point to existing Decision Records
+pub fn check_staged(cfg: &AdrToolConfig) -> io::Result<Vec<LintIssue>> {
+    let staged = adr_git::get_staged_adrs(cfg)?;
+    if staged.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    //what will be committed, not the working tree (e.g. the untracked drafts)
+    let adrs = adr_git::list_all_staged_adr(cfg)?;
+
+    let mut issues = Vec::new();
+    for adr in staged.iter() {
+        issues.extend(check_markers(adr));
+
+        let others: Vec<&Adr> = adrs
+            .iter()
+            .filter(|other| adr.file_id > 0 && other.file_id == adr.file_id && other.file_path != adr.file_path)
+            .collect();
+        if !others.is_empty() {
+            let paths: Vec<&str> = others.iter().map(|other| other.file_path.as_str()).collect();
+            issues.push(LintIssue {
+                file: adr.path(),
+                line: 0,
+                rule: RULE_DUPLICATE_ID.to_string(),
+                severity: Severity::ERROR,
+                message: format!("Id [{}] is also used by [{}]", adr.file_id, paths.join(", ")),
+            });
+        }
+    }
+    let staged_paths: Vec<String> = staged.iter().map(|adr| adr.path()).collect();
+    issues.extend(
+        check_links(&adrs)
+            .into_iter()
+            .filter(|issue| issue.rule == RULE_LINK_MISSING && staged_paths.contains(&issue.file)),
+    );
+
+    Ok(issues)
+}
+
+/// Machine-readable report, e.g. for CI
+pub fn to_json(issues: &[LintIssue]) -> io::Result<String> {
+    serde_json::to_string_pretty(issues).map_err(io::Error::other)
+}
+
+/// Checks the links between the ADRs available in `base_path`, see `check_links`
+pub fn check_all_links(base_path: &Path) -> io::Result<Vec<LintIssue>> {
+    let adrs = list_all_adr(base_path)?;
+    Ok(check_links(&adrs))
+}
+
+/// Resolves the `{status} file-name.adoc` references (see `Adr::add_reference`) and the `xref:` links of the ADRs. Reports
+/// the missing targets (e.g. a renamed or deleted ADR), the one-way references (A superseded by B, but B does not
+/// supersede A) and the inconsistent statuses (e.g. A superseded by B, but B is still wip)
+pub fn check_links(adrs: &[Adr]) -> Vec<LintIssue> {
+    lazy_static! {
+        static ref RE_XREF: Regex = Regex::new(r"xref:([^\s\[#]+\.adoc)").unwrap();
+    }
+
+    let phrase = |status: &Status| match status {
+        Status::SUPERSEDED => "superseded by",
+        Status::COMPLETED => "completed by",
+        _ => status.as_str(),
+    };
+
+    let name_pattern = adr_config::config::get_config().name_pattern;
+    let mut issues = Vec::new();
+    for adr in adrs.iter() {
+        let issue = |rule: &str, needle: &str, message: String| LintIssue {
+            file: adr.path(),
+            line: find_line(&adr.content, &[needle]),
+            rule: rule.to_string(),
+            severity: Severity::ERROR,
+            message,
+        };
+
+        let references = get_references(adr);
+        for (status, target) in references.iter() {
+            let others: Vec<&Adr> = adrs.iter().filter(|a| &a.file_name == target).collect();
+            let other = match others.first() {
+                Some(other) => *other,
+                None => {
+                    //e.g. renamed, same id but another title
+                    let id = get_seq_id_from_name(target, &name_pattern).unwrap_or(0);
+                    let hint = match adrs.iter().find(|a| id > 0 && a.file_id == id) {
+                        Some(candidate) => format!(" (renamed as [{}]?)", candidate.file_name),
+                        None => String::new(),
+                    };
+                    issues.push(issue(
+                        RULE_LINK_MISSING,
+                        target,
+                        format!("[{}] references [{}] which does not exist{}", status.as_str(), target, hint),
+                    ));
+                    continue;
+                }
+            };
+
+            let reverse = match status {
+                Status::SUPERSEDED => Status::SUPERSEDES,
+                Status::SUPERSEDES => Status::SUPERSEDED,
+                Status::COMPLETED => Status::COMPLETES,
+                _ => Status::COMPLETED,
+            };
+            if !get_references(other).contains(&(reverse, adr.file_name.clone())) {
+                issues.push(issue(
+                    RULE_LINK_ONE_WAY,
+                    target,
+                    format!(
+                        "[{}] {} [{}] but [{}] does not say it {} [{}]",
+                        adr.file_name,
+                        phrase(status),
+                        other.file_name,
+                        other.file_name,
+                        phrase(&reverse),
+                        adr.file_name
+                    ),
+                ));
+            }
+
+            //the ADR superseding or completing another one must be decided, the superseded one must not be in force anymore
+            let consistent = match status {
+                Status::SUPERSEDED | Status::COMPLETED => other.status.is_decided(),
+                Status::SUPERSEDES => matches!(other.status, Status::SUPERSEDED | Status::CANCELLED),
+                _ => other.status.is_decided(),
+            };
+            if !consistent {
+                issues.push(issue(
+                    RULE_LINK_STATUS,
+                    target,
+                    format!(
+                        "[{}] {} [{}] whose status is [{}]",
+                        adr.file_name,
+                        phrase(status),
+                        other.file_name,
+                        other.status.as_str()
+                    ),
+                ));
+            }
+        }
+
+        //e.g. superseded but by nothing
+        if matches!(adr.status, Status::SUPERSEDED | Status::SUPERSEDES | Status::COMPLETED | Status::COMPLETES)
+            && !references.iter().any(|(status, _)| *status == adr.status)
+        {
+            issues.push(issue(
+                RULE_LINK_STATUS,
+                "*Status:*",
+                format!("Status is [{}] but no Decision Record is referenced", adr.status.as_str()),
+            ));
+        }
+
+        let dir = Path::new(&adr.path()).parent().map(|p| p.to_path_buf()).unwrap_or_default();
+        for cap in RE_XREF.captures_iter(&adr.content) {
+            if !dir.join(&cap[1]).exists() {
+                issues.push(issue(
+                    RULE_LINK_MISSING,
+                    &cap[0],
+                    format!("xref [{}] does not exist", &cap[1]),
+                ));
+            }
+        }
+    }
+
+    issues
+}
+
+/// Returns the line (starting at 1) of the first line containing one of `needles`, 0 if none
+fn find_line(content: &str, needles: &[&str]) -> usize {
+    content
+        .lines()
+        .position(|line| needles.iter().any(|needle| line.contains(needle)))
+        .map(|index| index + 1)
+        .unwrap_or(0)
+}
+
+/// A decided ADR must go with one of its considered options
+fn check_outcome_names_option(adr: &Adr) -> Option<LintIssue> {
+    if !adr.status.is_decided() || adr.options.is_empty() || adr.chosen_option().is_some() {
+        return None;
+    }
+
+    let line = match Adr::get_section(&adr.content, "decision outcome") {
+        Some((index, _)) => index + 1,
+        None => 0,
+    };
+    let options: Vec<&str> = adr.options.iter().map(|o| o.name.as_str()).collect();
+
+    Some(LintIssue {
+        file: adr.path(),
+        line,
+        rule: RULE_OUTCOME_OPTION.to_string(),
+        severity: Severity::ERROR,
+        message: format!(
+            "Decision outcome [{}] does not name one of the considered options [{}]",
+            adr.outcome.as_deref().unwrap_or("None"),
+            options.join(", ")
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adr_lint::*;
+    use std::fs;
+
+    const ADOC_DECIDED: &str = "== my decision
+
+*Status:* {decided}  *Date:* 2019-10-28
+
+=== Considered Options
+
+[cols=\",\",options=\"header\",%autowidth]
+|===
+|Option |Description
+|Option #1 |Manage
+|Option #2 |Manage
+|===
+
+=== Decision Outcome
+
+The decision goes with *Option #2* :
+
+* It is likely...
+";
+
+    #[test]
+    fn test_outcome_names_option() {
+        let adr = Adr::from(
+            "base_path".to_string(),
+            "a_path.adoc".to_string(),
+            ADOC_DECIDED.to_string(),
+            "",
+        );
+        assert!(lint_adr(&adr, &LintRules::default()).is_empty());
+
+        let adr = Adr::from(
+            "base_path".to_string(),
+            "a_path.adoc".to_string(),
+            ADOC_DECIDED.replace("*Option #2*", "*Option #3*"),
+            "",
+        );
+        let issues = lint_adr(&adr, &LintRules::default());
+        assert_eq!(1, issues.len());
+        assert_eq!(RULE_OUTCOME_OPTION, issues[0].rule);
+        assert_eq!(Severity::ERROR, issues[0].severity);
+        assert_eq!(14, issues[0].line);
+
+        //not decided yet, nothing to say
+        let adr = Adr::from(
+            "base_path".to_string(),
+            "a_path.adoc".to_string(),
+            ADOC_DECIDED
+                .replace("*Option #2*", "*Option #3*")
+                .replace("{decided}", "{wip}"),
+            "",
+        );
+        assert!(lint_adr(&adr, &LintRules::default()).is_empty());
+    }
+
+    #[test]
+    fn test_configurable_rules() {
+        let rules = LintRules {
+            required_sections: vec![String::from("Context and Problem Statement"), String::from("Decision Outcome")],
+            forbidden_strings: vec![String::from("bla bla")],
+            max_title_length: 10,
+            allowed_tags: vec![String::from("Security")],
+            disabled_rules: vec![],
+        };
+        let adr = Adr::from(
+            "base_path".to_string(),
+            "a_path.adoc".to_string(),
+            format!("{}\nBla bla  \n\n[tags]#security# [tags]#data#\n", ADOC_DECIDED),
+            "",
+        );
+
+        let issues = lint_adr(&adr, &rules);
+        let rules_found: Vec<(&str, usize, Severity)> =
+            issues.iter().map(|i| (i.rule.as_str(), i.line, i.severity)).collect();
+        assert_eq!(
+            rules_found,
+            vec![
+                (RULE_REQUIRED_SECTION, 0, Severity::ERROR),
+                (RULE_PLACEHOLDER, 20, Severity::WARNING),
+                (RULE_TRAILING_WHITESPACE, 20, Severity::WARNING),
+                (RULE_TITLE_LENGTH, 1, Severity::WARNING),
+                (RULE_TAG_ALLOWED, 22, Severity::ERROR),
+                (RULE_TAG_ALLOWED, 22, Severity::ERROR),
+            ]
+        );
+        assert!(issues[4].message.contains("use [Security]"));
+
+        let mut disabled = rules.clone();
+        disabled.disabled_rules = vec![String::from(RULE_PLACEHOLDER), String::from(RULE_TAG_ALLOWED)];
+        assert_eq!(3, lint_adr(&adr, &disabled).len());
+    }
+
+    #[test]
+    fn test_fix_adr() {
+        let rules = LintRules {
+            allowed_tags: vec![String::from("Security")],
+            ..Default::default()
+        };
+        let mut adr = Adr::from(
+            "base_path".to_string(),
+            "a_path.adoc".to_string(),
+            String::from(":adr-status: wip\n:adr-tags: security, data\n\n== my decision  \n\n*Status:* {wip}\n"),
+            "",
+        );
+
+        let fixed = fix_adr(&mut adr, &rules);
+        let rules_fixed: Vec<&str> = fixed.iter().map(|i| i.rule.as_str()).collect();
+        assert_eq!(rules_fixed, vec![RULE_TRAILING_WHITESPACE, RULE_TAG_ALLOWED, RULE_DATE]);
+        let today = Utc::now().format("%Y-%m-%d").to_string();
+        assert_eq!(
+            adr.content,
+            format!(":adr-status: wip\n:adr-tags: Security, data\n:adr-date: {}\n\n== my decision\n\n*Status:* {{wip}}\n", today)
+        );
+
+        //the tag not allowed cannot be fixed
+        let adr = Adr::from("base_path".to_string(), "a_path.adoc".to_string(), adr.content, "");
+        let issues = lint_adr(&adr, &rules);
+        assert_eq!(1, issues.len());
+        assert!(issues[0].message.contains("Tag [data] is not allowed"));
+    }
+
+    #[test]
+    fn test_check_staged() {
+        let dir = tempdir::TempDir::new("my_git_folder").unwrap();
+        crate::adr_git::git(dir.path(), &["init", "-q"]).unwrap();
+        let write = |name: &str, content: &str| fs::write(dir.path().join(name), content).unwrap();
+        write("000001-use-postgres.adoc", ":adr-status: decided\n:adr-date: 2024-01-02\n\n== Use Postgres\n");
+        crate::adr_git::git(dir.path(), &["add", "000001-use-postgres.adoc"]).unwrap();
+        crate::adr_git::git(
+            dir.path(),
+            &["-c", "user.name=John Doe", "-c", "user.email=john@doe.com", "commit", "-q", "-m", "Use Postgres"],
+        )
+        .unwrap();
+        write("000002-use-kafka.adoc", ":adr-status: done\n:adr-date: 2024-01-02\n\n== Use Kafka\n");
+        write(
+            "000001-use-avro.adoc",
+            ":adr-status: completes\n:adr-date: 2024-01-02\n\n== Use Avro\n\n*Status:* {completes} 000003-use-redis.adoc\n",
+        );
+        crate::adr_git::git(dir.path(), &["add", "000002-use-kafka.adoc", "000001-use-avro.adoc"]).unwrap();
+        //not committed: the referenced ADR is missing and the draft id is not a duplicate
+        write("000003-use-redis.adoc", ":adr-status: decided\n:adr-date: 2024-01-02\n\n== Use Redis\n");
+        write("000002-draft.adoc", ":adr-status: wip\n:adr-date: 2024-01-02\n\n== Draft\n");
+
+        let cfg = AdrToolConfig {
+            adr_src_dir: format!("{}", dir.path().display()),
+            ..Default::default()
+        };
+        let issues = check_staged(&cfg).unwrap();
+        let rules: Vec<(&str, &str)> = issues
+            .iter()
+            .map(|i| (Path::new(&i.file).file_name().unwrap().to_str().unwrap(), i.rule.as_str()))
+            .collect();
+        assert_eq!(
+            rules,
+            vec![
+                ("000001-use-avro.adoc", RULE_DUPLICATE_ID),
+                ("000002-use-kafka.adoc", RULE_STATUS),
+                ("000001-use-avro.adoc", RULE_LINK_MISSING),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_markers() {
+        let adr = Adr::from(
+            "base_path".to_string(),
+            "a_path.adoc".to_string(),
+            ADOC_DECIDED.to_string(),
+            "",
+        );
+        assert!(check_markers(&adr).is_empty());
+
+        let adr = Adr::from(
+            "base_path".to_string(),
+            "a_path.adoc".to_string(),
+            ADOC_DECIDED
+                .replace("== my decision", "== {{title}}")
+                .replace("{decided}", "{decidd}")
+                .replace("2019-10-28", "28/10/2019"),
+            "",
+        );
+        let issues = check_markers(&adr);
+        let rules: Vec<&str> = issues.iter().map(|i| i.rule.as_str()).collect();
+        assert_eq!(rules, vec![RULE_TITLE, RULE_STATUS, RULE_DATE]);
+        assert_eq!(1, issues[0].line);
+        assert_eq!(3, issues[1].line);
+        assert_eq!(3, issues[2].line);
+
+        let adr = Adr::from(
+            "base_path".to_string(),
+            "a_path.adoc".to_string(),
+            String::from("no title, no status"),
+            "",
+        );
+        let issues = check_markers(&adr);
+        assert_eq!(3, issues.len());
+        assert_eq!(0, issues[0].line);
+    }
+
+    #[test]
+    fn test_check_links() {
+        let adr = |file_path: &str, status: &str, references: &str| {
+            Adr::from(
+                "/tmp/adrs".to_string(),
+                file_path.to_string(),
+                format!(":adr-status: {}\n\n== a decision\n\n*Status:* {{{}}}{}  *Date:* 2019-10-28\n", status, status, references),
+                "",
+            )
+        };
+
+        let adrs = vec![
+            adr("000001-use-postgres.adoc", "superseded", " 000002-use-kafka.adoc"),
+            adr("data/000002-use-kafka.adoc", "supersedes", " 000001-use-postgres.adoc"),
+        ];
+        assert!(check_links(&adrs).is_empty());
+
+        let adrs = vec![
+            //superseded by a wip ADR which does not say it supersedes it
+            adr("000001-use-postgres.adoc", "superseded", " 000002-use-kafka.adoc"),
+            adr("data/000002-use-kafka.adoc", "wip", ""),
+            //renamed
+            adr("000003-use-avro.adoc", "completes", " 000004-use-json.adoc"),
+            adr("000004-use-json-schema.adoc", "completed", ""),
+        ];
+        let issues = check_links(&adrs);
+        let rules: Vec<(&str, &str)> = issues
+            .iter()
+            .map(|i| (i.file.trim_start_matches("/tmp/adrs/"), i.rule.as_str()))
+            .collect();
+        assert_eq!(
+            rules,
+            vec![
+                ("000001-use-postgres.adoc", RULE_LINK_ONE_WAY),
+                ("000001-use-postgres.adoc", RULE_LINK_STATUS),
+                ("000003-use-avro.adoc", RULE_LINK_MISSING),
+                ("000004-use-json-schema.adoc", RULE_LINK_STATUS),
+            ]
+        );
+        assert_eq!(5, issues[0].line);
+        assert!(issues[2].message.contains("renamed as [000004-use-json-schema.adoc]"));
+        assert!(issues[1].message.contains("whose status is [wip]"));
+    }
+}
@@ -2,21 +2,27 @@ extern crate slog;
 extern crate slog_term;
 use slog::*;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs::{self};
 use std::io::{self};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 extern crate regex;
 use regex::Regex;
 
 use walkdir::{DirEntry, WalkDir};
 
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
 extern crate adr_config;
-use adr_config::config::AdrToolConfig;
+use adr_config::config::{AdrToolConfig, ID_STRATEGY_GIT, ID_STRATEGY_TIMESTAMP};
 
 use chrono::prelude::*;
 
+use crate::adr_git;
+use crate::adr_template;
+
 fn get_logger() -> slog::Logger {
     let cfg: AdrToolConfig = adr_config::config::get_config();
 
@@ -41,12 +47,44 @@ fn get_logger() -> slog::Logger {
 /// *
 ///
 pub fn create_adr(cfg: AdrToolConfig, path: Option<&str>, title: &str) -> io::Result<bool> {
-    let adr_template_dir = &cfg.adr_template_dir.as_str();
-    let adr_template_file = &cfg.adr_template_file.as_str();
+    create_adr_from_template(cfg, path, title, None, &HashMap::new()).map(|created| created.is_some())
+}
 
-    let path_to_template = Path::new(adr_template_dir);
-    let path_to_template = path_to_template.join(adr_template_file);
-    let path_to_template = path_to_template.as_path();
+/// Creates the file based on the template `template` (cf. `adr_template::get_template`), or on `adr_template_file` if None.
+/// Returns the path of the created file, None if not created (e.g. target file already exists...)
+///
+/// # Arguments
+///
+/// * `cfg` - The whole config object
+/// * `title`- the title of the ADR (specified by the user)
+/// * `vars` - the template variables to add to (or to override) the default ones (title, id, date, author, tags, slug, deciders)
+///
+pub fn create_adr_from_template(
+    cfg: AdrToolConfig,
+    path: Option<&str>,
+    title: &str,
+    template: Option<&str>,
+    vars: &HashMap<String, String>,
+) -> io::Result<Option<PathBuf>> {
+    let template = match template {
+        Some(name) => adr_template::get_template(&cfg, name)?,
+        None => {
+            let path_to_template = Path::new(&cfg.adr_template_dir).join(&cfg.adr_template_file);
+            if !path_to_template.exists() {
+                error!(
+                    get_logger(),
+                    "[{}] was not found",
+                    path_to_template.to_string_lossy()
+                );
+                return Ok(None);
+            }
+            adr_template::Template {
+                name: cfg.adr_template_file.clone(),
+                content: fs::read_to_string(&path_to_template)?,
+                path: Some(path_to_template.display().to_string()),
+            }
+        }
+    };
 
     let src_dir = Path::new(&cfg.adr_src_dir);
 
@@ -63,72 +101,146 @@ pub fn create_adr(cfg: AdrToolConfig, path: Option<&str>, title: &str) -> io::Re
         }
     };
 
-    let is_target_file = target_path.is_file();
-    if !is_target_file {
-        if path_to_template.exists() {
-            match fs::copy(path_to_template, &target_path) {
-                Ok(_val) => debug!(
-                    get_logger(),
-                    "Copy template file from [{:?}] to [{:?}]", &path_to_template, &target_path
-                ),
-                Err(_why) => error!(
-                    get_logger(),
-                    "Unable to copy template from [{:?}] to [{:?}]",
-                    &path_to_template,
-                    &target_path
-                ),
-            };
-            //build the Adr (and force the parsing)
-            let mut new_adr = match build_adr(Path::new(&cfg.adr_src_dir), &target_path) {
-                Ok(adr) => adr,
-                Err(why) => {
-                    error!(
-                        get_logger(),
-                        "Got error [{:?}] while getting ADR [{:?}]", why, target_path
-                    );
-                    panic!();
-                }
-            };
+    if target_path.is_file() {
+        error!(
+            get_logger(),
+            "Decision already exists. Please use another name",
+        );
+        return Ok(None);
+    }
 
-            new_adr.update_title(title);
+    let id = match get_seq_id_from_name_with_pattern(&name, &cfg.name_pattern) {
+        Ok(id) if id > 0 => format!("{:0>width$}", id, width = cfg.id_prefix_width),
+        _ => String::new(),
+    };
+    let mut template_vars = HashMap::new();
+    template_vars.insert(adr_template::VAR_TITLE.to_string(), title.to_string());
+    template_vars.insert(adr_template::VAR_ID.to_string(), id);
+    template_vars.insert(adr_template::VAR_DATE.to_string(), Utc::now().format("%Y-%m-%d").to_string());
+    let author = adr_template::get_author();
+    template_vars.insert(adr_template::VAR_DECIDERS.to_string(), author.clone());
+    template_vars.insert(adr_template::VAR_AUTHOR.to_string(), author);
+    template_vars.insert(adr_template::VAR_TAGS.to_string(), String::new());
+    template_vars.insert(adr_template::VAR_SLUG.to_string(), slugify(title));
+    template_vars.extend(vars.iter().map(|(k, v)| (k.to_string(), v.to_string())));
 
-            debug!(get_logger(), "Want to create ADR {:?}", &target_path);
-            match fs::write(&target_path, new_adr.content) {
-                Ok(_val) => info!(get_logger(), "New ADR [{:?}] created", target_path),
-                Err(why) => {
-                    error!(
-                        get_logger(),
-                        "Unable to create ADR [{:?}] - error [{:?}]", target_path, why
-                    );
-                }
-            };
-        //
-        } else {
+    debug!(
+        get_logger(),
+        "Render template [{}] to [{:?}]", &template.name, &target_path
+    );
+    let content = adr_template::render(&template.content, &template_vars);
+
+    //build the Adr (and force the parsing)
+    let splitted_file_path = split_path(src_dir, &target_path);
+    let mut new_adr = Adr::from(
+        String::from(splitted_file_path.0.to_str().unwrap()),
+        String::from(splitted_file_path.1.to_str().unwrap()),
+        content,
+        &cfg.name_pattern,
+    );
+    //legacy templates do not use the {{title}} variable
+    if new_adr.title != title {
+        new_adr.update_title(title);
+    }
+    //nor the {{tags}} and {{deciders}} ones, specified values are then declared as attributes
+    for (var, attribute) in [
+        (adr_template::VAR_TAGS, ATTR_TAGS),
+        (adr_template::VAR_DECIDERS, ATTR_DECIDERS),
+    ] {
+        match vars.get(var) {
+            Some(val) if !val.is_empty() && !adr_template::uses_variable(&template.content, var) => {
+                new_adr.set_attribute(attribute, val);
+            }
+            _ => {}
+        }
+    }
+
+    debug!(get_logger(), "Want to create ADR {:?}", &target_path);
+    match fs::write(&target_path, new_adr.content) {
+        Ok(_val) => info!(get_logger(), "New ADR [{:?}] created", target_path),
+        Err(why) => {
             error!(
                 get_logger(),
-                "[{}] was not found",
-                path_to_template.to_string_lossy()
+                "Unable to create ADR [{:?}] - error [{:?}]", target_path, why
             );
+            return Err(why);
         }
-    } else {
-        error!(
-            get_logger(),
-            "Decision already exists. Please use another name",
-        );
-    }
+    };
 
-    Ok(!is_target_file)
+    Ok(Some(target_path))
 }
 
-fn get_seq_id_from_name(name: &str) -> Result<usize> {
+/// Build the regex matching the file names generated by `pattern` (cf. AdrToolConfig.name_pattern), where the first group is the id.
+/// Returns None if the pattern does not contain any `{id}`
+fn get_name_pattern_regex(pattern: &str) -> Option<Regex> {
     lazy_static! {
-        static ref RE: Regex = Regex::new(r"^(\d+)-{1}").unwrap();
+        static ref RE_PLACEHOLDER: Regex = Regex::new(r"\{(id|slug|date)\}").unwrap();
+    }
+
+    let pattern = pattern.trim_end_matches(".adoc");
+    if !pattern.contains("{id}") {
+        return None;
+    }
+
+    let mut regex = String::from("^");
+    let mut last = 0;
+    let mut has_id = false;
+    for cap in RE_PLACEHOLDER.captures_iter(pattern) {
+        let placeholder = cap.get(0).unwrap();
+        regex.push_str(regex::escape(&pattern[last..placeholder.start()]).as_str());
+        regex.push_str(match &cap[1] {
+            "id" if !has_id => {
+                has_id = true;
+                r"(\d+)"
+            }
+            "id" => r"\d+",
+            "date" => r"\d{4}-\d{2}-\d{2}",
+            _ => ".+",
+        });
+        last = placeholder.end();
     }
+    regex.push_str(regex::escape(&pattern[last..]).as_str());
+    regex.push_str(r"(\.adoc)?$");
+
+    Regex::new(regex.as_str()).ok()
+}
+
+/// The patterns giving the id of a file name: `name_pattern` then the default `{id}-{slug}` one (e.g. for the ADRs
+/// created before `name_pattern` was changed). None if `name_pattern` has no `{id}` (e.g. `{date}-{slug}`)
+fn get_id_patterns(name_pattern: &str) -> Vec<&str> {
+    let name_pattern = match name_pattern.is_empty() {
+        true => "{id}-{slug}",
+        false => name_pattern,
+    };
+    match name_pattern.contains("{id}") {
+        true => vec![name_pattern, "{id}-{slug}"],
+        false => vec![],
+    }
+}
+
+/// Returns the id of the ADR given its file name and the configured `name_pattern`, 0 if there is no id
+/// (e.g. `name_pattern` is `{date}-{slug}`)
+pub(crate) fn get_seq_id_from_name(name: &str, name_pattern: &str) -> Result<usize> {
+    for pattern in get_id_patterns(name_pattern) {
+        let id = get_seq_id_from_name_with_pattern(name, pattern)?;
+        if id > 0 {
+            return Ok(id);
+        }
+    }
+
+    Ok(0)
+}
+
+fn get_seq_id_from_name_with_pattern(name: &str, pattern: &str) -> Result<usize> {
+    let re = match get_name_pattern_regex(pattern) {
+        Some(re) => re,
+        None => return Ok(0),
+    };
 
     let mut id: usize = 0;
-    if let Some(cap) = RE.captures(name) {
+    if let Some(cap) = re.captures(name) {
         debug!(get_logger(), "found first match [{}]", cap[1].to_string());
-        id = cap[1].to_string().parse().unwrap();
+        id = cap[1].to_string().parse().unwrap_or(0);
     } else {
         debug!(get_logger(), "Unable to extract_seq_id from [{}]", name);
     }
@@ -136,8 +248,12 @@ fn get_seq_id_from_name(name: &str) -> Result<usize> {
     Ok(id)
 }
 
-fn get_last_seq_id(adrs: Vec<Adr>) -> usize {
-    match get_seq_id_from_name(&adrs[adrs.len() - 1].file_name) {
+fn get_last_seq_id(adrs: Vec<Adr>, name_pattern: &str) -> usize {
+    let last = match adrs.last() {
+        Some(last) => last,
+        None => return 0,
+    };
+    match get_seq_id_from_name(&last.file_name, name_pattern) {
         Ok(val) => {
             val
         }, 
@@ -149,24 +265,123 @@ fn get_last_seq_id(adrs: Vec<Adr>) -> usize {
 }
 
 fn sort_by_id(mut adrs: Vec<Adr>) -> Vec<Adr> {
-    adrs.sort_by(|a, b| a.file_id.cmp(&b.file_id));
+    adrs.sort_by_key(|a| a.file_id);
     adrs
 }
 
-fn format_decision_name(cfg: AdrToolConfig, name: &str) -> Result<String> {
-    let mut prefix = String::new();
-    if cfg.use_id_prefix {
-        let adr_vec = list_all_adr(Path::new(cfg.adr_src_dir.as_str())).unwrap();
-        let last_seq_id = get_last_seq_id(adr_vec);
-        prefix = format!("{:0>width$}-", last_seq_id + 1, width = cfg.id_prefix_width); //"{:0width$}", x, width = width
-        debug!(get_logger(), "got seq number [{}]", prefix);
+/// Returns the last id used by the ADRs (the `.adoc` files of `src_dir`) of all the local and remote git branches
+/// (0 if `src_dir` is not in a git repository)
+fn get_last_seq_id_from_git(src_dir: &Path, name_pattern: &str) -> usize {
+    //the ADRs are listed from the root of the repository, the files outside src_dir are not ADRs
+    let (root, dir) = match fs::canonicalize(src_dir).and_then(|src_dir| {
+        let root = fs::canonicalize(adr_git::get_repository_root(&src_dir)?)?;
+        let dir = src_dir.strip_prefix(&root).map_err(io::Error::other)?.to_path_buf();
+        Ok((root, dir))
+    }) {
+        Ok(val) => val,
+        Err(why) => {
+            debug!(get_logger(), "No git repository for [{}] - [{}]", src_dir.display(), why);
+            return 0;
+        }
+    };
+    let dir = match dir.as_os_str().is_empty() {
+        true => String::from("."),
+        false => dir.display().to_string(),
+    };
+
+    let refs = match adr_git::git(&root, &["for-each-ref", "--format=%(refname)", "refs/heads", "refs/remotes"]) {
+        Ok(refs) => refs,
+        Err(why) => {
+            warn!(get_logger(), "Unable to list the git branches of [{}] - [{}]", root.display(), why);
+            return 0;
+        }
+    };
+
+    let mut last_seq_id = 0;
+    for git_ref in refs.lines().filter(|r| !r.ends_with("/HEAD")) {
+        let files = match adr_git::git(&root, &["ls-tree", "-r", "-z", "--name-only", git_ref, "--", &dir]) {
+            Ok(files) => files,
+            Err(why) => {
+                warn!(get_logger(), "Unable to list the files of [{}] - [{}]", git_ref, why);
+                continue;
+            }
+        };
+        for file in files.split('\0').filter(|file| file.ends_with(".adoc")) {
+            if let Some(name) = Path::new(file).file_name().and_then(|n| n.to_str()) {
+                last_seq_id = last_seq_id.max(get_seq_id_from_name(name, name_pattern).unwrap_or(0));
+            }
+        }
+    }
+    debug!(get_logger(), "last seq id across git branches is [{}]", last_seq_id);
+
+    last_seq_id
+}
+
+/// Returns the id of the next ADR, given the configured `id_strategy`
+fn get_next_seq_id(cfg: &AdrToolConfig) -> usize {
+    let src_dir = Path::new(cfg.adr_src_dir.as_str());
+    match cfg.id_strategy.as_str() {
+        ID_STRATEGY_TIMESTAMP => Utc::now()
+            .format("%Y%m%d%H%M%S")
+            .to_string()
+            .parse()
+            .unwrap(),
+        ID_STRATEGY_GIT => {
+            let last_seq_id = get_last_seq_id(list_all_adr(src_dir).unwrap(), &cfg.name_pattern);
+            last_seq_id.max(get_last_seq_id_from_git(src_dir, &cfg.name_pattern)) + 1
+        }
+        _ => get_last_seq_id(list_all_adr(src_dir).unwrap(), &cfg.name_pattern) + 1,
     }
+}
 
-    let name = name.to_ascii_lowercase();
-    let name = name.replace(" ", "-");
-    let name = format!("{}{}", prefix, name);
+/// Turns a title into a file name friendly slug: accents are removed, punctuation and spaces become `-`,
+/// other unicode letters are kept (e.g. `Café & Thé: l'été` gives `cafe-the-l-ete`)
+pub fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for c in title.nfd().filter(|c| !is_combining_mark(*c)) {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
 
-    Ok(name.to_string())
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Returns the file name (without the `.adoc` extension) of a new ADR, given the configured `name_pattern`.
+/// The `{id}` placeholder (and its separator) is removed if `use_id_prefix` is false
+fn format_decision_name(cfg: AdrToolConfig, title: &str) -> Result<String> {
+    let id = match cfg.use_id_prefix {
+        true => Some(get_next_seq_id(&cfg)),
+        false => None,
+    };
+
+    Ok(format_decision_name_with_id(&cfg, title, id))
+}
+
+/// Same as `format_decision_name` with a given id (e.g. when importing ADRs), the `{id}` placeholder is removed if None
+pub(crate) fn format_decision_name_with_id(cfg: &AdrToolConfig, title: &str, id: Option<usize>) -> String {
+    lazy_static! {
+        static ref RE_ID: Regex = Regex::new(r"\{id\}[-_.]?").unwrap();
+    }
+
+    let pattern = match cfg.name_pattern.is_empty() {
+        true => "{id}-{slug}",
+        false => cfg.name_pattern.as_str(),
+    };
+    let pattern = pattern.trim_end_matches(".adoc");
+
+    let name = match id {
+        Some(id) => {
+            let id = format!("{:0>width$}", id, width = cfg.id_prefix_width); //"{:0width$}", x, width = width
+            debug!(get_logger(), "got seq number [{}]", id);
+            pattern.replace("{id}", id.as_str())
+        }
+        None => RE_ID.replace_all(pattern, "").to_string(),
+    };
+    name.replace("{date}", Utc::now().format("%Y-%m-%d").to_string().as_str())
+        .replace("{slug}", slugify(title).as_str())
 }
 
 fn is_ok(entry: &DirEntry) -> bool {
@@ -185,7 +400,21 @@ fn is_ok(entry: &DirEntry) -> bool {
         .map(|s| s.ends_with(".adoc"))
         .unwrap_or(false);
 
-    (is_dir && !is_hidden) || (is_adoc && !is_hidden)
+    (is_dir || is_adoc) && !is_hidden
+}
+
+/// Returns true if `path` is one of the files read by `list_all_adr`: an `.adoc` file in `base_path`,
+/// outside of the hidden directories. The file may not exist anymore
+pub fn is_adr_file(base_path: &Path, path: &Path) -> bool {
+    match path.strip_prefix(base_path) {
+        Ok(relative) => {
+            relative.extension().map(|ext| ext == "adoc").unwrap_or(false)
+                && !relative
+                    .components()
+                    .any(|c| c.as_os_str().to_str().map(|s| s.starts_with('.')).unwrap_or(false))
+        }
+        Err(_) => false,
+    }
 }
 
 pub fn get_tags_popularity(base_path: &Path) -> Result<HashMap<String, u32>> {
@@ -202,17 +431,186 @@ pub fn get_tags_popularity(base_path: &Path) -> Result<HashMap<String, u32>> {
     Ok(popularity)
 }
 
+/// Holes in the sequence larger than this are not reported as missing ids (e.g. timestamp ids)
+const MAX_MISSING_IDS: usize = 100;
+
+/// Duplicated ids (with the files using them) and missing ids (holes in the sequence)
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct IdReport {
+    pub duplicates: Vec<(usize, Vec<String>)>,
+    pub missing: Vec<usize>,
+}
+
+/// Check the ids of the ADRs available in `base_path`. Missing ids are only relevant for sequential ids (`with_missing`)
+pub fn check_ids(base_path: &Path, with_missing: bool) -> io::Result<IdReport> {
+    let mut ids: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+    for adr in list_all_adr(base_path)? {
+        if adr.file_id > 0 {
+            ids.entry(adr.file_id).or_default().push(adr.path());
+        }
+    }
+
+    let mut report = IdReport::default();
+    for (id, paths) in ids.iter() {
+        if paths.len() > 1 {
+            report.duplicates.push((*id, paths.clone()));
+        }
+    }
+    if with_missing {
+        let mut previous = 0;
+        for id in ids.keys() {
+            if id - previous - 1 <= MAX_MISSING_IDS {
+                report.missing.extend(previous + 1..*id);
+            }
+            previous = *id;
+        }
+    }
+
+    Ok(report)
+}
+
+/// Give a new id to an ADR (the next one if `new_id` is not specified): the file is renamed and all the references
+/// to it (cf. `add_reference`) are rewritten. Returns the new path of the ADR.
+pub fn renumber_adr(cfg: AdrToolConfig, id_or_path: &str, new_id: Option<usize>) -> io::Result<String> {
+    let base_path = Path::new(cfg.adr_src_dir.as_str());
+    let mut adr = find_adr(base_path, id_or_path)?;
+    let new_id = match new_id {
+        Some(id) => id,
+        None => get_next_seq_id(&cfg),
+    };
+
+    let id = format!("{:0>width$}", new_id, width = cfg.id_prefix_width);
+    let id_position = get_id_patterns(&cfg.name_pattern)
+        .into_iter()
+        .filter_map(get_name_pattern_regex)
+        .find_map(|re| re.captures(&adr.file_name).and_then(|cap| cap.get(1)))
+        .map(|m| m.range());
+    let new_file_name = match id_position {
+        Some(range) => {
+            let mut new_file_name = adr.file_name.clone();
+            new_file_name.replace_range(range, id.as_str());
+            new_file_name
+        }
+        None => format!("{}-{}", id, adr.file_name),
+    };
+    let old_path = adr.path();
+    let new_path = Path::new(&old_path).with_file_name(&new_file_name);
+    if new_path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("[{}] already exists", new_path.display()),
+        ));
+    }
+
+    if adr.attributes.contains_key(ATTR_ID) {
+        adr.set_attribute(ATTR_ID, new_id.to_string().as_str());
+    }
+    fs::write(&new_path, &adr.content)?;
+    fs::remove_file(&old_path)?;
+    info!(get_logger(), "ADR [{}] renamed to [{}]", old_path, new_path.display());
+
+    //rewrite the references
+    for other in list_all_adr(base_path)? {
+        let content = rewrite_references(&other.content, &adr.file_name, &new_file_name);
+        if other.path() != format!("{}", new_path.display()) && content != other.content {
+            fs::write(other.path(), content)?;
+            info!(get_logger(), "References to [{}] updated in [{}]", adr.file_name, other.path());
+        }
+    }
+
+    Ok(format!("{}", new_path.display()))
+}
+
+/// Replaces the file name `old` by `new` in the references added by `add_reference` (`{superseded} old.adoc` or
+/// `:adr-superseded-by: old.adoc`...), other file names (e.g. `11-old.adoc`) and the rest of the content are kept
+fn rewrite_references(content: &str, old: &str, new: &str) -> String {
+    lazy_static! {
+        static ref RE_REFERENCES: Regex = Regex::new(
+            r"(?m)(\{(?:supersedes|superseded|completes|completed)\}|^:adr-(?:supersedes|superseded-by|completes|completed-by):)((?:[ \t]+[^\s{}*]+\.adoc)+)"
+        )
+        .unwrap();
+        static ref RE_TARGET: Regex = Regex::new(r"\S+").unwrap();
+    }
+
+    RE_REFERENCES
+        .replace_all(content, |cap: &regex::Captures| {
+            let targets = RE_TARGET.replace_all(&cap[2], |target: &regex::Captures| match &target[0] == old {
+                true => new.to_string(),
+                false => target[0].to_string(),
+            });
+            format!("{}{}", &cap[1], targets)
+        })
+        .to_string()
+}
+
+/// How many decisions a person took part in, and in which role
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Participation {
+    pub decisions: u32,
+    pub as_decider: u32,
+    pub as_expert: u32,
+    pub as_stakeholder: u32,
+}
+
+pub fn get_people_participation(base_path: &Path) -> Result<HashMap<String, Participation>> {
+    let mut participation: HashMap<String, Participation> = HashMap::new();
+    for adr in list_all_adr(base_path)? {
+        let mut people: Vec<&String> = Vec::new();
+        for decider in adr.deciders.iter() {
+            participation.entry(decider.to_string()).or_default().as_decider += 1;
+            people.push(decider);
+        }
+        for expert in adr.experts.iter() {
+            participation.entry(expert.to_string()).or_default().as_expert += 1;
+            people.push(expert);
+        }
+        for stakeholder in adr.stakeholders.iter() {
+            participation.entry(stakeholder.to_string()).or_default().as_stakeholder += 1;
+            people.push(stakeholder);
+        }
+
+        people.sort();
+        people.dedup();
+        for person in people {
+            participation.entry(person.to_string()).or_default().decisions += 1;
+        }
+    }
+
+    Ok(participation)
+}
+
+/// Rewrites the ADRs which still rely on the legacy markers so their metadata is declared as header attributes
+/// (`:adr-status:`, `:adr-date:`...). Returns the migrated ADRs and the added attributes. Nothing is written if `dry_run`.
+pub fn migrate_all_adr(base_path: &Path, dry_run: bool) -> io::Result<Vec<(Adr, Vec<String>)>> {
+    let mut migrated = Vec::new();
+    for mut adr in list_all_adr(base_path)? {
+        let added = adr.migrate_to_attributes();
+        if added.is_empty() {
+            continue;
+        }
+
+        if !dry_run {
+            fs::write(adr.path(), &adr.content)?;
+            info!(get_logger(), "ADR [{}] migrated - added {:?}", adr.path(), added);
+        }
+        migrated.push((adr, added));
+    }
+
+    Ok(migrated)
+}
+
 pub fn list_all_adr(dir: &Path) -> io::Result<Vec<Adr>> {
     let mut results = std::vec::Vec::new();
+    let name_pattern = adr_config::config::get_config().name_pattern;
 
     if dir.is_dir() {
         let walker = WalkDir::new(dir).follow_links(true).into_iter();
-        for entry in walker.filter_entry(|e| is_ok(e)) {
+        for entry in walker.filter_entry(is_ok) {
             let entry = entry?;
             debug!(get_logger(), "got file [{:?}]", entry.path());
             let metadata = entry.metadata().unwrap();
             if metadata.is_file() {
-                match build_adr(dir, entry.path()) {
+                match read_adr(dir, entry.path(), &name_pattern) {
                     Ok(adr) => {
                         results.push(adr);
                     }
@@ -229,6 +627,53 @@ pub fn list_all_adr(dir: &Path) -> io::Result<Vec<Adr>> {
     Ok(results)
 }
 
+/// Returns the (non hidden) sub directories of `dir`, relative to `dir` (e.g. `data/streaming`)
+pub fn list_sub_directories(dir: &Path) -> io::Result<Vec<String>> {
+    let mut results = Vec::new();
+
+    if dir.is_dir() {
+        let walker = WalkDir::new(dir).min_depth(1).follow_links(true).into_iter();
+        for entry in walker.filter_entry(is_ok) {
+            let entry = entry?;
+            if entry.file_type().is_dir() {
+                if let Ok(relative) = entry.path().strip_prefix(dir) {
+                    results.push(relative.to_string_lossy().to_string());
+                }
+            }
+        }
+    }
+    results.sort();
+
+    Ok(results)
+}
+
+/// Find an ADR given its id (e.g. `42`) or its path (absolute or relative to `base_path`)
+///
+/// # Arguments
+///
+/// * `base_path` - The root directory where are all the ADRs. This is typically AdrToolConfig.adr_src_dir
+/// * `id_or_path` - The id or the path of the ADR
+///
+pub fn find_adr(base_path: &Path, id_or_path: &str) -> io::Result<Adr> {
+    if let Ok(id) = id_or_path.parse::<usize>() {
+        return match list_all_adr(base_path)?.into_iter().find(|adr| adr.file_id == id) {
+            Some(adr) => Ok(adr),
+            None => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No ADR with id [{}] in [{}]", id, base_path.display()),
+            )),
+        };
+    }
+
+    let path = Path::new(id_or_path);
+    if path.is_file() {
+        //relative to the current directory, make it absolute so it can be split on base_path
+        build_adr(base_path, std::env::current_dir()?.join(path).as_path())
+    } else {
+        build_adr(base_path, base_path.join(path).as_path())
+    }
+}
+
 /// Given a complete `full_path` to a file, returns the difference compared to `base_path`.
 ///
 /// # Arguments
@@ -274,6 +719,11 @@ pub fn split_path<'a>(base_path: &'a Path, full_path: &'a Path) -> (&'a Path, &'
 /// let adr = build_adr(std::path::Path::new("/tmp/adrs/"), std::path::Path::new("/tmp/adrs/my-sub-dir/my-decision.adoc"));
 /// ```
 pub fn build_adr(base_path: &Path, full_path: &Path) -> io::Result<Adr> {
+    read_adr(base_path, full_path, &adr_config::config::get_config().name_pattern)
+}
+
+/// Same as `build_adr`, the ids being parsed from the file names with `name_pattern`
+fn read_adr(base_path: &Path, full_path: &Path, name_pattern: &str) -> io::Result<Adr> {
     debug!(
         get_logger(),
         "Want to create ADR from [{}] ",
@@ -287,6 +737,7 @@ pub fn build_adr(base_path: &Path, full_path: &Path) -> io::Result<Adr> {
         String::from(splitted_file_path.0.to_str().unwrap()),
         String::from(splitted_file_path.1.to_str().unwrap()),
         content,
+        name_pattern,
     );
 
     Ok(adr)
@@ -362,8 +813,8 @@ pub fn transition_to(
                     //if transition has been declined, we can stop here
                     match by_adr.update_status(TransitionStatus::revert(transition)) {
                         true => {
-                            from_adr.add_reference(format!("{}", by_adr.file_name).as_str());
-                            by_adr.add_reference(format!("{}", from_adr.file_name).as_str());
+                            from_adr.add_reference(&by_adr.file_name);
+                            by_adr.add_reference(&from_adr.file_name);
                             Ok(transition_adr(&from_adr, from_path, from_old_status)?
                                 == transition_adr(&by_adr, by_path, by_old_status)?)
                         }
@@ -399,8 +850,41 @@ pub struct Adr {
     pub state: AdrState,
     pub tags: String,
     pub tags_array: Vec<String>,
+    pub deciders: Vec<String>,
+    pub experts: Vec<String>,
+    pub stakeholders: Vec<String>,
+    pub attributes: HashMap<String, String>,
+    pub options: Vec<AdrOption>,
+    pub outcome: Option<String>,
+}
+
+/// An option listed in the `Considered Options` table (and detailed in the `Options Comparison` appendix)
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct AdrOption {
+    pub name: String,
+    pub description: String,
+    pub pros: Vec<String>,
+    pub cons: Vec<String>,
 }
 
+/// AsciiDoc document attributes holding the ADR metadata (e.g. `:adr-status: decided`).
+/// When present, they take precedence over the legacy `*Status:* {...}`, `*Date:*` and `[tags]#...#` markers.
+pub const ATTR_STATUS: &str = "adr-status";
+pub const ATTR_DATE: &str = "adr-date";
+pub const ATTR_TAGS: &str = "adr-tags";
+pub const ATTR_ID: &str = "adr-id";
+pub const ATTR_DECIDERS: &str = "adr-deciders";
+pub const ATTR_EXPERTS: &str = "adr-experts";
+pub const ATTR_STAKEHOLDERS: &str = "adr-stakeholders";
+/// The references added by `add_reference` when the status is only declared as an attribute
+/// (e.g. `:adr-superseded-by: 000042-my-decision.adoc`), space separated
+pub const ATTR_REFERENCES: [(Status, &str); 4] = [
+    (Status::SUPERSEDES, "adr-supersedes"),
+    (Status::SUPERSEDED, "adr-superseded-by"),
+    (Status::COMPLETES, "adr-completes"),
+    (Status::COMPLETED, "adr-completed-by"),
+];
+
 impl Adr {
     fn new() -> Adr {
         Adr {
@@ -415,16 +899,27 @@ impl Adr {
             state: AdrState::default(),
             tags: String::new(),
             tags_array: Vec::new(),
+            deciders: Vec::new(),
+            experts: Vec::new(),
+            stakeholders: Vec::new(),
+            attributes: HashMap::new(),
+            options: Vec::new(),
+            outcome: None,
         }
     }
 
-    pub fn from(base_path: String, file_path: String, content: String) -> Adr {
+    /// Parses `content`, the id being the `:adr-id:` attribute or parsed from the file name with `name_pattern`
+    /// (see `get_seq_id_from_name`)
+    pub fn from(base_path: String, file_path: String, content: String, name_pattern: &str) -> Adr {
         let mut adr = Adr::new();
 
         lazy_static! {
-            static ref RE_TITLE: Regex = Regex::new(r"= (.+)").unwrap();
-            static ref RE_STATUS: Regex = Regex::new(r"\{(.+)\}").unwrap();
+            static ref RE_TITLE: Regex = Regex::new(r"(?m)^\s*=+ (.+)$").unwrap();
+            static ref RE_STATUS: Regex = Regex::new(r"\*Status:\*\s*\{([^}]+)\}").unwrap();
+            static ref RE_DATE_MARKER: Regex =
+                Regex::new(r"\*Date:\*\s*([0-9]{4}-[0-9]{2}-[0-9]{2})").unwrap();
             static ref RE_DATE: Regex = Regex::new(r"([0-9]{4}-[0-9]{2}-[0-9]{2})").unwrap();
+            static ref RE_ID: Regex = Regex::new(r"(\d+)").unwrap();
         }
 
         //set file/path properties
@@ -439,12 +934,21 @@ impl Adr {
             Ok(name) => name,
             Err(err) => panic!("Unexpected Error: {:?}", err),
         };
-        adr.file_id = get_seq_id_from_name(&adr.file_name).unwrap();
 
         //set title/content
         adr.content = content;
+        adr.attributes = Adr::get_attributes(&adr.content);
+
+        adr.file_id = match adr.attributes.get(ATTR_ID) {
+            Some(val) => match RE_ID.captures(val) {
+                Some(cap) => cap[1].parse().unwrap_or(0),
+                None => 0,
+            },
+            None => get_seq_id_from_name(&adr.file_name, name_pattern).unwrap(),
+        };
+
         adr.title = match RE_TITLE.captures(&adr.content) {
-            Some(val) => val[1].to_string(),
+            Some(val) => val[1].trim().to_string(),
             None => {
                 error!(
                     get_logger(),
@@ -456,9 +960,16 @@ impl Adr {
             }
         };
 
-        //set date
-        adr.date = match RE_DATE.captures(&adr.content) {
-            Some(val) => val[1].trim().to_string(),
+        //set date - the attribute first, then the *Date:* marker, then the first date found
+        let date = match adr.attributes.get(ATTR_DATE) {
+            Some(val) => Some(val.to_string()),
+            None => RE_DATE_MARKER
+                .captures(&adr.content)
+                .or_else(|| RE_DATE.captures(&adr.content))
+                .map(|val| val[1].to_string()),
+        };
+        adr.date = match date {
+            Some(val) => val.trim().to_string(),
             None => {
                 debug!(
                     get_logger(),
@@ -471,13 +982,37 @@ impl Adr {
         };
 
         //set tags/tags_array
-        let tags = Adr::get_tags(&adr.content);
+        let tags = match adr.attributes.get(ATTR_TAGS) {
+            Some(val) => Adr::get_tags_from_attribute(val),
+            None => Adr::get_tags(&adr.content),
+        };
         adr.tags = tags.0;
         adr.tags_array = tags.1;
 
+        //set deciders/experts/stakeholders - the attributes first, then the role/people table
+        let roles = Adr::get_roles(&adr.content);
+        adr.deciders = match adr.attributes.get(ATTR_DECIDERS) {
+            Some(val) => Adr::split_attribute(val),
+            None => roles.0,
+        };
+        adr.experts = match adr.attributes.get(ATTR_EXPERTS) {
+            Some(val) => Adr::split_attribute(val),
+            None => roles.1,
+        };
+        adr.stakeholders = match adr.attributes.get(ATTR_STAKEHOLDERS) {
+            Some(val) => Adr::split_attribute(val),
+            None => roles.2,
+        };
+
         //set status/state
-        adr.status = Status::from_str(match RE_STATUS.captures(&adr.content) {
-            Some(val) => val[1].trim().to_string(),
+        let status = match adr.attributes.get(ATTR_STATUS) {
+            Some(val) => Some(val.to_string()),
+            None => RE_STATUS
+                .captures(&adr.content)
+                .map(|val| val[1].to_string()),
+        };
+        adr.status = Status::from_str(match status {
+            Some(val) => val.trim().to_string(),
             None => {
                 debug!(
                     get_logger(),
@@ -489,34 +1024,364 @@ impl Adr {
             }
         });
         adr.state = AdrState {
-            status: adr.status.clone(),
+            status: adr.status,
         };
+
+        //set considered options and decision outcome
+        adr.options = Adr::get_options(&adr.content);
+        adr.outcome = Adr::get_outcome(&adr.content, &adr.options);
         adr
     }
 
-    pub fn path(&self) -> String {
-        let full_path = Path::new(self.base_path.as_str()).join(self.file_path.as_str());
-        return format!("{}", full_path.display());
+    /// Returns the line index of the heading containing `title` and the content of its section (until the next heading)
+    pub fn get_section(val: &str, title: &str) -> Option<(usize, String)> {
+        lazy_static! {
+            static ref RE_HEADING: Regex = Regex::new(r"^\s*=+ (.+)$").unwrap();
+        }
+
+        let title = title.to_lowercase();
+        let mut section: Option<(usize, Vec<&str>)> = None;
+        for (index, line) in val.lines().enumerate() {
+            match RE_HEADING.captures(line) {
+                Some(cap) => {
+                    if section.is_some() {
+                        break;
+                    }
+                    if cap[1].to_lowercase().contains(title.as_str()) {
+                        section = Some((index, Vec::new()));
+                    }
+                }
+                None => {
+                    if let Some((_, lines)) = section.as_mut() {
+                        lines.push(line);
+                    }
+                }
+            }
+        }
+
+        section.map(|(index, lines)| (index, lines.join("\n")))
+    }
+
+    /// Returns the rows of the first table (`|===`) found in `val`, without the header row
+    pub fn get_table(val: &str) -> Vec<Vec<String>> {
+        let lines: Vec<&str> = val.lines().collect();
+        let start = match lines.iter().position(|l| l.trim() == "|===") {
+            Some(start) => start,
+            None => return Vec::new(),
+        };
+        let end = match lines[start + 1..].iter().position(|l| l.trim() == "|===") {
+            Some(end) => start + 1 + end,
+            None => lines.len(),
+        };
+        let has_header = (start > 0 && lines[start - 1].contains("header"))
+            || (start + 2 < end && lines[start + 2].trim().is_empty());
+
+        let body = &lines[start + 1..end];
+        let columns = match body.iter().find(|l| !l.trim().is_empty()) {
+            Some(first) => first.split('|').skip(1).count().max(1),
+            None => return Vec::new(),
+        };
+        let cells: Vec<String> = body
+            .join("\n")
+            .split('|')
+            .skip(1)
+            .map(|c| c.trim().to_string())
+            .collect();
+
+        let mut rows: Vec<Vec<String>> = cells.chunks(columns).map(|c| c.to_vec()).collect();
+        if has_header && !rows.is_empty() {
+            rows.remove(0);
+        }
+
+        rows
+    }
+
+    fn normalize_option(val: &str) -> String {
+        val.replace('*', "")
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ")
+            .to_lowercase()
+    }
+
+    /// Returns the options of the `Considered Options` table, enriched with the pros and cons of the `Options Comparison` table
+    pub fn get_options(val: &str) -> Vec<AdrOption> {
+        let mut options: Vec<AdrOption> = Vec::new();
+        if let Some((_, section)) = Adr::get_section(val, "considered options") {
+            for row in Adr::get_table(section.as_str()) {
+                let name = row[0].replace('*', "").trim().to_string();
+                if name.is_empty() {
+                    continue;
+                }
+                options.push(AdrOption {
+                    name,
+                    description: row[1..].join(" ").trim().to_string(),
+                    ..Default::default()
+                });
+            }
+        }
+
+        if let Some((_, section)) = Adr::get_section(val, "options comparison") {
+            let mut current: Option<usize> = None;
+            for row in Adr::get_table(section.as_str()) {
+                let name = row[0].replace('*', "").trim().to_string();
+                if !name.is_empty() {
+                    let normalized = Adr::normalize_option(&name);
+                    current = match options
+                        .iter()
+                        .position(|o| Adr::normalize_option(&o.name) == normalized)
+                    {
+                        Some(index) => Some(index),
+                        None => {
+                            options.push(AdrOption {
+                                name,
+                                ..Default::default()
+                            });
+                            Some(options.len() - 1)
+                        }
+                    };
+                }
+                if let Some(index) = current {
+                    if let Some(pros) = row.get(1).filter(|p| !p.is_empty()) {
+                        options[index].pros.push(pros.to_string());
+                    }
+                    if let Some(cons) = row.get(2).filter(|c| !c.is_empty()) {
+                        options[index].cons.push(cons.to_string());
+                    }
+                }
+            }
+        }
+
+        options
+    }
+
+    /// Returns the chosen option as written in the `Decision Outcome` section: the first bold text
+    /// (e.g. `The decision goes with *Option #2*`) or else the listed option named in the section
+    pub fn get_outcome(val: &str, options: &[AdrOption]) -> Option<String> {
+        lazy_static! {
+            static ref RE_BOLD: Regex = Regex::new(r"\*([^*\s][^*\n]*)\*").unwrap();
+        }
+
+        let (_, section) = Adr::get_section(val, "decision outcome")?;
+        if let Some(cap) = RE_BOLD.captures(section.as_str()) {
+            return Some(cap[1].trim().to_string());
+        }
+
+        Adr::find_named_option(section.as_str(), options).map(|o| o.name.to_string())
+    }
+
+    /// Whether the (normalized) `text` names the (normalized) `option` as a whole, e.g. `option #12` does not name `option #1`
+    fn names_option(text: &str, option: &str) -> bool {
+        !option.is_empty()
+            && text.match_indices(option).any(|(index, _)| {
+                let before = text[..index].chars().next_back();
+                let after = text[index + option.len()..].chars().next();
+                !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+            })
+    }
+
+    /// The option named in `text`, the longest one if several are (e.g. `Kafka Streams` rather than `Kafka`)
+    fn find_named_option<'a>(text: &str, options: &'a [AdrOption]) -> Option<&'a AdrOption> {
+        let text = Adr::normalize_option(text);
+        options
+            .iter()
+            .rev()
+            .filter(|o| Adr::names_option(&text, &Adr::normalize_option(&o.name)))
+            .max_by_key(|o| Adr::normalize_option(&o.name).len())
+    }
+
+    /// The option (among the considered ones) named by the decision outcome
+    pub fn chosen_option(&self) -> Option<&AdrOption> {
+        let outcome = Adr::normalize_option(self.outcome.as_ref()?.as_str());
+        self.options
+            .iter()
+            .find(|o| Adr::normalize_option(&o.name) == outcome)
+            .or_else(|| Adr::find_named_option(&outcome, &self.options))
+    }
+
+    pub fn path(&self) -> String {
+        let full_path = Path::new(self.base_path.as_str()).join(self.file_path.as_str());
+        format!("{}", full_path.display())
+    }
+
+    pub fn get_tags(val: &str) -> (String, Vec<String>) {
+        lazy_static! {
+            static ref RE_TAGS: Regex = Regex::new(r"(\[tags]\#([^#]+)\#)").unwrap();
+        }
+
+        let mut tags_str = String::from("");
+        for cap in RE_TAGS.captures_iter(val) {
+            use std::fmt::Write;
+            write!(tags_str, "#{} ", &cap[2]).unwrap();
+        }
+
+        let tags = tags_str
+            .split('#')
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .collect();
+
+        (tags_str, tags)
+    }
+
+    /// Same output as `get_tags` but for the comma separated `:adr-tags:` attribute
+    pub fn get_tags_from_attribute(val: &str) -> (String, Vec<String>) {
+        let mut tags_str = String::from("");
+        for tag in Adr::split_attribute(val) {
+            use std::fmt::Write;
+            write!(tags_str, "#{} ", tag).unwrap();
+        }
+
+        let tags = tags_str
+            .split('#')
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .collect();
+
+        (tags_str, tags)
+    }
+
+    /// Returns the (decision makers, experts, stakeholders) listed in the role/people table, e.g.
+    /// `|_Decision Makers_ |@John Smith, @Jane Doe`
+    pub fn get_roles(val: &str) -> (Vec<String>, Vec<String>, Vec<String>) {
+        lazy_static! {
+            static ref RE_ROLE: Regex =
+                Regex::new(r"(?im)^\s*\|\s*_?\*?(decision makers?|deciders?|experts?|stakeholders?)\*?_?\s*\|(.*)$").unwrap();
+        }
+
+        let mut roles = (Vec::new(), Vec::new(), Vec::new());
+        for cap in RE_ROLE.captures_iter(val) {
+            let people = Adr::split_people(&cap[2]);
+            let role = cap[1].to_ascii_lowercase();
+            if role.starts_with("decision") || role.starts_with("decider") {
+                roles.0.extend(people);
+            } else if role.starts_with("expert") {
+                roles.1.extend(people);
+            } else {
+                roles.2.extend(people);
+            }
+        }
+
+        roles
+    }
+
+    fn split_people(val: &str) -> Vec<String> {
+        let separator = match val.contains('@') {
+            true => '@',
+            false => ',',
+        };
+        val.split(separator)
+            .map(|s| s.trim().trim_end_matches(',').trim())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    fn split_attribute(val: &str) -> Vec<String> {
+        val.split(',')
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    /// Returns the `adr-*` attribute entries declared in the document header, i.e. before the first title
+    /// and right after it (until the first line which is not an attribute entry).
+    pub fn get_attributes(val: &str) -> HashMap<String, String> {
+        let mut attributes = HashMap::new();
+        for (_index, name, value) in Adr::get_header_attributes(val) {
+            if name.starts_with("adr-") {
+                attributes.insert(name, value);
+            }
+        }
+
+        attributes
     }
 
-    pub fn get_tags(val: &String) -> (String, Vec<String>) {
+    fn get_header_attributes(val: &str) -> Vec<(usize, String, String)> {
         lazy_static! {
-            static ref RE_TAGS: Regex = Regex::new(r"(\[tags]\#([^#]+)\#)").unwrap();
+            static ref RE_ATTRIBUTE: Regex = Regex::new(r"^\s*:([\w-]+):\s*(.*)$").unwrap();
+            static ref RE_HEADING: Regex = Regex::new(r"^\s*=+ ").unwrap();
         }
 
-        let mut tags_str = String::from("");
-        for cap in RE_TAGS.captures_iter(val) {
-            use std::fmt::Write;
-            write!(tags_str, "#{} ", &cap[2]).unwrap();
+        let mut attributes = Vec::new();
+        let mut title_found = false;
+        for (index, line) in val.lines().enumerate() {
+            if let Some(cap) = RE_ATTRIBUTE.captures(line) {
+                attributes.push((index, cap[1].to_string(), cap[2].trim().to_string()));
+            } else if RE_HEADING.is_match(line) && !title_found {
+                title_found = true;
+            } else if title_found || !(line.trim().is_empty() || line.trim_start().starts_with("//")) {
+                break;
+            }
         }
 
-        let tags = tags_str
-            .split('#')
-            .filter(|s| s.len() > 0)
-            .map(|s| s.to_string())
-            .collect();
+        attributes
+    }
 
-        (tags_str, tags)
+    /// Update (or add to the header) the attribute `name`
+    pub fn set_attribute(&mut self, name: &str, value: &str) {
+        debug!(get_logger(), "Want to set attribute [{}] to [{}]", name, value);
+
+        let header = Adr::get_header_attributes(&self.content);
+        let new_line = format!(":{}: {}", name, value);
+        let mut lines: Vec<String> = self.content.lines().map(|s| s.to_string()).collect();
+
+        match header.iter().find(|(_, attr, _)| attr == name) {
+            Some((index, _, _)) => lines[*index] = new_line,
+            None => {
+                let index = match header.last() {
+                    Some((index, _, _)) => index + 1,
+                    None => 0,
+                };
+                lines.insert(index, new_line);
+            }
+        }
+
+        let mut content = lines.join("\n");
+        if self.content.ends_with('\n') {
+            content.push('\n');
+        }
+        self.content = content;
+        self.attributes.insert(name.to_string(), value.to_string());
+    }
+
+    /// Add the metadata attributes (status, date, tags, id, deciders) which are not yet declared in the header,
+    /// based on the legacy markers. Returns the names of the added attributes.
+    pub fn migrate_to_attributes(&mut self) -> Vec<String> {
+        let mut candidates = Vec::new();
+        if self.status != Status::NONE {
+            candidates.push((ATTR_STATUS, self.status.as_str().to_string()));
+        }
+        if self.date != "None" {
+            candidates.push((ATTR_DATE, self.date.clone()));
+        }
+        if !self.tags_array.is_empty() {
+            let tags: Vec<&str> = self.tags_array.iter().map(|s| s.trim()).collect();
+            candidates.push((ATTR_TAGS, tags.join(", ")));
+        }
+        if self.file_id > 0 {
+            candidates.push((ATTR_ID, self.file_id.to_string()));
+        }
+        if !self.deciders.is_empty() {
+            candidates.push((ATTR_DECIDERS, self.deciders.join(", ")));
+        }
+        if !self.experts.is_empty() {
+            candidates.push((ATTR_EXPERTS, self.experts.join(", ")));
+        }
+        if !self.stakeholders.is_empty() {
+            candidates.push((ATTR_STAKEHOLDERS, self.stakeholders.join(", ")));
+        }
+
+        let mut added = Vec::new();
+        for (name, value) in candidates {
+            if !self.attributes.contains_key(name) {
+                self.set_attribute(name, value.as_str());
+                added.push(name.to_string());
+            }
+        }
+
+        added
     }
 
     pub fn update_status(&mut self, transition: TransitionStatus) -> bool {
@@ -533,6 +1398,9 @@ impl Adr {
             self.content = self
                 .content
                 .replace(current_status.as_str(), new_status.as_str());
+            if self.attributes.contains_key(ATTR_STATUS) {
+                self.set_attribute(ATTR_STATUS, state.status.as_str());
+            }
             self.status = state.status;
             self.state = state;
             self.update_date(Utc::now().date_naive());
@@ -557,9 +1425,18 @@ impl Adr {
             new_status
         );
 
-        self.content = self
-            .content
-            .replace(current_status.as_str(), new_status.as_str());
+        if self.content.contains(current_status.as_str()) {
+            self.content = self
+                .content
+                .replace(current_status.as_str(), new_status.as_str());
+        } else if let Some((_, attribute)) = ATTR_REFERENCES.iter().find(|(status, _)| *status == self.status) {
+            //no legacy marker, e.g. migrated with migrate_to_attributes
+            let references = match self.attributes.get(*attribute) {
+                Some(val) if !val.is_empty() => format!("{} {}", val, adr_title),
+                _ => adr_title.to_string(),
+            };
+            self.set_attribute(attribute, references.as_str());
+        }
     }
 
     pub fn update_date(&mut self, today: NaiveDate) {
@@ -567,11 +1444,26 @@ impl Adr {
         debug!(get_logger(), "Want to update ADR to date [{}]", new_date);
 
         self.date = new_date;
-        let re = Regex::new(r"(\d{4})-(\d{2})-(\d{2})").unwrap();
-        self.content = re
-            .replace(self.content.as_str(), self.date.as_str())
-            .as_ref()
-            .to_owned();
+        let has_attribute = self.attributes.contains_key(ATTR_DATE);
+        if has_attribute {
+            let date = self.date.clone();
+            self.set_attribute(ATTR_DATE, date.as_str());
+        }
+
+        let marker = Regex::new(r"(\*Date:\*\s*)(\d{4})-(\d{2})-(\d{2})").unwrap();
+        if marker.is_match(self.content.as_str()) {
+            let replacement = format!("${{1}}{}", self.date);
+            self.content = marker
+                .replace(self.content.as_str(), replacement.as_str())
+                .as_ref()
+                .to_owned();
+        } else if !has_attribute {
+            let re = Regex::new(r"(\d{4})-(\d{2})-(\d{2})").unwrap();
+            self.content = re
+                .replace(self.content.as_str(), self.date.as_str())
+                .as_ref()
+                .to_owned();
+        }
     }
 
     pub fn update_title(&mut self, title: &str) {
@@ -587,7 +1479,7 @@ impl Adr {
 impl Clone for Adr {
     fn clone(&self) -> Adr {
         Adr {
-            file_id: self.file_id.clone(),
+            file_id: self.file_id,
             file_name: String::from(self.file_name.as_str()),
             file_path: String::from(self.file_path.as_str()),
             base_path: String::from(self.base_path.as_str()),
@@ -596,8 +1488,14 @@ impl Clone for Adr {
             date: String::from(self.date.as_str()),
             tags: String::from(self.tags.as_str()),
             tags_array: self.tags_array.clone(),
-            status: self.state.status.clone(),
-            state: self.state.clone(),
+            deciders: self.deciders.clone(),
+            experts: self.experts.clone(),
+            stakeholders: self.stakeholders.clone(),
+            attributes: self.attributes.clone(),
+            options: self.options.clone(),
+            outcome: self.outcome.clone(),
+            status: self.state.status,
+            state: self.state,
         }
     }
 }
@@ -638,6 +1536,7 @@ impl TransitionStatus {
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(val: String) -> TransitionStatus {
         match val.as_str() {
             "decided" => TransitionStatus::DECIDED,
@@ -651,8 +1550,9 @@ impl TransitionStatus {
     }
 }
 
-#[derive(Debug, PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
 pub enum Status {
+    #[default]
     WIP,
     DECIDED,
     COMPLETED,
@@ -663,12 +1563,6 @@ pub enum Status {
     NONE,
 }
 
-impl Default for Status {
-    fn default() -> Self {
-        Status::WIP
-    }
-}
-
 impl Status {
     pub fn as_str(&self) -> &'static str {
         match *self {
@@ -683,6 +1577,19 @@ impl Status {
         }
     }
 
+    /// true if the decision has been made (whatever happened to it afterwards, except being obsoleted)
+    pub fn is_decided(&self) -> bool {
+        matches!(
+            *self,
+            Status::DECIDED
+                | Status::COMPLETED
+                | Status::COMPLETES
+                | Status::SUPERSEDED
+                | Status::SUPERSEDES
+        )
+    }
+
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(val: String) -> Status {
         match val.as_str() {
             "wip" => Status::WIP,
@@ -812,12 +1719,10 @@ impl State for AdrState {
                     Status::SUPERSEDES
                 }
             },
-            Status::CANCELLED => match transition {
-                _ => {
-                    has_been_modified = false;
-                    Status::CANCELLED
-                }
-            },
+            Status::CANCELLED => {
+                has_been_modified = false;
+                Status::CANCELLED
+            }
 
             _ => {
                 has_been_modified = false;
@@ -838,7 +1743,7 @@ impl State for AdrState {
     }
 
     fn build(status: Status) -> AdrState {
-        AdrState { status: status }
+        AdrState { status }
     }
 }
 
@@ -904,8 +1809,39 @@ mod tests {
                 status: Status::DECIDED
             }
         );
-        assert_eq!(adr_sut.content.contains(Status::DECIDED.as_str()), true);
-        assert_eq!(update_true, true);
+        assert!(adr_sut.content.contains(Status::DECIDED.as_str()));
+        assert!(update_true);
+    }
+
+    #[test]
+    fn test_transition_to_with_attributes_only() {
+        let src = TempDir::new("my_src_folder").unwrap();
+        let content = "= My Decision\n:adr-status: decided\n:adr-date: 2024-01-02\n\n== Context\n\nSome context\n";
+        fs::write(src.path().join("001-ADR-1.adoc"), content).unwrap();
+        fs::write(src.path().join("002-ADR-2.adoc"), content).unwrap();
+
+        let path = format!("{}", src.path().join("001-ADR-1.adoc").display());
+        let by = format!("{}", src.path().join("002-ADR-2.adoc").display());
+        assert!(super::transition_to_superseded_by(src.path(), &path, &by).unwrap());
+
+        let adr = super::build_adr(src.path(), Path::new(&path)).unwrap();
+        assert_eq!(adr.status, Status::SUPERSEDED);
+        assert!(adr.content.contains(":adr-superseded-by: 002-ADR-2.adoc\n"));
+        assert_eq!(
+            crate::adr_graph::get_references(&adr),
+            vec![(Status::SUPERSEDED, String::from("002-ADR-2.adoc"))]
+        );
+        let by_adr = super::build_adr(src.path(), Path::new(&by)).unwrap();
+        assert_eq!(by_adr.status, Status::SUPERSEDES);
+        assert_eq!(
+            crate::adr_graph::get_references(&by_adr),
+            vec![(Status::SUPERSEDES, String::from("001-ADR-1.adoc"))]
+        );
+
+        //the references are appended
+        let mut adr = by_adr.clone();
+        adr.add_reference("003-ADR-3.adoc");
+        assert_eq!(adr.attributes.get("adr-supersedes").unwrap(), "001-ADR-1.adoc 003-ADR-3.adoc");
     }
 
     #[test]
@@ -933,7 +1869,7 @@ mod tests {
         );
 
         let expected_status = "{decided} by adr-num-123 *Date:* 2019-10-28";
-        assert_eq!(adr_sut.content.contains(expected_status), true);
+        assert!(adr_sut.content.contains(expected_status));
     }
 
     #[test]
@@ -1167,22 +2103,22 @@ mod tests {
 
     #[test]
     fn test_get_seq() {
-        let seq = super::get_seq_id_from_name("01-my-decision.adoc").unwrap();
+        let seq = super::get_seq_id_from_name("01-my-decision.adoc", "{id}-{slug}.adoc").unwrap();
         assert_eq!(seq, 1);
-        let seq = super::get_seq_id_from_name("00000010-my-decision.adoc").unwrap();
+        let seq = super::get_seq_id_from_name("00000010-my-decision.adoc", "{id}-{slug}.adoc").unwrap();
         assert_eq!(seq, 10);
-        let seq = super::get_seq_id_from_name("00000001-my-decision.adoc").unwrap();
+        let seq = super::get_seq_id_from_name("00000001-my-decision.adoc", "{id}-{slug}.adoc").unwrap();
         assert_eq!(seq, 1);
-        let seq = super::get_seq_id_from_name("00000001-my-decision-594.adoc").unwrap();
+        let seq = super::get_seq_id_from_name("00000001-my-decision-594.adoc", "{id}-{slug}.adoc").unwrap();
         assert_eq!(seq, 1);
-        let seq = super::get_seq_id_from_name("00000001-my-decision-594-full.adoc").unwrap();
+        let seq = super::get_seq_id_from_name("00000001-my-decision-594-full.adoc", "{id}-{slug}.adoc").unwrap();
         assert_eq!(seq, 1);
-        let seq = super::get_seq_id_from_name("00000001-my-decision-594-full.adoc").unwrap();
+        let seq = super::get_seq_id_from_name("00000001-my-decision-594-full.adoc", "{id}-{slug}.adoc").unwrap();
         assert_eq!(seq, 1);
-        let seq = super::get_seq_id_from_name("00000002-my-decision-594-full.adoc").unwrap();
+        let seq = super::get_seq_id_from_name("00000002-my-decision-594-full.adoc", "{id}-{slug}.adoc").unwrap();
         assert_eq!(seq, 2);
 
-        let seq = super::get_seq_id_from_name("my-decision-full.adoc").unwrap();
+        let seq = super::get_seq_id_from_name("my-decision-full.adoc", "{id}-{slug}.adoc").unwrap();
         assert_eq!(seq, 0);
 
         // let result =
@@ -1190,83 +2126,186 @@ mod tests {
         // assert!(result.is_err());
     }
 
+    #[test]
+    fn test_get_seq_with_pattern() {
+        let seq = super::get_seq_id_from_name_with_pattern("ADR-000042-my-decision.adoc", "ADR-{id}-{slug}").unwrap();
+        assert_eq!(seq, 42);
+        let seq = super::get_seq_id_from_name_with_pattern("000042-my-decision.adoc", "ADR-{id}-{slug}").unwrap();
+        assert_eq!(seq, 0);
+        let seq = super::get_seq_id_from_name_with_pattern("2024-01-02-12-my-decision.adoc", "{date}-{id}-{slug}.adoc").unwrap();
+        assert_eq!(seq, 12);
+        let seq = super::get_seq_id_from_name_with_pattern("my-decision_7.adoc", "{slug}_{id}.adoc").unwrap();
+        assert_eq!(seq, 7);
+        //no id in the pattern (e.g. dates are not ids)
+        let seq = super::get_seq_id_from_name_with_pattern("2024-01-02-my-decision.adoc", "{date}-{slug}").unwrap();
+        assert_eq!(seq, 0);
+
+        //the default pattern is tried for the ADRs created before name_pattern was changed
+        let seq = super::get_seq_id_from_name("000042-my-decision.adoc", "ADR-{id}-{slug}").unwrap();
+        assert_eq!(seq, 42);
+        //but not when name_pattern has no id: the year is not an id
+        let seq = super::get_seq_id_from_name("2024-01-02-my-decision.adoc", "{date}-{slug}").unwrap();
+        assert_eq!(seq, 0);
+        let seq = super::get_seq_id_from_name("000042-my-decision.adoc", "{date}-{slug}").unwrap();
+        assert_eq!(seq, 0);
+    }
+
+    #[test]
+    fn test_get_last_seq_id_from_git() {
+        let dir = TempDir::new("my_git_folder").unwrap();
+        let git = |args: &[&str]| crate::adr_git::git(dir.path(), args).unwrap();
+        git(&["init", "-q"]);
+        fs::create_dir_all(dir.path().join("docs/adr")).unwrap();
+        fs::create_dir_all(dir.path().join("db/migrations")).unwrap();
+        fs::write(dir.path().join("docs/adr/000003-my-decision.adoc"), ADOC_TMPL_NOTAG).unwrap();
+        fs::write(dir.path().join("docs/adr/000009-notes.txt"), "").unwrap();
+        fs::write(dir.path().join("db/migrations/20240101-init.sql"), "").unwrap();
+        git(&["add", "-A"]);
+        git(&["-c", "user.name=John Doe", "-c", "user.email=john@doe.com", "commit", "-q", "-m", "init"]);
+        //another branch with a newer ADR
+        git(&["checkout", "-q", "-b", "other"]);
+        fs::write(dir.path().join("docs/adr/000005-other-decision.adoc"), ADOC_TMPL_NOTAG).unwrap();
+        git(&["add", "-A"]);
+        git(&["-c", "user.name=John Doe", "-c", "user.email=john@doe.com", "commit", "-q", "-m", "other"]);
+        git(&["checkout", "-q", "-"]);
+        fs::remove_file(dir.path().join("docs/adr/000005-other-decision.adoc")).ok();
+
+        //only the ADRs of adr_src_dir are read, not the migrations nor the other files
+        let seq = super::get_last_seq_id_from_git(&dir.path().join("docs/adr"), "{id}-{slug}.adoc");
+        assert_eq!(seq, 5);
+
+        let seq = super::get_last_seq_id_from_git(&dir.path().join("db"), "{id}-{slug}.adoc");
+        assert_eq!(seq, 0);
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(super::slugify("my Decision"), "my-decision");
+        assert_eq!(super::slugify("  Use Kafka (v3.2) for events!  "), "use-kafka-v3-2-for-events");
+        assert_eq!(super::slugify("Café & Thé: l'été"), "cafe-the-l-ete");
+        assert_eq!(super::slugify("Straße über Ærø"), "straße-uber-ærø");
+        assert_eq!(super::slugify("日本語 タイトル"), "日本語-タイトル");
+        assert_eq!(super::slugify("my-decision"), "my-decision");
+    }
+
+    #[test]
+    fn test_format_decision_name_w_pattern() {
+        let src = TempDir::new("my_src_folder").unwrap();
+        fs::write(src.path().join("ADR-003-ADR-3.adoc"), ADOC_TMPL_NOTAG).unwrap();
+
+        let mut cfg = AdrToolConfig {
+            adr_src_dir: format!("{}", src.path().display()),
+            use_id_prefix: true,
+            id_prefix_width: 3,
+            name_pattern: String::from("ADR-{id}-{slug}"),
+            ..Default::default()
+        };
+        //the ids are read with the pattern of the given config
+        let name = super::format_decision_name(cfg.clone(), "Élan vital?").unwrap();
+        assert_eq!(name, "ADR-004-elan-vital");
+
+        cfg.use_id_prefix = false;
+        let name = super::format_decision_name(cfg.clone(), "Élan vital?").unwrap();
+        assert_eq!(name, "ADR-elan-vital");
+
+        cfg.name_pattern = String::from("{date}-{slug}.adoc");
+        let name = super::format_decision_name(cfg, "Élan vital?").unwrap();
+        assert_eq!(
+            name,
+            format!("{}-elan-vital", Utc::now().format("%Y-%m-%d"))
+        );
+    }
+
+    #[test]
+    fn test_adr_from_w_pattern() {
+        let adr = |file_path: &str, name_pattern: &str| {
+            super::Adr::from("base_path".to_string(), file_path.to_string(), ADOC_TMPL_NOTAG.to_string(), name_pattern)
+        };
+        assert_eq!(adr("ADR-007-use-kafka.adoc", "ADR-{id}-{slug}").file_id, 7);
+        assert_eq!(adr("007-use-kafka.adoc", "").file_id, 7);
+        //no id in the pattern
+        assert_eq!(adr("2024-01-02-use-kafka.adoc", "{date}-{slug}.adoc").file_id, 0);
+    }
+
     #[test]
     fn test_extract_seq_id_from_all_1() {
-        let mut paths = Vec::new();
-        paths.push(String::from("01-my-decision.adoc"));
-        paths.push(String::from("00000010-my-decision.adoc"));
-        paths.push(String::from("mypath/00000002-my-decision.adoc"));
-        paths.push(String::from("mypath/00000003-my-decision-594.adoc"));
-        paths.push(String::from("mypath/00000001-my-decision-594-full.adoc"));
-        paths.push(String::from("00000001-my-decision-594-full.adoc"));
-        paths.push(String::from(
-            "mypath/00000001/00000002-my-decision-594-full.adoc",
-        ));
-        paths.push(String::from("path/my-decision-full.adoc"));
-        paths.push(String::from("path/my-decision-543-0.adoc"));
+        let paths = vec![
+            String::from("01-my-decision.adoc"),
+            String::from("00000010-my-decision.adoc"),
+            String::from("mypath/00000002-my-decision.adoc"),
+            String::from("mypath/00000003-my-decision-594.adoc"),
+            String::from("mypath/00000001-my-decision-594-full.adoc"),
+            String::from("00000001-my-decision-594-full.adoc"),
+            String::from("mypath/00000001/00000002-my-decision-594-full.adoc"),
+            String::from("path/my-decision-full.adoc"),
+            String::from("path/my-decision-543-0.adoc"),
+        ];
 
         let mut adr_vec = Vec::new();
         for adr in paths.into_iter() {
             adr_vec.push(super::Adr::from(
                 String::from("/adr/"),
-                String::from(adr),
+                adr,
                 String::from(ADOC_TMPL_NOTAG),
+                "",
             ));
         }
 
         adr_vec = super::sort_by_id(adr_vec);
-        let seq = super::get_last_seq_id(adr_vec);
+        let seq = super::get_last_seq_id(adr_vec, "{id}-{slug}.adoc");
         assert_eq!(seq, 10);
     }
 
     #[test]
     fn test_extract_seq_id_from_all_2() {
-        let mut paths = Vec::new();
-        paths.push(String::from("attemtps.adoc"));
-        paths.push(String::from("attemtps43.adoc"));
-        paths.push(String::from("this-is-a-sample-12.adoc"));
-        paths.push(String::from("this-is-a-sample-14.adoc"));
-        paths.push(String::from("this-is-a-sample-17.adoc"));
-        paths.push(String::from("this-is-a-smple4.adoc"));
-        paths.push(String::from("this-is-a-smple7.adoc"));
+        let paths = vec![
+            String::from("attemtps.adoc"),
+            String::from("attemtps43.adoc"),
+            String::from("this-is-a-sample-12.adoc"),
+            String::from("this-is-a-sample-14.adoc"),
+            String::from("this-is-a-sample-17.adoc"),
+            String::from("this-is-a-smple4.adoc"),
+            String::from("this-is-a-smple7.adoc"),
+        ];
 
         let mut adr_vec = Vec::new();
         for adr in paths.into_iter() {
             adr_vec.push(super::Adr::from(
                 String::from("/adr/"),
-                String::from(adr),
+                adr,
                 String::from(ADOC_TMPL_NOTAG),
+                "",
             ));
         }
 
-        let seq = super::get_last_seq_id(adr_vec);
+        let seq = super::get_last_seq_id(adr_vec, "{id}-{slug}.adoc");
         assert_eq!(seq, 0);
     }
 
     #[test]
     fn test_extract_seq_id_from_all_with_nested_dir() {
-        let mut paths = Vec::new();
-        paths.push(String::from("mypath/mysubpath/00000064-my-decision.adoc")); //this is should be the last seq id
-        paths.push(String::from("mypath/00000063-my-decision.adoc")); //this is should be the last seq id
-        paths.push(String::from("00000010-my-decision.adoc"));
-        paths.push(String::from("00000001-my-decision-594-full.adoc"));
-        paths.push(String::from(
-            "mypath/00000001/00000002-my-decision-594-full.adoc",
-        ));
-        paths.push(String::from("path/my-decision-full.adoc"));
-        paths.push(String::from("path/my-decision-543-0.adoc"));
+        let paths = vec![
+            String::from("mypath/mysubpath/00000064-my-decision.adoc"), //this is should be the last seq id
+            String::from("mypath/00000063-my-decision.adoc"), //this is should be the last seq id
+            String::from("00000010-my-decision.adoc"),
+            String::from("00000001-my-decision-594-full.adoc"),
+            String::from("mypath/00000001/00000002-my-decision-594-full.adoc"),
+            String::from("path/my-decision-full.adoc"),
+            String::from("path/my-decision-543-0.adoc"),
+        ];
 
         let mut adr_vec = Vec::new();
         for adr in paths.into_iter() {
             adr_vec.push(super::Adr::from(
                 String::from("/adr/"),
-                String::from(adr),
+                adr,
                 String::from(ADOC_TMPL_NOTAG),
+                "",
             ));
         }
 
         adr_vec = super::sort_by_id(adr_vec);
-        let seq = super::get_last_seq_id(adr_vec);
+        let seq = super::get_last_seq_id(adr_vec, "{id}-{slug}.adoc");
         assert_eq!(seq, 64);
     }
 
@@ -1302,6 +2341,7 @@ mod tests {
             "base_path".to_string(),
             "a_path".to_string(),
             content.to_string(),
+            "",
         );
 
         assert_eq!(adr_sut.title, "ADR-MVA-507 Decide about ...");
@@ -1359,6 +2399,7 @@ mod tests {
             adr_search_index: format!("{}", src.path().display()),
             use_id_prefix: false,
             id_prefix_width: 3,
+            ..Default::default()
         };
 
         let to = PathBuf::from(src.path()).join("template.adoc");
@@ -1368,8 +2409,8 @@ mod tests {
         let created = super::create_adr(config, None, "title of the ADR");
         //
         assert!(created.unwrap());
-        assert_eq!(true, src.path().exists());
-        assert_eq!(true, src.path().join("title-of-the-adr.adoc").exists());
+        assert!(src.path().exists());
+        assert!(src.path().join("title-of-the-adr.adoc").exists());
     }
 
     #[test]
@@ -1395,6 +2436,7 @@ mod tests {
             adr_search_index: format!("{}", src.path().display()),
             use_id_prefix: true,
             id_prefix_width: 3,
+            ..Default::default()
         };
 
         let to = PathBuf::from(src.path()).join("template.adoc");
@@ -1410,8 +2452,8 @@ mod tests {
         let created = super::create_adr(config, None, "title of the ADR");
         //
         assert!(created.unwrap());
-        assert_eq!(true, src.path().exists());
-        assert_eq!(true, src.path().join("004-title-of-the-adr.adoc").exists());
+        assert!(src.path().exists());
+        assert!(src.path().join("004-title-of-the-adr.adoc").exists());
     }
 
     #[test]
@@ -1437,6 +2479,7 @@ mod tests {
             adr_search_index: format!("{}", src.path().display()),
             use_id_prefix: true,
             id_prefix_width: 3,
+            ..Default::default()
         };
 
         let to = PathBuf::from(src.path()).join("template.adoc");
@@ -1453,9 +2496,8 @@ mod tests {
             let created = super::create_adr(config.clone(), Some("sub_dir"), "title of the ADR");
             //
             assert!(created.unwrap());
-            assert_eq!(true, src.path().exists());
-            assert_eq!(
-                true,
+            assert!(src.path().exists());
+            assert!(
                 src.path()
                     .join("sub_dir")
                     .join("004-title-of-the-adr.adoc")
@@ -1467,9 +2509,8 @@ mod tests {
             let created = super::create_adr(config.clone(), Some("./sub_dir"), "title of the ADR");
             //
             assert!(created.unwrap());
-            assert_eq!(true, src.path().exists());
-            assert_eq!(
-                true,
+            assert!(src.path().exists());
+            assert!(
                 src.path()
                     .join("sub_dir")
                     .join("005-title-of-the-adr.adoc")
@@ -1478,6 +2519,189 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_check_ids() {
+        let src = TempDir::new("my_src_folder").unwrap();
+        fs::write(src.path().join("001-ADR-1.adoc"), ADOC_TMPL_NOTAG).unwrap();
+        fs::write(src.path().join("003-ADR-3.adoc"), ADOC_TMPL_NOTAG).unwrap();
+        fs::create_dir_all(src.path().join("sub_dir")).unwrap();
+        fs::write(src.path().join("sub_dir/003-ADR-other-3.adoc"), ADOC_TMPL_NOTAG).unwrap();
+        fs::write(src.path().join("005-ADR-5.adoc"), ADOC_TMPL_NOTAG).unwrap();
+
+        let report = super::check_ids(src.path(), true).unwrap();
+        assert_eq!(1, report.duplicates.len());
+        assert_eq!(3, report.duplicates[0].0);
+        assert_eq!(2, report.duplicates[0].1.len());
+        assert_eq!(vec![2, 4], report.missing);
+
+        let report = super::check_ids(src.path(), false).unwrap();
+        assert!(report.missing.is_empty());
+
+        //large holes (e.g. timestamp ids) are not missing ids
+        fs::write(src.path().join("20240101120000-ADR-6.adoc"), ADOC_TMPL_NOTAG).unwrap();
+        let report = super::check_ids(src.path(), true).unwrap();
+        assert_eq!(vec![2, 4], report.missing);
+    }
+
+    #[test]
+    fn test_renumber_adr() {
+        let src = TempDir::new("my_src_folder").unwrap();
+        let config = AdrToolConfig {
+            log_level: 6,
+            adr_src_dir: format!("{}", src.path().display()),
+            use_id_prefix: true,
+            id_prefix_width: 3,
+            ..Default::default()
+        };
+
+        fs::write(src.path().join("001-ADR-1.adoc"), ADOC_TMPL_NOTAG).unwrap();
+        fs::write(
+            src.path().join("002-ADR-2.adoc"),
+            ADOC_TMPL_NOTAG.replace("{decided}", "{superseded} 002-ADR-other-2.adoc"),
+        )
+        .unwrap();
+        fs::write(
+            src.path().join("002-ADR-other-2.adoc"),
+            ADOC_TMPL_NOTAG.replace("{decided}", "{supersedes} 002-ADR-2.adoc"),
+        )
+        .unwrap();
+        //other file names and the prose are not references
+        fs::write(
+            src.path().join("ADR-notes.adoc"),
+            format!(
+                ":adr-completes: 002-ADR-other-2.adoc 1002-ADR-other-2.adoc\n{}\nSee 002-ADR-other-2.adoc\n",
+                ADOC_TMPL_NOTAG.replace("{decided}", "{completed} 1002-ADR-other-2.adoc")
+            ),
+        )
+        .unwrap();
+
+        let new_path = super::renumber_adr(config.clone(), "002-ADR-other-2.adoc", None).unwrap();
+
+        assert_eq!(
+            format!("{}", src.path().join("003-ADR-other-2.adoc").display()),
+            new_path
+        );
+        assert!(!src.path().join("002-ADR-other-2.adoc").exists());
+        let content = fs::read_to_string(src.path().join("002-ADR-2.adoc")).unwrap();
+        assert!(content.contains("{superseded} 003-ADR-other-2.adoc"));
+        let content = fs::read_to_string(src.path().join("ADR-notes.adoc")).unwrap();
+        assert!(content.contains(":adr-completes: 003-ADR-other-2.adoc 1002-ADR-other-2.adoc\n"));
+        assert!(content.contains("{completed} 1002-ADR-other-2.adoc"));
+        assert!(content.contains("See 002-ADR-other-2.adoc"));
+
+        //target already exists
+        assert!(super::renumber_adr(config, "003-ADR-other-2.adoc", Some(3)).is_err());
+    }
+
+    #[test]
+    fn test_create_adr_w_timestamp_id() {
+        let src = TempDir::new("my_src_folder").unwrap();
+        let config = AdrToolConfig {
+            log_level: 6,
+            adr_src_dir: format!("{}", src.path().display()),
+            adr_template_dir: format!("{}", src.path().display()),
+            adr_template_file: String::from("template.adoc"),
+            use_id_prefix: true,
+            id_prefix_width: 3,
+            id_strategy: String::from(adr_config::config::ID_STRATEGY_TIMESTAMP),
+            ..Default::default()
+        };
+        fs::write(src.path().join("template.adoc"), ADOC_TMPL_NOTAG).unwrap();
+
+        let today = Utc::now().format("%Y%m%d").to_string();
+        let name = super::format_decision_name(config, "title of the ADR").unwrap();
+        assert!(name.starts_with(today.as_str()));
+        assert!(name.ends_with("-title-of-the-adr"));
+    }
+
+    #[test]
+    fn test_create_adr_from_template() {
+        let src = TempDir::new("my_src_folder").unwrap();
+        let config = AdrToolConfig {
+            log_level: 6,
+            adr_src_dir: format!("{}", src.path().display()),
+            adr_template_dir: format!("{}", src.path().join("templates").display()),
+            use_id_prefix: true,
+            id_prefix_width: 3,
+            ..Default::default()
+        };
+
+        let mut vars = HashMap::new();
+        vars.insert(String::from("tags"), String::from("kafka, messaging"));
+        vars.insert(String::from("deciders"), String::from("Alice, Bob"));
+        let created = super::create_adr_from_template(
+            config.clone(),
+            None,
+            "Use Kafka",
+            Some("lightweight"),
+            &vars,
+        )
+        .unwrap();
+        assert_eq!(created, Some(src.path().join("001-use-kafka.adoc")));
+
+        let adr = super::build_adr(src.path(), &src.path().join("001-use-kafka.adoc")).unwrap();
+        assert_eq!(adr.title, "Use Kafka");
+        assert_eq!(adr.status, Status::WIP);
+        assert_eq!(adr.date, Utc::now().format("%Y-%m-%d").to_string());
+        assert_eq!(adr.tags_array, vec!["kafka ", "messaging "]);
+        assert_eq!(adr.deciders, vec!["Alice", "Bob"]);
+        assert!(adr.content.contains("=== Consequences"));
+        assert!(!adr.content.contains("{{"));
+
+        let created = super::create_adr_from_template(config, None, "Another", Some("unknown"), &vars);
+        assert!(created.is_err());
+    }
+
+    #[test]
+    fn test_create_adr_from_legacy_template() {
+        let src = TempDir::new("my_src_folder").unwrap();
+        let config = AdrToolConfig {
+            log_level: 6,
+            adr_src_dir: format!("{}", src.path().display()),
+            adr_template_dir: format!("{}", src.path().display()),
+            adr_template_file: String::from("template.adoc"),
+            use_id_prefix: false,
+            ..Default::default()
+        };
+        fs::write(src.path().join("template.adoc"), ADOC_TMPL_NOTAG).unwrap();
+
+        let mut vars = HashMap::new();
+        vars.insert(String::from("tags"), String::from("kafka"));
+        vars.insert(String::from("deciders"), String::from("Alice"));
+        assert!(super::create_adr_from_template(config, None, "Use Kafka", None, &vars)
+            .unwrap()
+            .is_some());
+
+        let adr = super::build_adr(src.path(), &src.path().join("use-kafka.adoc")).unwrap();
+        assert_eq!(adr.title, "Use Kafka");
+        assert_eq!(adr.tags_array, vec!["kafka "]);
+        assert_eq!(adr.deciders, vec!["Alice"]);
+    }
+
+    #[test]
+    fn test_list_sub_directories() {
+        let src = TempDir::new("my_src_folder").unwrap();
+        fs::create_dir_all(src.path().join("data/streaming")).unwrap();
+        fs::create_dir_all(src.path().join("security")).unwrap();
+        fs::create_dir_all(src.path().join(".git/objects")).unwrap();
+        fs::write(src.path().join("001-ADR-1.adoc"), ADOC_TMPL_TAG).unwrap();
+
+        let dirs = super::list_sub_directories(src.path()).unwrap();
+        assert_eq!(dirs, vec!["data", "data/streaming", "security"]);
+    }
+
+    #[test]
+    fn test_is_adr_file() {
+        let base = Path::new("/tmp/adr");
+        assert!(super::is_adr_file(base, Path::new("/tmp/adr/000001-use-kafka.adoc")));
+        assert!(super::is_adr_file(base, Path::new("/tmp/adr/data/000002-use-postgres.adoc")));
+        assert!(!super::is_adr_file(base, Path::new("/tmp/adr/.index/meta.json")));
+        assert!(!super::is_adr_file(base, Path::new("/tmp/adr/.git/000001-use-kafka.adoc")));
+        assert!(!super::is_adr_file(base, Path::new("/tmp/adr/data/.000001-use-kafka.adoc.swp")));
+        assert!(!super::is_adr_file(base, Path::new("/tmp/adr/README.md")));
+        assert!(!super::is_adr_file(base, Path::new("/tmp/other/000001-use-kafka.adoc")));
+    }
+
     #[test]
     fn test_get_tags_popularity() {
         let src = match TempDir::new("my_src_folder") {
@@ -1520,6 +2744,7 @@ mod tests {
             "base_path".to_string(),
             "a_path".to_string(),
             content.to_string(),
+            "",
         );
 
         assert_eq!(adr_sut.title, "ADR-MVA-507 Decide about ...");
@@ -1529,6 +2754,251 @@ mod tests {
         assert_eq!(adr_sut.tags, "");
     }
 
+    #[test]
+    fn test_build_adr_from_attributes() {
+        let content = ":docinfo1:
+:wip: pass:quotes[[.label.wip]#In Progress#]
+:adr-status: decided
+:adr-date: 2024-02-12
+:adr-tags: security, network view
+:adr-id: 42
+:adr-deciders: Alice, Bob
+
+== {%%ADR TITLE%%}
+
+*Status:* {wip}  *Date:* 2019-10-28
+
+[tags]#deployment#";
+
+        let adr_sut = super::Adr::from(
+            "base_path".to_string(),
+            "a_path.adoc".to_string(),
+            content.to_string(),
+            "",
+        );
+
+        assert_eq!(adr_sut.title, "{%%ADR TITLE%%}");
+        assert_eq!(adr_sut.status, super::Status::DECIDED);
+        assert_eq!(adr_sut.date, "2024-02-12");
+        assert_eq!(adr_sut.tags, "#security #network view ");
+        assert_eq!(adr_sut.tags_array, vec!["security ", "network view "]);
+        assert_eq!(adr_sut.file_id, 42);
+        assert_eq!(adr_sut.deciders, vec!["Alice", "Bob"]);
+    }
+
+    #[test]
+    fn test_build_adr_fallback_markers() {
+        let content = ":docinfo1:
+
+== {%%ADR TITLE%%}
+
+*Status:* {wip}  *Date:* 2019-10-28
+
+|===
+|a = b |c
+|===";
+
+        let adr_sut = super::Adr::from(
+            "base_path".to_string(),
+            "a_path.adoc".to_string(),
+            content.to_string(),
+            "",
+        );
+
+        //the title placeholder must not be taken as the status
+        assert_eq!(adr_sut.status, super::Status::WIP);
+        assert_eq!(adr_sut.title, "{%%ADR TITLE%%}");
+        assert_eq!(adr_sut.date, "2019-10-28");
+        assert!(adr_sut.attributes.is_empty());
+    }
+
+    #[test]
+    fn test_update_status_with_attributes() {
+        let content = ":docinfo1:
+:adr-status: wip
+:adr-date: 2019-10-28
+
+== my decision
+
+*Status:* {wip}  *Date:* 2019-10-28
+....";
+
+        let mut adr_sut = super::Adr::from(
+            "base_path".to_string(),
+            "a_path.adoc".to_string(),
+            content.to_string(),
+            "",
+        );
+
+        assert!(adr_sut.update_status(TransitionStatus::DECIDED));
+
+        let today = Utc::now().date_naive().format("%Y-%m-%d").to_string();
+        assert!(adr_sut.content.contains(":adr-status: decided\n"));
+        assert!(adr_sut.content.contains(format!(":adr-date: {}\n", today).as_str()));
+        assert!(adr_sut
+            .content
+            .contains(format!("*Status:* {{decided}}  *Date:* {}", today).as_str()));
+    }
+
+    #[test]
+    fn test_migrate_to_attributes() {
+        let content = ":docinfo1:
+:wip: pass:quotes[[.label.wip]#In Progress#]
+
+== my decision
+
+*Status:* {decided}  *Date:* 2019-10-28
+
+[tags]#tag1# [tags]#tag2#
+";
+
+        let mut adr_sut = super::Adr::from(
+            "base_path".to_string(),
+            "000012-my-decision.adoc".to_string(),
+            content.to_string(),
+            "",
+        );
+
+        let added = adr_sut.migrate_to_attributes();
+        assert_eq!(added, vec![ATTR_STATUS, ATTR_DATE, ATTR_TAGS, ATTR_ID]);
+        assert!(adr_sut.content.starts_with(
+            ":docinfo1:
+:wip: pass:quotes[[.label.wip]#In Progress#]
+:adr-status: decided
+:adr-date: 2019-10-28
+:adr-tags: tag1, tag2
+:adr-id: 12
+
+== my decision"
+        ));
+
+        //already migrated, nothing to do
+        let mut adr_sut = super::Adr::from(
+            "base_path".to_string(),
+            "000012-my-decision.adoc".to_string(),
+            adr_sut.content,
+            "",
+        );
+        assert!(adr_sut.migrate_to_attributes().is_empty());
+        assert_eq!(adr_sut.status, Status::DECIDED);
+        assert_eq!(adr_sut.tags_array, vec!["tag1 ", "tag2 "]);
+    }
+
+    #[test]
+    fn test_get_roles() {
+        let content = "== my decision
+
+[cols=\"1h,4\",options=\"header\",]
+|===
+|role |people
+|_Decision Makers_ |@Alice Martin, @Bob
+|_Experts_ |@Carol
+|_Stakeholders_ |Dave, Erin
+|===";
+
+        let adr_sut = super::Adr::from(
+            "base_path".to_string(),
+            "a_path.adoc".to_string(),
+            content.to_string(),
+            "",
+        );
+
+        assert_eq!(adr_sut.deciders, vec!["Alice Martin", "Bob"]);
+        assert_eq!(adr_sut.experts, vec!["Carol"]);
+        assert_eq!(adr_sut.stakeholders, vec!["Dave", "Erin"]);
+    }
+
+    #[test]
+    fn test_get_people_participation() {
+        let src = TempDir::new("my_src_folder").unwrap();
+
+        let content = "== my decision
+
+*Status:* {decided}  *Date:* 2019-10-28
+
+|===
+|role |people
+|_Decision Makers_ |@Alice
+|_Experts_ |@Alice @Bob
+|_Stakeholders_ |@Carol
+|===";
+        fs::write(src.path().join("001-ADR-1.adoc"), content).unwrap();
+        fs::write(src.path().join("002-ADR-2.adoc"), content.replace("@Carol", "@Bob")).unwrap();
+
+        let people = super::get_people_participation(src.path()).unwrap();
+        assert_eq!(3, people.len());
+        assert_eq!(
+            Some(&Participation {
+                decisions: 2,
+                as_decider: 2,
+                as_expert: 2,
+                as_stakeholder: 0
+            }),
+            people.get("Alice")
+        );
+        assert_eq!(
+            Some(&Participation {
+                decisions: 2,
+                as_decider: 0,
+                as_expert: 2,
+                as_stakeholder: 1
+            }),
+            people.get("Bob")
+        );
+        assert_eq!(1, people.get("Carol").unwrap().decisions);
+    }
+
+    #[test]
+    fn test_get_options_and_outcome() {
+        let content = fs::read_to_string("../templates/adr-template-v0.1.adoc").unwrap();
+
+        let adr_sut = super::Adr::from(
+            "base_path".to_string(),
+            "a_path.adoc".to_string(),
+            content,
+            "",
+        );
+
+        assert_eq!(2, adr_sut.options.len());
+        assert_eq!("Option #1", adr_sut.options[0].name);
+        assert_eq!("Manage", adr_sut.options[0].description);
+        assert_eq!(
+            vec!["Simplicity for ....", "Simplicity for ...."],
+            adr_sut.options[0].pros
+        );
+        assert!(adr_sut.options[0].cons.is_empty());
+        assert_eq!(vec!["Capabilities ...."], adr_sut.options[1].pros);
+        assert_eq!(vec!["Complexity in ..."], adr_sut.options[1].cons);
+
+        assert_eq!(Some("Option #2".to_string()), adr_sut.outcome);
+        assert_eq!("Option #2", adr_sut.chosen_option().unwrap().name);
+    }
+
+    #[test]
+    fn test_chosen_option() {
+        let option = |name: &str| AdrOption {
+            name: name.to_string(),
+            ..Default::default()
+        };
+        let mut adr_sut = Adr::new();
+        adr_sut.options = vec![option("Option #1"), option("Option #2"), option("Kafka"), option("Kafka Streams")];
+
+        adr_sut.outcome = Some(String::from("Chosen option: Option #2, because..."));
+        assert_eq!("Option #2", adr_sut.chosen_option().unwrap().name);
+        //not a whole option name
+        adr_sut.outcome = Some(String::from("Chosen option: Option #12"));
+        assert!(adr_sut.chosen_option().is_none());
+        //the longest name
+        adr_sut.outcome = Some(String::from("We go with Kafka Streams"));
+        assert_eq!("Kafka Streams", adr_sut.chosen_option().unwrap().name);
+        adr_sut.outcome = Some(String::from("We go with Kafka"));
+        assert_eq!("Kafka", adr_sut.chosen_option().unwrap().name);
+
+        let options = adr_sut.options.clone();
+        let content = "== Decision Outcome\n\nWe go with option #12 or Kafka Streams.\n";
+        assert_eq!(Some(String::from("Kafka Streams")), Adr::get_outcome(content, &options));
+    }
+
     #[test]
     fn test_update_date() {
         let content = "
@@ -1541,6 +3011,7 @@ mod tests {
             "base_path".to_string(),
             "a_path".to_string(),
             content.to_string(),
+            "",
         );
 
         assert_eq!(adr_sut.date, "2019-10-28");
@@ -1552,7 +3023,7 @@ mod tests {
         assert_eq!(adr_sut.date, date.to_string());
 
         let contain = format!("*Status:* {{wip}}  *Date:* {}", date);
-        assert_eq!(true, adr_sut.content.contains(contain.as_str()));
+        assert!(adr_sut.content.contains(contain.as_str()));
     }
 
     #[test]
@@ -1567,14 +3038,14 @@ mod tests {
             "base_path".to_string(),
             "a_path".to_string(),
             content.to_string(),
+            "",
         );
 
         assert_eq!(adr_sut.title, "ADR-MVA-507 Decide about ...");
         adr_sut.update_title("This is a new completly amazing title");
 
         assert_eq!(adr_sut.title, "This is a new completly amazing title");
-        assert_eq!(
-            true,
+        assert!(
             adr_sut
                 .content
                 .contains("== This is a new completly amazing title")
@@ -0,0 +1,268 @@
+extern crate slog;
+extern crate slog_term;
+use slog::*;
+
+use std::io::{self};
+use std::path::Path;
+
+extern crate adr_config;
+use adr_config::config::AdrToolConfig;
+
+use crate::adr_git::{self, AdrChange, Change};
+use crate::adr_lint::{self, LintIssue, LintRules};
+use crate::adr_repo::{self, Adr};
+
+fn get_logger() -> slog::Logger {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+
+    let decorator = slog_term::TermDecorator::new().build();
+    let drain = slog_term::FullFormat::new(decorator).build().fuse();
+    let drain = slog_async::Async::new(drain).build().fuse();
+    let drain = slog::LevelFilter::new(
+        drain,
+        Level::from_usize(cfg.log_level).unwrap_or(Level::Debug),
+    )
+    .fuse();
+
+    slog::Logger::root(drain, o!())
+}
+
+/// What a branch does to the decision log, compared to its base
+#[derive(Debug, Clone, PartialEq)]
+pub struct Review {
+    pub base: String,
+    /// a git revision, or `working tree`
+    pub head: String,
+    pub changes: Vec<AdrChange>,
+    /// the tags used by the head ADRs but by none of the base ones
+    pub new_tags: Vec<String>,
+    /// the problems of the added and modified ADRs
+    pub issues: Vec<LintIssue>,
+}
+
+/// Compares the Decision Records of `head` (a git revision, the working tree if None) to the ones of `base`
+pub fn review(cfg: &AdrToolConfig, base: &str, head: Option<&str>, rules: &LintRules) -> io::Result<Review> {
+    let base_adrs = adr_git::list_all_adr_at(cfg, base)?;
+    let head_adrs = match head {
+        Some(rev) => adr_git::list_all_adr_at(cfg, rev)?,
+        None => adr_repo::list_all_adr(Path::new(&cfg.adr_src_dir))?,
+    };
+    let changes = adr_git::compare(&base_adrs, &head_adrs);
+
+    let tags = |adrs: &[Adr]| -> Vec<String> {
+        let mut tags: Vec<String> = adrs
+            .iter()
+            .flat_map(|adr| adr.tags_array.iter().map(|t| t.trim().to_string()))
+            .filter(|t| !t.is_empty())
+            .collect();
+        tags.sort();
+        tags.dedup();
+        tags
+    };
+    let base_tags = tags(&base_adrs);
+    let new_tags: Vec<String> = tags(&head_adrs).into_iter().filter(|t| !base_tags.contains(t)).collect();
+
+    //only the problems of what the branch touches
+    let touched: Vec<&Adr> = head_adrs
+        .iter()
+        .filter(|adr| changes.iter().any(|c| c.change != Change::REMOVED && c.path == adr.file_path))
+        .collect();
+    let mut issues: Vec<LintIssue> = touched.iter().flat_map(|adr| adr_lint::lint_adr(adr, rules)).collect();
+    issues.extend(
+        adr_lint::check_links(&head_adrs)
+            .into_iter()
+            .filter(|issue| touched.iter().any(|adr| adr.path() == issue.file))
+            .filter(|issue| !rules.disabled_rules.contains(&issue.rule)),
+    );
+    //relative to adr_src_dir, the paths are the same in both cases
+    for issue in issues.iter_mut() {
+        if let Some(adr) = touched.iter().find(|adr| adr.path() == issue.file) {
+            issue.file = adr.file_path.clone();
+        }
+    }
+
+    let head = head.unwrap_or("working tree").to_string();
+    debug!(get_logger(), "[{}] change(s) between [{}] and [{}]", changes.len(), base, head);
+
+    Ok(Review {
+        base: base.to_string(),
+        head,
+        changes,
+        new_tags,
+        issues,
+    })
+}
+
+impl Review {
+    pub fn get_changes(&self, change: Change) -> Vec<&AdrChange> {
+        self.changes.iter().filter(|c| c.change == change).collect()
+    }
+
+    /// The decisions already made in `base` whose content changed, they may need to be approved again
+    pub fn get_edited_decided(&self) -> Vec<&AdrChange> {
+        self.changes
+            .iter()
+            .filter(|c| c.change == Change::EDITED && c.from.map(|s| s.is_decided()).unwrap_or(false))
+            .collect()
+    }
+
+    /// A Markdown summary, e.g. to be pasted as a pull request comment
+    pub fn to_markdown(&self) -> String {
+        let cell = |val: &str| val.replace('|', "\\|");
+        let status = |c: &AdrChange, before: bool| {
+            let status = if before { c.from } else { c.to };
+            status.map(|s| s.as_str()).unwrap_or("")
+        };
+        let adr_table = |changes: &[&AdrChange], before: bool| {
+            let mut table = String::from("| ID | Title | Status | File |\n| --- | --- | --- | --- |\n");
+            for c in changes {
+                table.push_str(&format!(
+                    "| {} | {} | {} | `{}` |\n",
+                    c.id,
+                    cell(&c.title),
+                    status(c, before),
+                    c.path
+                ));
+            }
+            table
+        };
+
+        let added = self.get_changes(Change::ADDED);
+        let removed = self.get_changes(Change::REMOVED);
+        let transitions = self.get_changes(Change::STATUS);
+        let edited = self.get_edited_decided();
+
+        let mut md = format!("## Decision Records review: `{}` → `{}`\n\n", self.base, self.head);
+        if added.is_empty()
+            && removed.is_empty()
+            && transitions.is_empty()
+            && edited.is_empty()
+            && self.new_tags.is_empty()
+            && self.issues.is_empty()
+        {
+            md.push_str("No change to the Decision Records.\n");
+            return md;
+        }
+        md.push_str(&format!(
+            "**{} new, {} status transition(s), {} edited decided, {} removed, {} new tag(s), {} lint problem(s)**\n",
+            added.len(),
+            transitions.len(),
+            edited.len(),
+            removed.len(),
+            self.new_tags.len(),
+            self.issues.len()
+        ));
+
+        if !added.is_empty() {
+            md.push_str(&format!("\n### New Decision Records\n\n{}", adr_table(&added, false)));
+        }
+        if !transitions.is_empty() {
+            md.push_str("\n### Status transitions\n\n| ID | Title | From | To |\n| --- | --- | --- | --- |\n");
+            for c in transitions.iter() {
+                md.push_str(&format!(
+                    "| {} | {} | {} | **{}** |\n",
+                    c.id,
+                    cell(&c.title),
+                    status(c, true),
+                    status(c, false)
+                ));
+            }
+        }
+        if !edited.is_empty() {
+            md.push_str("\n### Edited decided Decision Records\n\n");
+            md.push_str("> :warning: These decisions have already been made, their changes may need to be approved again.\n\n");
+            md.push_str(&adr_table(&edited, false));
+        }
+        if !removed.is_empty() {
+            md.push_str(&format!("\n### Removed Decision Records\n\n{}", adr_table(&removed, true)));
+        }
+        if !self.new_tags.is_empty() {
+            let tags: Vec<String> = self.new_tags.iter().map(|t| format!("`{}`", t)).collect();
+            md.push_str(&format!("\n### New tags\n\n{}\n", tags.join(", ")));
+        }
+        if !self.issues.is_empty() {
+            md.push_str("\n### Lint problems\n\n| File | Line | Rule | Severity | Message |\n| --- | --- | --- | --- | --- |\n");
+            for issue in self.issues.iter() {
+                md.push_str(&format!(
+                    "| `{}` | {} | {} | {} | {} |\n",
+                    issue.file,
+                    issue.line,
+                    issue.rule,
+                    issue.severity.as_str(),
+                    cell(&issue.message)
+                ));
+            }
+        }
+
+        md
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use adr_config::config::AdrToolConfig;
+    use tempdir::TempDir;
+
+    use crate::adr_git::git;
+    use crate::adr_lint::LintRules;
+
+    #[test]
+    fn test_review() {
+        let dir = TempDir::new("my_git_folder").unwrap();
+        git(dir.path(), &["init", "-q", "-b", "main"]).unwrap();
+        let src_dir = dir.path().join("docs/adr");
+        fs::create_dir_all(&src_dir).unwrap();
+        fs::write(src_dir.join("000001-use-kafka.adoc"), "== Use Kafka\n\n*Status:* {decided}\n\n[tags]#kafka#\n").unwrap();
+        fs::write(src_dir.join("000002-use-redis.adoc"), "== Use Redis\n\n*Status:* {wip}\n").unwrap();
+        git(dir.path(), &["add", "-A"]).unwrap();
+        git(
+            dir.path(),
+            &["-c", "user.name=John Doe", "-c", "user.email=john@doe.com", "commit", "-q", "-m", "init"],
+        )
+        .unwrap();
+
+        fs::write(
+            src_dir.join("000001-use-kafka.adoc"),
+            "== Use Kafka | Streams\n\n*Status:* {decided}\n\n[tags]#kafka#\n",
+        )
+        .unwrap();
+        fs::write(src_dir.join("000002-use-redis.adoc"), "== Use Redis\n\n*Status:* {decided}\n").unwrap();
+        fs::write(
+            src_dir.join("000003-use-pulsar.adoc"),
+            "== Use Pulsar\n\n*Status:* {wip}\n\n[tags]#messaging#\n\n{superseded} 000009-nope.adoc\n",
+        )
+        .unwrap();
+
+        let cfg = AdrToolConfig {
+            adr_src_dir: format!("{}", src_dir.display()),
+            ..Default::default()
+        };
+        let review = super::review(&cfg, "main", None, &LintRules::default()).unwrap();
+        assert_eq!(review.head, "working tree");
+        assert_eq!(review.get_changes(crate::adr_git::Change::ADDED).len(), 1);
+        assert_eq!(review.get_changes(crate::adr_git::Change::STATUS).len(), 1);
+        let edited: Vec<usize> = review.get_edited_decided().iter().map(|c| c.id).collect();
+        assert_eq!(edited, vec![1]);
+        assert_eq!(review.new_tags, vec!["messaging"]);
+        assert!(review
+            .issues
+            .iter()
+            .any(|i| i.file == "000003-use-pulsar.adoc" && i.rule == crate::adr_lint::RULE_LINK_MISSING));
+
+        let md = review.to_markdown();
+        assert!(md.starts_with("## Decision Records review: `main` → `working tree`\n"));
+        assert!(md.contains("### New Decision Records\n\n| ID | Title | Status | File |\n| --- | --- | --- | --- |\n| 3 | Use Pulsar | wip | `000003-use-pulsar.adoc` |\n"));
+        assert!(md.contains("| 2 | Use Redis | wip | **decided** |\n"));
+        assert!(md.contains("| 1 | Use Kafka \\| Streams | decided | `000001-use-kafka.adoc` |\n"));
+        assert!(md.contains("### New tags\n\n`messaging`\n"));
+        assert!(md.contains("### Lint problems\n"));
+
+        //nothing changed in HEAD
+        let review = super::review(&cfg, "main", Some("HEAD"), &LintRules::default()).unwrap();
+        assert!(review.changes.is_empty());
+        assert!(review.to_markdown().ends_with("No change to the Decision Records.\n"));
+        assert!(super::review(&cfg, "unknown", None, &LintRules::default()).is_err());
+    }
+}
@@ -0,0 +1,249 @@
+extern crate slog;
+extern crate slog_term;
+use slog::*;
+
+use std::collections::HashMap;
+use std::fs::{self};
+use std::io::{self};
+use std::path::{Path, PathBuf};
+
+extern crate regex;
+use regex::{Captures, Regex};
+
+extern crate adr_config;
+use adr_config::config::AdrToolConfig;
+
+use crate::adr_git;
+
+fn get_logger() -> slog::Logger {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+
+    let decorator = slog_term::TermDecorator::new().build();
+    let drain = slog_term::FullFormat::new(decorator).build().fuse();
+    let drain = slog_async::Async::new(drain).build().fuse();
+    let drain = slog::LevelFilter::new(
+        drain,
+        Level::from_usize(cfg.log_level).unwrap_or(Level::Debug),
+    )
+    .fuse();
+
+    slog::Logger::root(drain, o!())
+}
+
+pub const TEMPLATE_FULL: &str = "full";
+pub const TEMPLATE_LIGHTWEIGHT: &str = "lightweight";
+pub const TEMPLATE_SECURITY_REVIEW: &str = "security-review";
+
+pub const VAR_TITLE: &str = "title";
+pub const VAR_ID: &str = "id";
+pub const VAR_DATE: &str = "date";
+pub const VAR_AUTHOR: &str = "author";
+pub const VAR_TAGS: &str = "tags";
+pub const VAR_SLUG: &str = "slug";
+pub const VAR_DECIDERS: &str = "deciders";
+
+lazy_static! {
+    static ref RE_VAR: Regex = Regex::new(r"\{\{\s*([\w-]+)\s*\}\}").unwrap();
+}
+
+/// The templates shipped with adrust, available even if they are not in `adr_template_dir`
+const BUILTIN_TEMPLATES: [(&str, &str); 3] = [
+    (
+        TEMPLATE_FULL,
+        include_str!("../../../templates/adr-template-v0.1.adoc"),
+    ),
+    (
+        TEMPLATE_LIGHTWEIGHT,
+        include_str!("../../../templates/adr-template-lightweight.adoc"),
+    ),
+    (
+        TEMPLATE_SECURITY_REVIEW,
+        include_str!("../../../templates/adr-template-security-review.adoc"),
+    ),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    pub name: String,
+    /// None for a built-in template
+    pub path: Option<String>,
+    pub content: String,
+}
+
+/// Lists the built-in templates and the `.adoc` files available in `adr_template_dir` (named after their file stem).
+/// A file named after a built-in template overrides it.
+pub fn list_templates(cfg: &AdrToolConfig) -> io::Result<Vec<Template>> {
+    let mut templates: Vec<Template> = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(name, content)| Template {
+            name: name.to_string(),
+            path: None,
+            content: content.to_string(),
+        })
+        .collect();
+
+    let template_dir = Path::new(&cfg.adr_template_dir);
+    if !template_dir.is_dir() {
+        debug!(get_logger(), "[{}] does not exist, only built-in templates are available", template_dir.display());
+        return Ok(templates);
+    }
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(template_dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file() && path.extension().map(|e| e == "adoc").unwrap_or(false))
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        let name = path.file_stem().unwrap().to_string_lossy().to_string();
+        let template = Template {
+            name: name.clone(),
+            path: Some(path.display().to_string()),
+            content: fs::read_to_string(&path)?,
+        };
+        match templates.iter_mut().find(|t| t.name == name) {
+            Some(builtin) => *builtin = template,
+            None => templates.push(template),
+        }
+    }
+
+    Ok(templates)
+}
+
+/// Returns the template `name` (a file stem or file name in `adr_template_dir`, or a built-in template)
+pub fn get_template(cfg: &AdrToolConfig, name: &str) -> io::Result<Template> {
+    let name = name.trim_end_matches(".adoc");
+    match list_templates(cfg)?.into_iter().find(|t| t.name == name) {
+        Some(template) => Ok(template),
+        None => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Template [{}] does not exist (see adr templates list)", name),
+        )),
+    }
+}
+
+/// Returns the attribute entries declaring the status labels (e.g. `:wip: ...`), i.e. the header of the built-in template
+/// without the `adr-*` attributes. Useful to build ADRs from scratch (e.g. when importing them).
+pub fn get_status_labels() -> String {
+    let mut labels = String::new();
+    for line in BUILTIN_TEMPLATES[0].1.lines() {
+        if line.starts_with(":adr-") || line.starts_with("==") {
+            break;
+        }
+        labels.push_str(line);
+        labels.push('\n');
+    }
+
+    labels
+}
+
+/// Copies `from` in `adr_template_dir` as `<name>.adoc`. Fails if the template file already exists.
+pub fn add_template(cfg: &AdrToolConfig, name: &str, from: &Path) -> io::Result<PathBuf> {
+    let name = name.trim_end_matches(".adoc");
+    if name.is_empty() || name.contains(['/', '\\']) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("[{}] is not a valid template name", name),
+        ));
+    }
+
+    let template_dir = Path::new(&cfg.adr_template_dir);
+    let target = template_dir.join(format!("{}.adoc", name));
+    if target.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("Template [{}] already exists", target.display()),
+        ));
+    }
+
+    fs::create_dir_all(template_dir)?;
+    fs::copy(from, &target)?;
+    info!(get_logger(), "Template [{}] added as [{}]", name, target.display());
+
+    Ok(target)
+}
+
+/// Replaces the `{{variable}}` placeholders of `template`. Unknown variables are left untouched.
+pub fn render(template: &str, vars: &HashMap<String, String>) -> String {
+    RE_VAR
+        .replace_all(template, |cap: &Captures| match vars.get(&cap[1]) {
+            Some(val) => val.to_string(),
+            None => {
+                warn!(get_logger(), "Unknown template variable [{}]", &cap[1]);
+                cap[0].to_string()
+            }
+        })
+        .to_string()
+}
+
+/// Returns true if `template` contains the `{{name}}` placeholder
+pub fn uses_variable(template: &str, name: &str) -> bool {
+    RE_VAR.captures_iter(template).any(|cap| &cap[1] == name)
+}
+
+/// Returns the git `user.name`, or the current user if git is not configured
+pub fn get_author() -> String {
+    if let Ok(name) = adr_git::git(Path::new("."), &["config", "user.name"]) {
+        let name = name.trim();
+        if !name.is_empty() {
+            return name.to_string();
+        }
+    }
+
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::fs;
+    use std::path::Path;
+
+    use adr_config::config::AdrToolConfig;
+    use tempdir::TempDir;
+
+    #[test]
+    fn test_render() {
+        let mut vars = HashMap::new();
+        vars.insert(String::from("title"), String::from("my Decision"));
+        vars.insert(String::from("tags"), String::from(""));
+
+        let rendered = super::render("== {{title}}\n:adr-tags: {{ tags }}\n{{unknown}} {title}", &vars);
+        assert_eq!(rendered, "== my Decision\n:adr-tags: \n{{unknown}} {title}");
+        assert!(super::uses_variable("== {{ title }}", "title"));
+        assert!(!super::uses_variable("== {{title}}", "tags"));
+    }
+
+    #[test]
+    fn test_list_and_add_templates() {
+        let dir = TempDir::new("my_template_folder").unwrap();
+        let cfg = AdrToolConfig {
+            adr_template_dir: format!("{}", dir.path().join("templates").display()),
+            ..Default::default()
+        };
+
+        //template dir does not exist yet
+        let templates = super::list_templates(&cfg).unwrap();
+        let names: Vec<&str> = templates.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["full", "lightweight", "security-review"]);
+        assert!(templates.iter().all(|t| t.path.is_none()));
+
+        let from = dir.path().join("rfc.adoc");
+        fs::write(&from, "== {{title}}").unwrap();
+        super::add_template(&cfg, "rfc", &from).unwrap();
+        assert!(super::add_template(&cfg, "rfc", &from).is_err());
+        assert!(super::add_template(&cfg, "../rfc", &from).is_err());
+        fs::write(Path::new(&cfg.adr_template_dir).join("lightweight.adoc"), "== overridden").unwrap();
+
+        let templates = super::list_templates(&cfg).unwrap();
+        assert_eq!(templates.len(), 4);
+        assert_eq!(super::get_template(&cfg, "rfc.adoc").unwrap().content, "== {{title}}");
+        let lightweight = super::get_template(&cfg, "lightweight").unwrap();
+        assert_eq!(lightweight.content, "== overridden");
+        assert!(lightweight.path.is_some());
+        assert!(super::get_template(&cfg, "security-review").unwrap().path.is_none());
+        assert!(super::get_template(&cfg, "unknown").is_err());
+    }
+}
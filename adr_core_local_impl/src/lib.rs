@@ -1,4 +1,13 @@
 #[macro_use]
 extern crate lazy_static;
+#[macro_use]
+extern crate serde_derive;
 
+pub mod adr_export;
+pub mod adr_git;
+pub mod adr_graph;
+pub mod adr_import;
+pub mod adr_lint;
 pub mod adr_repo;
+pub mod adr_review;
+pub mod adr_template;
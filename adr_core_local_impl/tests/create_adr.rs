@@ -33,7 +33,7 @@ mod helper {
     extern crate directories;
     use directories::ProjectDirs;
 
-    pub fn create_decision(name: &str, scenario_name: &String) -> io::Result<bool> {
+    pub fn create_decision(name: &str, scenario_name: &str) -> io::Result<bool> {
         let project_dirs: ProjectDirs = match ProjectDirs::from("murex", "adrust-tool", self::get_workspace(scenario_name).as_str()) {
             None => panic!("issue while preparing test"),
             Some(project_dirs) => project_dirs,
@@ -62,10 +62,8 @@ mod helper {
         Ok(adr_core::adr_repo::create_adr(cfg, None, name).unwrap())
     }
 
-    pub fn get_workspace(scenario_name: &String) -> String {
-        let val = format!("test-{}", scenario_name.replace(" ","-"));
-
-        return val.clone();
+    pub fn get_workspace(scenario_name: &str) -> String {
+        format!("test-{}", scenario_name.replace(" ","-"))
     }
 }
 
@@ -84,7 +82,7 @@ fn list_all_tags(_world: &mut AdrNames) {
 #[then(regex=r"A new file named (.+) is created$")]
 fn check_tags(adr: &mut AdrNames, name: String) {
     let is_created = helper::create_decision(&adr.name, &adr.scenario_name).unwrap();
-    assert_eq!(is_created, true);
+    assert!(is_created);
 
     // TODO there is certainly a way to return project_dir as part of create_decision
     let project_dirs: ProjectDirs = match ProjectDirs::from("murex", "adrust-tool", helper::get_workspace(&adr.scenario_name).as_str()) {
@@ -92,11 +90,11 @@ fn check_tags(adr: &mut AdrNames, name: String) {
         Some(project_dirs) => project_dirs
     };
     let t = project_dirs.cache_dir().join(
-        format!("src/{}.adoc", name.to_string())
+        format!("src/{}.adoc", name)
     );
     //
     let expected_path = t.as_path();
-    assert_eq!(expected_path.exists(), true);
+    assert!(expected_path.exists());
     //
     let content: String = fs::read_to_string(expected_path).unwrap();
     assert!(content.contains("{wip}"));
@@ -119,7 +117,7 @@ fn create_new_adr(_adr: &mut AdrNames) {
 fn new_adr_creation_fails(adr: &mut AdrNames) {
     //create the same file
     let is_created = helper::create_decision(&adr.name, &adr.scenario_name).unwrap();
-    assert_eq!(is_created, false);
+    assert!(!is_created);
 }
 
 
@@ -192,7 +190,7 @@ fn the_new_status_is(adr: &mut AdrNames, new_status: String, by: String) {
     assert_eq!(Status::from_str(new_status), new_adr.status);
     if "n/a" != by && adr.has_transitioned {
         let by = format!("}} {}", by);
-        assert_eq!(true, new_adr.content.contains(&by));
+        assert!(new_adr.content.contains(&by));
     }
 }
 
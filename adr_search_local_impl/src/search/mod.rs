@@ -7,6 +7,8 @@ use tantivy::query::QueryParser;
 use tantivy::schema::*;
 use tantivy::DateTime;
 use tantivy::Index;
+use tantivy::IndexSettings;
+use tantivy::IndexWriter;
 use tantivy::ReloadPolicy;
 
 use std::path::Path;
@@ -37,12 +39,7 @@ fn get_logger() -> slog::Logger {
     slog::Logger::root(drain, o!())
 }
 
-pub fn build_index(index_path: String, adrs: Vec<Adr>) -> tantivy::Result<()> {
-    info!(get_logger(), "Building Index in folder [{}]", index_path);
-
-    let now = Instant::now();
-    let index_path = Path::new(&index_path);
-
+fn get_schema() -> Schema {
     let mut schema_builder = Schema::builder();
     schema_builder.add_text_field("title", TEXT | STORED);
     schema_builder.add_text_field("status", TEXT | STORED);
@@ -50,52 +47,92 @@ pub fn build_index(index_path: String, adrs: Vec<Adr>) -> tantivy::Result<()> {
     schema_builder.add_text_field("body", TEXT);
     schema_builder.add_text_field("tags", TEXT | STORED);
     schema_builder.add_text_field("path", TEXT | STORED);
-    let schema = schema_builder.build();
-
-    let mmap_directory = MmapDirectory::open(index_path)?;
-    let index = Index::open_or_create(mmap_directory, schema.clone())?; // should use open_or_create to not overwrite existing index.
-    let mut index_writer = index.writer(100_000_000)?; //multi threaded behind the scene # of thread < 8
-
-    index_writer.delete_all_documents()?;
-    index_writer.commit()?;
+    schema_builder.add_text_field("deciders", TEXT);
+    schema_builder.add_text_field("experts", TEXT);
+    schema_builder.add_text_field("stakeholders", TEXT);
+    //the (not tokenized) path, to delete the document of an ADR
+    schema_builder.add_text_field("file", STRING);
+    schema_builder.build()
+}
 
+fn get_document(schema: &Schema, adr: &Adr) -> TantivyDocument {
     let title = schema.get_field("title").unwrap();
     let status = schema.get_field("status").unwrap();
     let date = schema.get_field("date").unwrap();
     let body = schema.get_field("body").unwrap();
     let tags = schema.get_field("tags").unwrap();
     let path = schema.get_field("path").unwrap();
+    let deciders = schema.get_field("deciders").unwrap();
+    let experts = schema.get_field("experts").unwrap();
+    let stakeholders = schema.get_field("stakeholders").unwrap();
+    let file = schema.get_field("file").unwrap();
+
+    //as usual, string / date conversions are a mess - All the following is to be able to index a datetime as expected by tantivy
+    let adr_date_as_date = match NaiveDate::parse_from_str(&adr.date, "%Y-%m-%d") {
+        Ok(r) => r,
+        Err(why) => {
+            debug!(
+                get_logger(),
+                "Pb while parsing date for ADR {:?} - {:?}",
+                adr.path(),
+                why
+            );
+            warn!(get_logger(), "Pb while parsing date for ADR {:?} - will use arbitraty January, 1rst 1970 date", adr.path().as_str());
+            NaiveDate::from_ymd_opt(1970, 1, 1).unwrap()
+        }
+    };
+    let zero_time = NaiveTime::from_hms_opt(0, 0, 0).unwrap();
+    let date_time = NaiveDateTime::new(adr_date_as_date, zero_time);
+
+    let epoc = date_time.and_utc().timestamp();
+
+    doc!(
+        title => String::from(adr.title.as_str()),
+        status => String::from(adr.status.as_str()),
+        date => DateTime::from_timestamp_secs(epoc),
+        body => String::from(adr.content.as_str()),
+        tags => String::from(adr.tags.as_str()), //recreate a string from the tags Vec via Debug...
+        path => String::from(adr.path().as_str()),
+        deciders => adr.deciders.join(", "),
+        experts => adr.experts.join(", "),
+        stakeholders => adr.stakeholders.join(", "),
+        file => adr.path(),
+    )
+}
 
-    for adr in adrs {
-        //as usual, string / date conversions are a mess - All the following is to be able to index a datetime as expected by tantivy
-        let adr_date_as_date = match NaiveDate::parse_from_str(&adr.date, "%Y-%m-%d") {
-            Ok(r) => r,
-            Err(why) => {
-                debug!(
+pub fn build_index(index_path: String, adrs: Vec<Adr>) -> tantivy::Result<()> {
+    info!(get_logger(), "Building Index in folder [{}]", index_path);
+
+    let now = Instant::now();
+    let index_path = Path::new(&index_path);
+
+    let schema = get_schema();
+
+    let mmap_directory = MmapDirectory::open(index_path)?;
+    let index = match Index::exists(&mmap_directory)? {
+        true => {
+            let index = Index::open(mmap_directory)?;
+            if index.schema() == schema {
+                index
+            } else {
+                //the index has been built by a previous version (e.g. without deciders), so recreate it
+                warn!(
                     get_logger(),
-                    "Pb while parsing date for ADR {:?} - {:?}",
-                    adr.path(),
-                    why
+                    "The schema of the index [{}] has changed - will recreate it",
+                    index_path.display()
                 );
-                warn!(get_logger(), "Pb while parsing date for ADR {:?} - will use arbitraty January, 1rst 1970 date", adr.path().as_str());
-                NaiveDate::from_ymd_opt(1970, 1, 1).unwrap()
+                Index::create(MmapDirectory::open(index_path)?, schema.clone(), IndexSettings::default())?
             }
-        };
-        let zero_time = NaiveTime::from_hms_opt(0, 0, 0).unwrap();
-        let date_time = NaiveDateTime::new(adr_date_as_date, zero_time);
-
-        let epoc = date_time.and_utc().timestamp();
-
-        index_writer
-            .add_document(doc!(
-                title => String::from(adr.title.as_str()),
-                status => String::from(adr.status.as_str()),
-                date => DateTime::from_timestamp_secs(epoc),
-                body => String::from(adr.content.as_str()),
-                tags => String::from(adr.tags.as_str()), //recreate a string from the tags Vec via Debug...
-                path => String::from(adr.path().as_str()),
-            ))
-            .ok();
+        }
+        false => Index::create(mmap_directory, schema.clone(), IndexSettings::default())?,
+    };
+    let mut index_writer = index.writer(100_000_000)?; //multi threaded behind the scene # of thread < 8
+
+    index_writer.delete_all_documents()?;
+    index_writer.commit()?;
+
+    for adr in adrs {
+        index_writer.add_document(get_document(&schema, &adr)).ok();
     }
 
     index_writer.commit()?;
@@ -118,6 +155,37 @@ pub struct SearchResult {
     pub path: [String; 1],
 }
 
+/// Updates the index of `build_index`: the documents of the `removed` paths (see `Adr::path()`) are deleted
+/// and the ones of the `updated` ADRs replaced
+pub fn update_index(index_path: String, updated: Vec<Adr>, removed: Vec<String>) -> tantivy::Result<()> {
+    let now = Instant::now();
+    let index = Index::open(MmapDirectory::open(Path::new(&index_path))?)?;
+    let schema = index.schema();
+    //an index built by a previous version has no file field, it has to be built again
+    let file = schema.get_field("file")?;
+    let mut index_writer: IndexWriter = index.writer(50_000_000)?;
+
+    let updated_paths: Vec<String> = updated.iter().map(|adr| adr.path()).collect();
+    for path in removed.iter().chain(updated_paths.iter()) {
+        index_writer.delete_term(Term::from_field_text(file, path));
+    }
+    for adr in updated.iter() {
+        index_writer.add_document(get_document(&schema, adr))?;
+    }
+    index_writer.commit()?;
+
+    info!(
+        get_logger(),
+        "[{}] ADR(s) updated and [{}] removed in Index [{}] in [{}] milli seconds",
+        updated.len(),
+        removed.len(),
+        index_path,
+        now.elapsed().as_millis()
+    );
+
+    Ok(())
+}
+
 pub fn search(
     index_path: String,
     query_as_string: String,
@@ -134,21 +202,14 @@ pub fn search(
     //println!("file exist {}", Index::exists(&mmap_directory) );
     let index = Index::open(mmap_directory)?;
 
-    //
-    let mut schema_builder = Schema::builder();
-    schema_builder.add_text_field("title", TEXT | STORED);
-    schema_builder.add_text_field("status", TEXT | STORED);
-    schema_builder.add_date_field("date", INDEXED | STORED);
-    schema_builder.add_text_field("body", TEXT);
-    schema_builder.add_text_field("tags", TEXT | STORED);
-    schema_builder.add_text_field("path", TEXT | STORED);
-    let schema = schema_builder.build();
+    let schema = get_schema();
 
     let title = schema.get_field("title").unwrap();
     let body = schema.get_field("body").unwrap();
     let status = schema.get_field("status").unwrap();
     let tags = schema.get_field("tags").unwrap();
     let path = schema.get_field("path").unwrap();
+    let deciders = schema.get_field("deciders").unwrap();
 
     //
     let reader = index
@@ -161,11 +222,16 @@ pub fn search(
     debug!(get_logger(), "Search with query [{}]", &query_as_string);
 
     // default_fields is the set of fields to use if none is specified in the query. date is not part of the default
-    let query_parser = QueryParser::for_index(&index, vec![title, body, status, tags, path]);
+    let query_parser = QueryParser::for_index(&index, vec![title, body, status, tags, path, deciders]);
 
     let query = match query_parser.parse_query(&query_as_string) {
         Ok(e) => e,
-        Err(why) => panic!("Search | Error while parsing {:?}", why),
+        Err(why) => {
+            return Err(tantivy::TantivyError::InvalidArgument(format!(
+                "Invalid query [{}] - {:?}",
+                query_as_string, why
+            )))
+        }
     };
 
     let top_docs = searcher.search(&query, &TopDocs::with_limit(limit))?;
@@ -186,3 +252,85 @@ pub fn search(
 
     Ok(results)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tantivy::schema::{Schema, TEXT};
+    use tantivy::Index;
+    use tempdir::TempDir;
+
+    use adr_core::adr_repo::Adr;
+
+    const ADOC: &str = "== Use Kafka
+
+*Status:* {decided}  *Date:* 2024-01-02
+
+== Context
+
+We need a broker
+";
+
+    fn index_path(dir: &TempDir) -> String {
+        format!("{}", dir.path().display())
+    }
+
+    #[test]
+    fn test_build_index_with_previous_schema() {
+        let dir = TempDir::new("my_index_folder").unwrap();
+        //e.g. built by a previous version, without deciders
+        let mut schema = Schema::builder();
+        schema.add_text_field("title", TEXT);
+        Index::create_in_dir(dir.path(), schema.build()).unwrap();
+
+        let adr = Adr::from(String::from("/adr"), String::from("000001-use-kafka.adoc"), String::from(ADOC), "");
+        super::build_index(index_path(&dir), vec![adr]).unwrap();
+
+        let results = super::search(index_path(&dir), String::from("kafka"), 10).unwrap();
+        assert_eq!(1, results.len());
+    }
+
+    #[test]
+    fn test_build_index_errors() {
+        let dir = TempDir::new("my_index_folder").unwrap();
+        fs::write(dir.path().join("meta.json"), "not an index").unwrap();
+        fs::write(dir.path().join("notes.txt"), "not to be removed").unwrap();
+
+        //the configured directory is not removed, whatever the error
+        assert!(super::build_index(index_path(&dir), vec![]).is_err());
+        assert!(dir.path().join("notes.txt").is_file());
+    }
+
+    #[test]
+    fn test_update_index() {
+        let dir = TempDir::new("my_index_folder").unwrap();
+        let adr = |file: &str, title: &str| {
+            Adr::from(String::from("/adr"), file.to_string(), ADOC.replace("Use Kafka", title), "")
+        };
+        let adrs = vec![
+            adr("000001-use-kafka.adoc", "Use Kafka"),
+            adr("000002-use-pulsar.adoc", "Use Pulsar"),
+            adr("000003-use-redis.adoc", "Use Redis"),
+        ];
+        super::build_index(index_path(&dir), adrs.clone()).unwrap();
+
+        let updated = adr("000001-use-kafka.adoc", "Use Kafka Streams");
+        super::update_index(index_path(&dir), vec![updated.clone()], vec![adrs[1].path()]).unwrap();
+        //updated again, still a single document
+        super::update_index(index_path(&dir), vec![updated.clone()], vec![]).unwrap();
+
+        let mut paths: Vec<String> = super::search(index_path(&dir), String::from("broker"), 10)
+            .unwrap()
+            .into_iter()
+            .map(|result| result.path[0].clone())
+            .collect();
+        paths.sort();
+        assert_eq!(paths, vec![adrs[0].path(), adrs[2].path()]);
+
+        let results = super::search(index_path(&dir), String::from("streams"), 10).unwrap();
+        assert_eq!(1, results.len());
+        assert_eq!(results[0].title[0], "Use Kafka Streams");
+        assert!(super::search(index_path(&dir), String::from("pulsar"), 10).unwrap().is_empty());
+    }
+}
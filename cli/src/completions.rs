@@ -0,0 +1,171 @@
+use std::ffi::OsStr;
+use std::io;
+use std::path::Path;
+
+use clap_complete::env::{Bash, EnvCompleter, Fish, Powershell, Zsh};
+use clap_complete::CompletionCandidate;
+
+use adr_config::config::AdrToolConfig;
+use adr_core::adr_repo::{self, Adr, TransitionStatus};
+
+/// The environment variable asking `adr` for the completions (see `clap_complete::CompleteEnv`)
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+pub const SHELLS: [&str; 4] = ["bash", "zsh", "fish", "powershell"];
+
+/// Prints the script registering the completions of `adr` in `shell`
+pub fn print_registration(shell: &str) -> io::Result<()> {
+    let completer: &dyn EnvCompleter = match shell {
+        "zsh" => &Zsh,
+        "fish" => &Fish,
+        "powershell" => &Powershell,
+        _ => &Bash,
+    };
+    //as CompleteEnv does, the script calls this binary (relative paths are made absolute)
+    let mut bin = std::env::args().next().unwrap_or_else(|| String::from("adr"));
+    if Path::new(&bin).components().count() > 1 {
+        bin = std::env::current_dir()?.join(bin).display().to_string();
+    }
+
+    completer.write_registration(COMPLETE_VAR, "adr", "adr", &bin, &mut io::stdout())
+}
+
+fn get_adrs() -> Vec<Adr> {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+    adr_repo::list_all_adr(Path::new(&cfg.adr_src_dir)).unwrap_or_default()
+}
+
+fn describe(adr: &Adr) -> Option<clap::builder::StyledStr> {
+    Some(format!("{} ({})", adr.title, adr.status.as_str()).into())
+}
+
+/// The ids of the Decision Records
+pub fn ids() -> Vec<CompletionCandidate> {
+    get_adrs()
+        .iter()
+        .map(|adr| CompletionCandidate::new(adr.file_id.to_string()).help(describe(adr)))
+        .collect()
+}
+
+/// The paths (relative to the current directory if possible) of the Decision Records `transition` can be applied to,
+/// e.g. the decided ones for `superseded-by`
+pub fn paths(transition: TransitionStatus) -> impl Fn() -> Vec<CompletionCandidate> + Send + Sync {
+    move || {
+        let current_dir = std::env::current_dir().unwrap_or_default();
+        get_adrs()
+            .iter()
+            .filter(|adr| {
+                let mut adr = (*adr).clone();
+                adr.update_status(transition)
+            })
+            .map(|adr| {
+                let path = adr.path();
+                let path = match Path::new(&path).strip_prefix(&current_dir) {
+                    Ok(relative) => relative.display().to_string(),
+                    Err(_) => path.clone(),
+                };
+                CompletionCandidate::new(path).help(describe(adr))
+            })
+            .collect()
+    }
+}
+
+fn get_tags() -> Vec<(String, u32)> {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+    let mut tags: Vec<(String, u32)> = adr_repo::get_tags_popularity(Path::new(&cfg.adr_src_dir))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(tag, count)| (tag.trim().to_string(), count))
+        .filter(|(tag, _)| !tag.is_empty())
+        .collect();
+    tags.sort();
+
+    tags
+}
+
+/// The tags used by the Decision Records
+pub fn tags() -> Vec<CompletionCandidate> {
+    get_tags()
+        .into_iter()
+        .map(|(tag, count)| CompletionCandidate::new(tag).help(Some(format!("{} ADR(s)", count).into())))
+        .collect()
+}
+
+/// The tags for a comma separated list, e.g. `--tags "security, dep` completes the last one
+pub fn tag_list(current: &OsStr) -> Vec<CompletionCandidate> {
+    complete_tag_list(&current.to_string_lossy(), get_tags())
+}
+
+fn complete_tag_list(current: &str, tags: Vec<(String, u32)>) -> Vec<CompletionCandidate> {
+    let (given, last) = match current.rfind(',') {
+        Some(index) => current.split_at(index + 1),
+        None => ("", current),
+    };
+    //the candidates start with what has been typed
+    let typed = format!("{}{}", given, &last[..last.len() - last.trim_start().len()]);
+    let given = crate::wizard::split_list(given);
+
+    tags.into_iter()
+        .filter(|(tag, _)| !given.contains(tag) && tag.starts_with(last.trim_start()))
+        .map(|(tag, count)| {
+            CompletionCandidate::new(format!("{}{}", typed, tag)).help(Some(format!("{} ADR(s)", count).into()))
+        })
+        .collect()
+}
+
+/// The names of the configuration items
+pub fn config_names() -> Vec<CompletionCandidate> {
+    adr_config::config::CONFIG_NAMES.iter().map(CompletionCandidate::new).collect()
+}
+
+/// The names of the templates
+pub fn templates() -> Vec<CompletionCandidate> {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+    adr_core::adr_template::list_templates(&cfg)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|template| {
+            let help = template.path.unwrap_or_else(|| String::from("built-in"));
+            CompletionCandidate::new(template.name).help(Some(help.into()))
+        })
+        .collect()
+}
+
+/// The sub directories of adr_src_dir
+pub fn dirs() -> Vec<CompletionCandidate> {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+    adr_repo::list_sub_directories(Path::new(&cfg.adr_src_dir))
+        .unwrap_or_default()
+        .into_iter()
+        .map(CompletionCandidate::new)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::complete_tag_list;
+
+    fn complete(current: &str) -> Vec<String> {
+        let tags = vec![
+            (String::from("dep"), 1),
+            (String::from("deprecated"), 2),
+            (String::from("security"), 3),
+        ];
+        complete_tag_list(current, tags)
+            .iter()
+            .map(|candidate| candidate.get_value().to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_tag_list() {
+        assert_eq!(complete(""), vec!["dep", "deprecated", "security"]);
+        assert_eq!(complete("sec"), vec!["security"]);
+        assert_eq!(complete("security, dep"), vec!["security, dep", "security, deprecated"]);
+        assert_eq!(complete("security,dep"), vec!["security,dep", "security,deprecated"]);
+        //the given tags are not proposed again
+        assert_eq!(complete("security, "), vec!["security, dep", "security, deprecated"]);
+        assert_eq!(complete("dep, security,"), vec!["dep, security,deprecated"]);
+        assert!(complete("security, kafka").is_empty());
+    }
+}
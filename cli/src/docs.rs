@@ -0,0 +1,38 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use clap::Command;
+use clap_mangen::Man;
+
+fn write_man_page(cmd: Command, out_dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for sub_cmd in cmd.get_subcommands().filter(|sub_cmd| !sub_cmd.is_hide_set()).cloned() {
+        write_man_page(sub_cmd, out_dir, files)?;
+    }
+    files.push(Man::new(cmd).manual("ADRust Manual").generate_to(out_dir)?);
+
+    Ok(())
+}
+
+/// Writes the man pages of `cmd` and of each of its subcommands (e.g. `adr-lf-new.1`) in `out_dir`
+pub fn write_man_pages(cmd: Command, out_dir: &Path) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(out_dir)?;
+    //builds the subcommands names (adr-lf-new) and versions
+    let mut cmd = cmd.disable_help_subcommand(true);
+    cmd.build();
+
+    let mut files = Vec::new();
+    write_man_page(cmd, out_dir, &mut files)?;
+    files.sort();
+
+    Ok(files)
+}
+
+/// The Markdown reference of `cmd` and of all its subcommands
+pub fn markdown(cmd: &Command) -> String {
+    let options = clap_markdown::MarkdownOptions::new()
+        .title(format!("Command-Line Reference for `{}`", cmd.get_name()))
+        .show_footer(false);
+
+    clap_markdown::help_markdown_command_custom(cmd, &options)
+}
@@ -0,0 +1,70 @@
+use std::io::{self, IsTerminal};
+use std::path::Path;
+use std::process::Command;
+
+use dialoguer::theme::ColorfulTheme;
+use dialoguer::Confirm;
+
+use adr_core::adr_lint;
+
+/// Returns the command line of the editor: `$VISUAL`, then `$EDITOR`, then a platform default
+fn get_editor() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|val| !val.trim().is_empty())
+        .unwrap_or_else(|| match cfg!(windows) {
+            true => String::from("notepad"),
+            false => String::from("vi"),
+        })
+}
+
+fn open_editor(path: &Path) -> io::Result<()> {
+    let editor = get_editor();
+    //e.g. EDITOR="code --wait"
+    let mut args = editor.split_whitespace();
+    let program = args.next().unwrap_or("vi");
+
+    let status = Command::new(program).args(args).arg(path).status()?;
+    if !status.success() {
+        return Err(io::Error::other(format!(
+            "[{}] exited with [{}]",
+            editor, status
+        )));
+    }
+
+    Ok(())
+}
+
+/// Opens the Decision Record in the editor, then parses it again. If the title, status or date markers are broken,
+/// they are reported and the user can reopen the editor. Returns true if the Decision Record is valid.
+pub fn edit(base_path: &Path, path: &Path) -> io::Result<bool> {
+    loop {
+        open_editor(path)?;
+
+        let adr = adr_core::adr_repo::build_adr(base_path, path)?;
+        let issues = adr_lint::check_markers(&adr);
+        if issues.is_empty() {
+            return Ok(true);
+        }
+
+        for issue in issues.iter() {
+            eprintln!(
+                "warning: {}:{} [{}] {}",
+                issue.file, issue.line, issue.rule, issue.message
+            );
+        }
+
+        if !io::stdin().is_terminal() {
+            return Ok(false);
+        }
+        let reopen = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt("The Decision Record cannot be fully parsed. Reopen the editor?")
+            .default(true)
+            .interact()
+            .map_err(crate::wizard::to_io_error)?;
+        if !reopen {
+            return Ok(false);
+        }
+    }
+}
@@ -2,8 +2,8 @@ extern crate slog;
 extern crate slog_term;
 use slog::*;
 
-use std::io::{self};
-use std::path::Path;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
 
 use comfy_table::modifiers::UTF8_ROUND_CORNERS;
 use comfy_table::presets::UTF8_FULL;
@@ -14,11 +14,22 @@ extern crate lazy_static;
 
 extern crate clap;
 use clap::{Arg, Command};
+use clap_complete::{ArgValueCandidates, ArgValueCompleter};
 
 extern crate dirs;
 
+extern crate dialoguer;
+mod completions;
+mod docs;
+mod editor;
+mod server;
+mod tui;
+mod watch;
+mod wizard;
+
 extern crate adr_core;
-use adr_core::adr_repo::Status;
+use adr_core::adr_lint::Severity;
+use adr_core::adr_repo::{Adr, Status, TransitionStatus};
 extern crate adr_config;
 use adr_config::config::AdrToolConfig;
 extern crate adr_search;
@@ -38,7 +49,15 @@ fn get_logger() -> slog::Logger {
     slog::Logger::root(drain, o!())
 }
 
-pub fn list_all_adr() -> io::Result<()> {
+/// The Decision Records of the working tree, or of the git revision `at`
+fn get_all_adr(cfg: &AdrToolConfig, at: Option<&String>) -> io::Result<Vec<Adr>> {
+    match at {
+        Some(rev) => adr_core::adr_git::list_all_adr_at(cfg, rev),
+        None => adr_core::adr_repo::list_all_adr(Path::new(&cfg.adr_src_dir)),
+    }
+}
+
+pub fn list_all_adr(decider: Option<&str>, at: Option<&String>) -> io::Result<()> {
     let cfg: AdrToolConfig = adr_config::config::get_config();
 
     let mut table = Table::new();
@@ -58,15 +77,25 @@ pub fn list_all_adr() -> io::Result<()> {
     title_column.set_constraint(UpperBoundary(Fixed(90)));
 
     info!(get_logger(), "list all ADR from [{}]", &cfg.adr_src_dir);
-    for entry in adr_core::adr_repo::list_all_adr(Path::new(&cfg.adr_src_dir))? {
+    for entry in get_all_adr(&cfg, at)? {
+        if let Some(decider) = decider {
+            let decider = decider.to_lowercase();
+            if !entry
+                .deciders
+                .iter()
+                .any(|d| d.to_lowercase().contains(decider.as_str()))
+            {
+                continue;
+            }
+        }
         //table.add_row(row![entry.title, Fg->entry.status, entry.path, entry.tags]);
         let style = get_cell_style(entry.status);
         table.add_row(vec![
-            Cell::new(&entry.file_id.to_string()),
+            Cell::new(entry.file_id.to_string()),
             Cell::new(&entry.title).fg(style),
             Cell::new(entry.status.as_str()).fg(style),
             Cell::new(&entry.date),
-            Cell::new(&entry.path()),
+            Cell::new(entry.path()),
             Cell::new(&entry.tags).add_attributes(vec![Attribute::Italic]),
         ]);
     }
@@ -77,6 +106,438 @@ pub fn list_all_adr() -> io::Result<()> {
     Ok(())
 }
 
+fn show_adr(id_or_path: &str, show_options: bool, show_history: bool, at: Option<&String>) -> io::Result<()> {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+    let adr = match at {
+        Some(rev) => adr_core::adr_git::find_adr_at(&cfg, id_or_path, rev)?,
+        None => adr_core::adr_repo::find_adr(Path::new(&cfg.adr_src_dir), id_or_path)?,
+    };
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec!["Property", "Value"]);
+
+    let style = get_cell_style(adr.status);
+    table.add_row(vec![Cell::new("ID"), Cell::new(adr.file_id.to_string())]);
+    table.add_row(vec![Cell::new("Title"), Cell::new(&adr.title).fg(style)]);
+    table.add_row(vec![
+        Cell::new("Status"),
+        Cell::new(adr.status.as_str()).fg(style),
+    ]);
+    table.add_row(vec![Cell::new("Date"), Cell::new(&adr.date)]);
+    table.add_row(vec![Cell::new("File"), Cell::new(adr.path())]);
+    table.add_row(vec![
+        Cell::new("Tags"),
+        Cell::new(&adr.tags).add_attributes(vec![Attribute::Italic]),
+    ]);
+    table.add_row(vec![
+        Cell::new("Decision Makers"),
+        Cell::new(adr.deciders.join(", ")),
+    ]);
+    table.add_row(vec![Cell::new("Experts"), Cell::new(adr.experts.join(", "))]);
+    table.add_row(vec![
+        Cell::new("Stakeholders"),
+        Cell::new(adr.stakeholders.join(", ")),
+    ]);
+    table.add_row(vec![
+        Cell::new("Decision Outcome"),
+        Cell::new(adr.outcome.as_deref().unwrap_or("")),
+    ]);
+
+    println!("{table}");
+
+    if show_options {
+        let mut table = Table::new();
+        table
+            .load_preset(UTF8_FULL)
+            .apply_modifier(UTF8_ROUND_CORNERS)
+            .set_content_arrangement(ContentArrangement::Dynamic);
+        table.set_header(vec!["Option", "Description", "Pros", "Cons", "Chosen"]);
+
+        let chosen = adr.chosen_option().map(|o| o.name.as_str());
+        for option in adr.options.iter() {
+            let is_chosen = Some(option.name.as_str()) == chosen;
+            let name = match is_chosen {
+                true => Cell::new(&option.name).add_attribute(Attribute::Bold),
+                false => Cell::new(&option.name),
+            };
+            table.add_row(vec![
+                name,
+                Cell::new(&option.description),
+                Cell::new(option.pros.join("\n")),
+                Cell::new(option.cons.join("\n")),
+                Cell::new(if is_chosen { "Y" } else { "" }),
+            ]);
+        }
+
+        println!("{table}");
+    }
+
+    if show_history {
+        let history = adr_core::adr_git::get_history(&adr)?;
+        let mut table = Table::new();
+        table
+            .load_preset(UTF8_FULL)
+            .apply_modifier(UTF8_ROUND_CORNERS)
+            .set_content_arrangement(ContentArrangement::Dynamic);
+        table.set_header(vec!["Event", "Date", "Author", "Commit", "Message"]);
+
+        let mut events: Vec<(String, &adr_core::adr_git::Commit)> = Vec::new();
+        if let Some(created) = history.created() {
+            events.push((String::from("created"), created));
+        }
+        for commit in history.status_changes().into_iter().skip(1) {
+            events.push((format!("status {}", commit.status.as_str()), commit));
+        }
+        if let Some(last) = history.last_modified() {
+            events.push((String::from("last modified"), last));
+        }
+        for (event, commit) in events {
+            table.add_row(vec![
+                Cell::new(event).fg(get_cell_style(commit.status)),
+                Cell::new(&commit.date),
+                Cell::new(&commit.author),
+                Cell::new(commit.short_hash()),
+                Cell::new(&commit.subject),
+            ]);
+        }
+
+        match history.created() {
+            Some(_) => println!("{table}"),
+            None => println!("[{}] has not been committed yet", adr.path()),
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints all the commits of a Decision Record, the status changes are in bold
+fn log_adr(id_or_path: &str) -> io::Result<()> {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+    let adr = adr_core::adr_repo::find_adr(Path::new(&cfg.adr_src_dir), id_or_path)?;
+    let history = adr_core::adr_git::get_history(&adr)?;
+    if history.commits.is_empty() {
+        println!("[{}] has not been committed yet", adr.path());
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec!["Commit", "Date", "Author", "Status", "File", "Message"]);
+
+    let changes: Vec<&str> = history.status_changes().iter().map(|c| c.hash.as_str()).collect();
+    for commit in history.commits.iter().rev() {
+        let status = Cell::new(commit.status.as_str()).fg(get_cell_style(commit.status));
+        let status = match changes.contains(&commit.hash.as_str()) {
+            true => status.add_attribute(Attribute::Bold),
+            false => status,
+        };
+        table.add_row(vec![
+            Cell::new(commit.short_hash()),
+            Cell::new(&commit.date),
+            Cell::new(format!("{} <{}>", commit.author, commit.email)),
+            status,
+            Cell::new(&commit.path),
+            Cell::new(&commit.subject),
+        ]);
+    }
+
+    println!("{table}");
+
+    Ok(())
+}
+
+fn edit_adr(id_or_path: &str) -> io::Result<bool> {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+    let base_path = Path::new(&cfg.adr_src_dir);
+    let adr = adr_core::adr_repo::find_adr(base_path, id_or_path)?;
+
+    editor::edit(base_path, Path::new(&adr.path()))
+}
+
+fn print_import_report(imported: Vec<adr_core::adr_import::ImportedAdr>, dry_run: bool) {
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec!["Operation", "ID", "Source", "Target", "Status", "Not mapped"]);
+
+    for entry in imported.iter() {
+        let (operation, style) = match entry.created {
+            true => ("create", get_cell_style(entry.status)),
+            false => ("skip", Color::Red),
+        };
+        table.add_row(vec![
+            Cell::new(operation).fg(style),
+            Cell::new(entry.id.to_string()),
+            Cell::new(&entry.source),
+            Cell::new(&entry.target).fg(style),
+            Cell::new(entry.status.as_str()).fg(style),
+            Cell::new(entry.warnings.join("\n")),
+        ]);
+    }
+
+    println!("{table}");
+    let created = imported.iter().filter(|i| i.created).count();
+    let warnings: usize = imported.iter().map(|i| i.warnings.len()).sum();
+    println!(
+        "{} record(s) {}, {} skipped, {} element(s) not mapped",
+        created,
+        match dry_run {
+            true => "to import",
+            false => "imported",
+        },
+        imported.len() - created,
+        warnings
+    );
+    if dry_run {
+        println!("Dry run, nothing has been written. Run again without --dry-run to import the records.");
+    }
+}
+
+fn import(format: &str, dir: &str, dry_run: bool) -> io::Result<()> {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+    let dir = Path::new(dir);
+    let imported = match format {
+        "adr-tools" => adr_core::adr_import::import_adr_tools(&cfg, dir, dry_run)?,
+        "madr" => adr_core::adr_import::import_madr(&cfg, dir, dry_run)?,
+        "log4brains" => adr_core::adr_import::import_log4brains(&cfg, dir, dry_run)?,
+        _ => unreachable!(),
+    };
+    print_import_report(imported, dry_run);
+
+    Ok(())
+}
+
+fn graph(format: &str, tag: Option<&String>, dir: Option<&String>, at: Option<&String>) -> io::Result<()> {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+    let adrs = get_all_adr(&cfg, at)?;
+    let graph = adr_core::adr_graph::build_graph(&adrs).filter(tag.map(|t| t.as_str()), dir.map(|d| d.as_str()));
+
+    match format {
+        "mermaid" => print!("{}", graph.to_mermaid()),
+        "json" => println!("{}", graph.to_json()?),
+        _ => print!("{}", graph.to_dot()),
+    }
+
+    Ok(())
+}
+
+/// Lists the Decision Records added, removed or whose status changed between the git revisions `from` and `to`
+fn diff(from: &str, to: &str) -> io::Result<()> {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+    let changes = adr_core::adr_git::diff(&cfg, from, to)?;
+    if changes.is_empty() {
+        println!("No Decision Record added, removed or changed in status between [{}] and [{}]", from, to);
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec!["Change", "ID", "Title", "File", from, to]);
+
+    let status_cell = |status: Option<Status>| match status {
+        Some(status) => Cell::new(status.as_str()).fg(get_cell_style(status)),
+        None => Cell::new(""),
+    };
+    for change in changes.iter() {
+        table.add_row(vec![
+            Cell::new(change.change.as_str()).add_attribute(Attribute::Bold),
+            Cell::new(change.id.to_string()),
+            Cell::new(&change.title),
+            Cell::new(&change.path),
+            status_cell(change.from),
+            status_cell(change.to),
+        ]);
+    }
+
+    println!("{table}");
+
+    Ok(())
+}
+
+/// Prints, as Markdown, what `head` (the working tree if None) does to the decision log compared to `base`
+fn review(base: &str, head: Option<&String>) -> io::Result<()> {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+    let rules = adr_core::adr_lint::LintRules::from_config(&cfg);
+    let review = adr_core::adr_review::review(&cfg, base, head.map(|h| h.as_str()), &rules)?;
+    print!("{}", review.to_markdown());
+
+    Ok(())
+}
+
+fn export_site(out_dir: &str) -> io::Result<()> {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+    let files = adr_core::adr_export::export_site(&cfg, Path::new(out_dir))?;
+    println!(
+        "{} file(s) written, open {}",
+        files.len(),
+        Path::new(out_dir).join("index.html").display()
+    );
+
+    Ok(())
+}
+
+fn lint(fix: bool, format: &str) -> io::Result<bool> {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+    let base_path = Path::new(&cfg.adr_src_dir);
+    let rules = adr_core::adr_lint::LintRules::from_config(&cfg);
+
+    if fix {
+        let fixed = adr_core::adr_lint::fix_all_adr(base_path, &rules)?;
+        for issue in fixed.iter() {
+            eprintln!("fixed: {} [{}] {}", issue.file, issue.rule, issue.message);
+        }
+        eprintln!("{} problem(s) fixed", fixed.len());
+    }
+
+    let issues = adr_core::adr_lint::lint_all_adr(base_path, &rules)?;
+    match format {
+        "json" => println!("{}", adr_core::adr_lint::to_json(&issues)?),
+        //GitHub Actions workflow commands, the issues are shown as annotations of the pull request
+        "github" => {
+            for issue in issues.iter() {
+                println!(
+                    "::{} file={},line={},title={}::{}",
+                    issue.severity.as_str(),
+                    issue.file,
+                    issue.line.max(1),
+                    issue.rule,
+                    issue.message
+                );
+            }
+        }
+        _ => {
+            print_issues(&issues);
+        }
+    }
+
+    Ok(!issues.iter().any(|i| i.severity == Severity::ERROR))
+}
+
+fn install_hook() -> io::Result<()> {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+    let path = adr_core::adr_git::install_hook(&cfg)?;
+    println!("pre-commit hook installed in {}", path.display());
+
+    Ok(())
+}
+
+fn uninstall_hook() -> io::Result<()> {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+    match adr_core::adr_git::uninstall_hook(&cfg)? {
+        true => println!("pre-commit hook removed"),
+        false => println!("No pre-commit hook to remove"),
+    }
+
+    Ok(())
+}
+
+/// Run by the pre-commit hook, only the staged Decision Records are checked
+fn run_hook() -> io::Result<bool> {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+    let issues = adr_core::adr_lint::check_staged(&cfg)?;
+    if issues.is_empty() {
+        return Ok(true);
+    }
+
+    let ok = print_issues(&issues);
+    if !ok {
+        eprintln!("Commit aborted, fix the Decision Records above (or use git commit --no-verify)");
+    }
+
+    Ok(ok)
+}
+
+fn check_links() -> io::Result<bool> {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+    let issues = adr_core::adr_lint::check_all_links(Path::new(&cfg.adr_src_dir))?;
+
+    Ok(print_issues(&issues))
+}
+
+/// Prints the issues, returns false if one of them is an error
+fn print_issues(issues: &[adr_core::adr_lint::LintIssue]) -> bool {
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec!["File", "Line", "Rule", "Severity", "Message"]);
+
+    for issue in issues.iter() {
+        let style = match issue.severity {
+            Severity::ERROR => Color::DarkRed,
+            Severity::WARNING => Color::DarkYellow,
+        };
+        table.add_row(vec![
+            Cell::new(&issue.file),
+            Cell::new(issue.line.to_string()),
+            Cell::new(&issue.rule),
+            Cell::new(issue.severity.as_str()).fg(style),
+            Cell::new(&issue.message),
+        ]);
+    }
+
+    println!("{table}");
+    println!("\n Found {} problem(s)", issues.len());
+
+    !issues.iter().any(|i| i.severity == Severity::ERROR)
+}
+
+fn check_ids() -> io::Result<bool> {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+    let with_missing = cfg.id_strategy != adr_config::config::ID_STRATEGY_TIMESTAMP;
+    let report = adr_core::adr_repo::check_ids(Path::new(&cfg.adr_src_dir), with_missing)?;
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec!["ID", "Problem", "Files"]);
+
+    for (id, paths) in report.duplicates.iter() {
+        table.add_row(vec![
+            Cell::new(id.to_string()),
+            Cell::new("duplicate").fg(Color::DarkRed),
+            Cell::new(paths.join("\n")),
+        ]);
+    }
+    for id in report.missing.iter() {
+        table.add_row(vec![
+            Cell::new(id.to_string()),
+            Cell::new("missing").fg(Color::DarkYellow),
+            Cell::new(""),
+        ]);
+    }
+
+    println!("{table}");
+    if !report.duplicates.is_empty() {
+        println!("\n Duplicated ids can be fixed with adr renumber --path <file>");
+    }
+
+    Ok(report.duplicates.is_empty())
+}
+
+fn renumber(id_or_path: &str, new_id: Option<usize>) -> io::Result<()> {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+    let new_path = adr_core::adr_repo::renumber_adr(cfg, id_or_path, new_id)?;
+    println!("ADR renamed to [{}]", new_path);
+
+    Ok(())
+}
+
 fn set_config(name: &str, value: &str) -> Result<()> {
     adr_config::config::set_config(name, value)
 }
@@ -133,6 +594,61 @@ fn list_all_config() -> Result<()> {
         cfg.id_prefix_width.to_string().as_str(),
         "Y",
     ]);
+    table.add_row(vec![
+        adr_config::config::ID_STRATEGY,
+        cfg.id_strategy.as_str(),
+        "Y",
+    ]);
+    table.add_row(vec![
+        adr_config::config::NAME_PATTERN,
+        cfg.name_pattern.as_str(),
+        "Y",
+    ]);
+    table.add_row(vec![
+        adr_config::config::LINT_REQUIRED_SECTIONS,
+        cfg.lint_required_sections.as_str(),
+        "Y",
+    ]);
+    table.add_row(vec![
+        adr_config::config::LINT_FORBIDDEN_STRINGS,
+        cfg.lint_forbidden_strings.as_str(),
+        "Y",
+    ]);
+    table.add_row(vec![
+        adr_config::config::LINT_MAX_TITLE_LENGTH,
+        cfg.lint_max_title_length.to_string().as_str(),
+        "Y",
+    ]);
+    table.add_row(vec![
+        adr_config::config::LINT_ALLOWED_TAGS,
+        cfg.lint_allowed_tags.as_str(),
+        "Y",
+    ]);
+    table.add_row(vec![
+        adr_config::config::LINT_DISABLED_RULES,
+        cfg.lint_disabled_rules.as_str(),
+        "Y",
+    ]);
+    table.add_row(vec![
+        adr_config::config::AUTO_COMMIT,
+        cfg.auto_commit.to_string().as_str(),
+        "Y",
+    ]);
+    table.add_row(vec![
+        adr_config::config::COMMIT_MESSAGE,
+        cfg.commit_message.as_str(),
+        "Y",
+    ]);
+    table.add_row(vec![
+        adr_config::config::AUTO_BRANCH,
+        cfg.auto_branch.to_string().as_str(),
+        "Y",
+    ]);
+    table.add_row(vec![
+        adr_config::config::BRANCH_PATTERN,
+        cfg.branch_pattern.as_str(),
+        "Y",
+    ]);
 
     // Print the table to stdout
     println!("{table}");
@@ -154,7 +670,44 @@ fn list_all_tags() -> Result<()> {
     let popularity = adr_core::adr_repo::get_tags_popularity(Path::new(&cfg.adr_src_dir))?;
 
     for (key, val) in popularity.iter() {
-        table.add_row(vec![Cell::new(key), Cell::new(&val.to_string())]);
+        table.add_row(vec![Cell::new(key), Cell::new(val.to_string())]);
+    }
+
+    // Print the table to stdout
+    println!("{table}");
+
+    Ok(())
+}
+
+fn list_all_people() -> Result<()> {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec![
+        "People",
+        "Decisions",
+        "Decision Maker",
+        "Expert",
+        "Stakeholder",
+    ]);
+
+    let participation =
+        adr_core::adr_repo::get_people_participation(Path::new(&cfg.adr_src_dir))?;
+    let mut people: Vec<_> = participation.iter().collect();
+    people.sort_by(|a, b| b.1.decisions.cmp(&a.1.decisions).then(a.0.cmp(b.0)));
+
+    for (name, val) in people {
+        table.add_row(vec![
+            Cell::new(name),
+            Cell::new(val.decisions.to_string()),
+            Cell::new(val.as_decider.to_string()),
+            Cell::new(val.as_expert.to_string()),
+            Cell::new(val.as_stakeholder.to_string()),
+        ]);
     }
 
     // Print the table to stdout
@@ -163,6 +716,51 @@ fn list_all_tags() -> Result<()> {
     Ok(())
 }
 
+fn list_all_templates() -> Result<()> {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec!["Template", "Source", "Default"]);
+
+    for template in adr_core::adr_template::list_templates(&cfg)? {
+        let is_default = template
+            .path
+            .as_ref()
+            .map(|p| Path::new(p).ends_with(&cfg.adr_template_file))
+            .unwrap_or(false);
+        table.add_row(vec![
+            Cell::new(&template.name),
+            Cell::new(template.path.as_deref().unwrap_or("built-in")),
+            Cell::new(if is_default { "Y" } else { "" }),
+        ]);
+    }
+
+    // Print the table to stdout
+    println!("{table}");
+
+    Ok(())
+}
+
+fn show_template(name: &str) -> io::Result<()> {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+    let template = adr_core::adr_template::get_template(&cfg, name)?;
+    println!("{}", template.content);
+
+    Ok(())
+}
+
+fn add_template(name: &str, file: &str) -> io::Result<()> {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+    let target = adr_core::adr_template::add_template(&cfg, name, Path::new(file))?;
+    println!("Template [{}] added as [{}]", name, target.display());
+
+    Ok(())
+}
+
 fn build_index() -> Result<()> {
     let cfg: AdrToolConfig = adr_config::config::get_config();
     let adrs = match adr_core::adr_repo::list_all_adr(Path::new(&cfg.adr_src_dir)) {
@@ -223,16 +821,89 @@ fn search(query: String) -> Result<()> {
     Ok(())
 }
 
+fn migrate(dry_run: bool) -> Result<()> {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec!["File", "Added Attributes"]);
+
+    let migrated = adr_core::adr_repo::migrate_all_adr(Path::new(&cfg.adr_src_dir), dry_run)?;
+    let migrated_size = migrated.len();
+    for (adr, added) in migrated {
+        table.add_row(vec![Cell::new(adr.path()), Cell::new(added.join(", "))]);
+    }
+
+    println!("{table}");
+
+    match dry_run {
+        true => println!("\n {} ADR(s) would be migrated - run without --dry-run to apply", migrated_size),
+        false => println!("\n {} ADR(s) migrated", migrated_size),
+    }
+
+    Ok(())
+}
+
 fn get_cell_style(status: Status) -> Color {
-    let style = match status {
+    match status {
         Status::WIP => Color::DarkYellow,
         Status::DECIDED => Color::DarkGreen,
         Status::COMPLETED => Color::Green,
         Status::COMPLETES => Color::Green,
         _ => Color::DarkRed,
-    };
+    }
+}
+
+/// `--at` of the commands reading the Decision Records
+fn at_arg() -> Arg {
+    Arg::new("at")
+        .long("at")
+        .action(clap::ArgAction::Set)
+        .value_name("REV")
+        .help("Read the Decision Records at this git revision (e.g. a tag, a branch or a commit) instead of the working tree")
+}
+
+/// `--commit` of the lifecycle commands
+fn commit_arg() -> Arg {
+    Arg::new("commit")
+        .long("commit")
+        .action(clap::ArgAction::SetTrue)
+        .help("Commit the modified Decision Record(s) with the commit_message pattern (always done if auto_commit is true)")
+}
+
+/// Commits `files` if `--commit` is given or `auto_commit` is true, the message is built from `commit_message`
+/// and the first file (the transitioned Decision Record)
+fn commit_lifecycle(commit: bool, action: &str, files: &[PathBuf]) -> io::Result<()> {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+    if !commit && !cfg.auto_commit {
+        return Ok(());
+    }
+
+    let adr = adr_core::adr_repo::build_adr(Path::new(&cfg.adr_src_dir), &files[0])?;
+    let message = adr_core::adr_git::format_pattern(&cfg.commit_message, &adr, action);
+    let hash = adr_core::adr_git::commit(files, &message)?;
+    println!("[{}] {}", hash, message);
+
+    Ok(())
+}
+
+/// `ADR-<id>` of the Decision Record `path`, as used in the commit messages
+fn get_adr_ref(path: &str) -> io::Result<String> {
+    let cfg: AdrToolConfig = adr_config::config::get_config();
+    let adr = adr_core::adr_repo::build_adr(Path::new(&cfg.adr_src_dir), Path::new(path))?;
+
+    Ok(format!("ADR-{}", adr.file_id))
+}
 
-    style
+/// `--dry-run` of the import commands
+fn dry_run_arg() -> Arg {
+    Arg::new("dry-run")
+        .long("dry-run")
+        .action(clap::ArgAction::SetTrue)
+        .help("Only shows the planned file operations, nothing is written")
 }
 
 /**
@@ -242,23 +913,365 @@ fn init() -> Result<()> {
     adr_config::config::init()
 }
 
-///
-/// The main program - start the CLI ...
-fn main() {
-    //
-    const VERSION: &'static str = env!("CARGO_PKG_VERSION");
-    let cmd = Command::new("adr")
+/// The syntax of `adr search --query`
+const SEARCH_SYNTAX: &str = "Query syntax:
+
+    a AND b OR c       the ADRs containing the terms (a and b) or c
+    -b                 the ADRs that do not contain the term b
+    +c                 the ADRs that must contain the term c
+    tags:a AND tags:b  the ADRs having the tags a and b
+    title:a            the ADRs having the term a in their title
+    date:[2022-08-01T00:00:00Z TO 2023-10-02T18:00:00Z] AND tags:BPaaS
+                       the ADRs of this date range having the tag BPaaS
+    status:decided     the decided ADRs
+    deciders:alice     the ADRs where alice is a Decision Maker";
+
+/// The whole command tree, also used to complete the command lines
+fn build_cli() -> Command {
+    const VERSION: &str = env!("CARGO_PKG_VERSION");
+    Command::new("adr")
         .version(VERSION)
+        .propagate_version(true)
         .about("A CLI to help you manage your ADR in git")
         .subcommand(
-            Command::new("list")
-                .about("Lists all Decision Records")
-                .version("0.1.0"),
+            Command::new("list")
+                .about("Lists all Decision Records")
+                .arg(
+                    Arg::new("decider")
+                        .short('d')
+                        .long("decider")
+                        .action(clap::ArgAction::Set)
+                        .required(false)
+                        .help("Only list the Decision Records where the specified person is a Decision Maker"),
+                )
+                .arg(at_arg()),
+        )
+        .subcommand(
+            Command::new("show")
+                .about("Shows the details of a Decision Record")
+                .arg(
+                    Arg::new("adr")
+                        .action(clap::ArgAction::Set)
+                        .required(true)
+                        .add(ArgValueCandidates::new(completions::ids))
+                        .help("The id or the path of the Decision Record"),
+                )
+                .arg(
+                    Arg::new("options")
+                        .short('o')
+                        .long("options")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Show the considered options and the chosen one"),
+                )
+                .arg(
+                    Arg::new("history")
+                        .long("history")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Show who created the Decision Record, when its status changed and its last modification (from git)"),
+                )
+                .arg(at_arg().conflicts_with("history")),
+        )
+        .subcommand(
+            Command::new("diff")
+                .about("Lists the Decision Records added, removed or changed in status between two git revisions")
+                .arg(
+                    Arg::new("from")
+                        .action(clap::ArgAction::Set)
+                        .required(true)
+                        .help("The git revision to compare from (e.g. a tag)"),
+                )
+                .arg(
+                    Arg::new("to")
+                        .action(clap::ArgAction::Set)
+                        .default_value("HEAD")
+                        .help("The git revision to compare to"),
+                ),
+        )
+        .subcommand(
+            Command::new("log")
+                .about("Lists the git commits of a Decision Record (renames included), newest first")
+                .arg(
+                    Arg::new("adr")
+                        .action(clap::ArgAction::Set)
+                        .required(true)
+                        .add(ArgValueCandidates::new(completions::ids))
+                        .help("The id or the path of the Decision Record"),
+                ),
+        )
+        .subcommand(
+            Command::new("edit")
+                .about("Opens a Decision Record in $VISUAL (or $EDITOR) and checks it can still be parsed")
+                .arg(
+                    Arg::new("adr")
+                        .action(clap::ArgAction::Set)
+                        .required(true)
+                        .add(ArgValueCandidates::new(completions::ids))
+                        .help("The id or the path of the Decision Record"),
+                ),
+        )
+        .subcommand(
+            Command::new("serve")
+                .about("Serves a web UI (filters, full-text search, rendered ADRs) and a REST API (/api/adrs, /api/adrs/<id or path>, /api/search?q=, /api/tags, /api/graph, POST to create and transition ADRs with If-Match ETags) on localhost")
+                .arg(
+                    Arg::new("port")
+                        .short('p')
+                        .long("port")
+                        .action(clap::ArgAction::Set)
+                        .value_parser(clap::value_parser!(u16))
+                        .default_value("8080")
+                        .help("The port to listen to (on 127.0.0.1)"),
+                ),
+        )
+        .subcommand(
+            Command::new("docs")
+                .about("Generates the command-line reference from the definition of the commands")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("man")
+                        .about("Writes the man pages of adr and of each of its subcommands (e.g. adr-lf-new.1)")
+                        .arg(
+                            Arg::new("dir")
+                                .action(clap::ArgAction::Set)
+                                .required(true)
+                                .help("The output directory, created if needed"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("markdown")
+                        .about("Prints the Markdown reference of all the commands")
+                        .arg(
+                            Arg::new("output")
+                                .short('o')
+                                .long("output")
+                                .action(clap::ArgAction::Set)
+                                .required(false)
+                                .help("Write the reference in this file instead of the standard output"),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("completions")
+                .about("Prints the shell completions script, e.g. source <(adr completions bash) in ~/.bashrc. The ADR ids and paths, tags, templates and configuration items are completed")
+                .arg(
+                    Arg::new("shell")
+                        .action(clap::ArgAction::Set)
+                        .required(true)
+                        .value_parser(completions::SHELLS)
+                        .help("The shell to complete the commands in"),
+                ),
+        )
+        .subcommand(
+            Command::new("tui")
+                .about("Browses the Decision Records in full screen: status colours, preview, incremental full-text search, status and tag filters, lifecycle transitions and editor"),
+        )
+        .subcommand(
+            Command::new("watch")
+                .about("Watches adr_src_dir: the changed ADRs are parsed again and updated in the search index, the site and the graph can be regenerated")
+                .arg(
+                    Arg::new("site")
+                        .long("site")
+                        .action(clap::ArgAction::Set)
+                        .value_name("DIR")
+                        .help("Regenerates the static site (see adr export site) in this directory"),
+                )
+                .arg(
+                    Arg::new("graph")
+                        .long("graph")
+                        .action(clap::ArgAction::Set)
+                        .value_name("FILE")
+                        .help("Regenerates the graph (see adr graph) in this file: Mermaid for .mmd, JSON for .json, DOT otherwise"),
+                ),
+        )
+        .subcommand(
+            Command::new("review")
+                .about("Summarizes, as Markdown (e.g. for a pull request comment), the new ADRs, status transitions, edited decided ADRs, new tags and lint problems compared to a base git revision")
+                .arg(
+                    Arg::new("base")
+                        .long("base")
+                        .action(clap::ArgAction::Set)
+                        .required(true)
+                        .help("The git revision to compare to, e.g. main or origin/main"),
+                )
+                .arg(
+                    Arg::new("head")
+                        .long("head")
+                        .action(clap::ArgAction::Set)
+                        .num_args(0..=1)
+                        .default_missing_value("HEAD")
+                        .help("Review a git revision (HEAD if no value is given) instead of the working tree"),
+                ),
+        )
+        .subcommand(
+            Command::new("graph")
+                .about("Prints the graph of the Decision Records (nodes coloured by status, supersedes / completes edges)")
+                .arg(
+                    Arg::new("format")
+                        .short('f')
+                        .long("format")
+                        .action(clap::ArgAction::Set)
+                        .value_parser(["dot", "mermaid", "json"])
+                        .default_value("dot")
+                        .help("dot (e.g. adr graph | dot -Tsvg > decisions.svg), mermaid or json"),
+                )
+                .arg(
+                    Arg::new("tag")
+                        .short('t')
+                        .long("tag")
+                        .action(clap::ArgAction::Set)
+                        .required(false)
+                        .add(ArgValueCandidates::new(completions::tags))
+                        .help("Only the Decision Records with this tag"),
+                )
+                .arg(
+                    Arg::new("dir")
+                        .long("dir")
+                        .action(clap::ArgAction::Set)
+                        .required(false)
+                        .add(ArgValueCandidates::new(completions::dirs))
+                        .help("Only the Decision Records of this sub directory of adr_src_dir (e.g. data)"),
+                )
+                .arg(at_arg()),
+        )
+        .subcommand(
+            Command::new("lint")
+                .about("Checks the Decision Records (e.g. the outcome of a decided ADR names one of its options, required sections, placeholders, title length, allowed tags - see the lint_* configuration items), exits with 1 if an error is found")
+                .arg(
+                    Arg::new("fix")
+                        .long("fix")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Applies the safe fixes first (trailing whitespace, case of the allowed tags, missing date)"),
+                )
+                .arg(
+                    Arg::new("format")
+                        .short('f')
+                        .long("format")
+                        .action(clap::ArgAction::Set)
+                        .value_parser(["table", "json", "github"])
+                        .default_value("table")
+                        .help("table, json (machine-readable report) or github (GitHub Actions annotations)"),
+                ),
+        )
+        .subcommand(
+            Command::new("check")
+                .about("Checks the Decision Records, exits with 1 if a problem is found")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("links")
+                        .about("Reports the references to missing Decision Records, the one-way references and the inconsistent statuses (e.g. superseded by a wip Decision Record)"),
+                ),
+        )
+        .subcommand(
+            Command::new("hooks")
+                .about("Manage the git pre-commit hook validating the staged Decision Records (title, status and date can be parsed, unique ids, no broken references)")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("install")
+                        .about("Install the pre-commit hook in the git repository containing adr_src_dir"),
+                )
+                .subcommand(Command::new("uninstall").about("Remove the pre-commit hook"))
+                .subcommand(
+                    Command::new("run")
+                        .about("Check the staged Decision Records, as done by the pre-commit hook")
+                        .hide(true),
+                ),
+        )
+        .subcommand(
+            Command::new("doctor")
+                .about("Diagnose the Decision Records")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("ids").about("Find duplicated and missing ids"),
+                ),
+        )
+        .subcommand(
+            Command::new("renumber")
+                .about("Give a new id to a Decision Record (file is renamed and references are rewritten)")
+                .arg(
+                    Arg::new("path")
+                        .short('p')
+                        .long("path")
+                        .action(clap::ArgAction::Set)
+                        .required(true)
+                        .add(ArgValueCandidates::new(completions::ids))
+                        .help("Give the path (or the id) of your Decision Record"),
+                )
+                .arg(
+                    Arg::new("id")
+                        .short('i')
+                        .long("id")
+                        .action(clap::ArgAction::Set)
+                        .value_parser(clap::value_parser!(usize))
+                        .required(false)
+                        .help("The new id (by default, the next available one)"),
+                ),
+        )
+        .subcommand(
+            Command::new("import")
+                .about("Import Decision Records from other tools")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("adr-tools")
+                        .about("Import npryce adr-tools records (e.g. doc/adr/0001-record-architecture-decisions.md) into adr_src_dir")
+                        .arg(
+                            Arg::new("dir")
+                                .action(clap::ArgAction::Set)
+                                .required(true)
+                                .help("The directory containing the adr-tools records (e.g. doc/adr)"),
+                        )
+                        .arg(dry_run_arg()),
+                )
+                .subcommand(
+                    Command::new("madr")
+                        .about("Import MADR records (e.g. docs/decisions/0001-use-madr.md), keeping the nested directories")
+                        .arg(
+                            Arg::new("dir")
+                                .action(clap::ArgAction::Set)
+                                .required(true)
+                                .help("The directory containing the MADR records (e.g. docs/decisions)"),
+                        )
+                        .arg(dry_run_arg()),
+                )
+                .subcommand(
+                    Command::new("log4brains")
+                        .about("Import log4brains records (e.g. docs/adr/backend/20200101-use-kafka.md), keeping the nested directories")
+                        .arg(
+                            Arg::new("dir")
+                                .action(clap::ArgAction::Set)
+                                .required(true)
+                                .help("The directory containing the log4brains records (e.g. docs/adr)"),
+                        )
+                        .arg(dry_run_arg()),
+                ),
+        )
+        .subcommand(
+            Command::new("export")
+                .about("Publish the Decision Records")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("site")
+                        .about("Generate a browsable static site (index filterable by status and tag, one page per Decision Record, tag pages and a timeline)")
+                        .arg(
+                            Arg::new("out-dir")
+                                .action(clap::ArgAction::Set)
+                                .required(true)
+                                .help("The directory where the site is generated"),
+                        ),
+                ),
         )
         .subcommand(
             Command::new("init")
-                .about("Init ADRust based on config")
-                .version("0.1.0"),
+                .about("Init ADRust based on config"),
+        )
+        .subcommand(
+            Command::new("migrate")
+                .about("Declare ADRs metadata (status, date, tags, id) as header attributes (e.g. :adr-status:)")
+                .arg(
+                    Arg::new("dry-run")
+                        .short('d')
+                        .long("dry-run")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Only show the ADRs that would be migrated"),
+                ),
         )
         .subcommand(
             Command::new("config")
@@ -273,6 +1286,7 @@ fn main() {
                                 .long("name")
                                 .required(true)
                                 .action(clap::ArgAction::Set)
+                                .add(ArgValueCandidates::new(completions::config_names))
                                 .help("the name of the property"),
                         )
                         .arg(
@@ -294,20 +1308,65 @@ fn main() {
                 .subcommand_required(true)
                 .subcommand(Command::new("list").about("List All the Tags")),
         )
+        .subcommand(
+            Command::new("templates")
+                .about("Manage Templates")
+                .subcommand_required(true)
+                .subcommand(Command::new("list").about("List All the Templates"))
+                .subcommand(
+                    Command::new("show")
+                        .about("Show the content of a Template")
+                        .arg(
+                            Arg::new("name")
+                                .action(clap::ArgAction::Set)
+                                .required(true)
+                                .add(ArgValueCandidates::new(completions::templates))
+                                .help("The name of the template"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("add")
+                        .about("Add a Template to adr_template_dir")
+                        .arg(
+                            Arg::new("name")
+                                .short('n')
+                                .long("name")
+                                .action(clap::ArgAction::Set)
+                                .required(true)
+                                .help("The name of the template"),
+                        )
+                        .arg(
+                            Arg::new("file")
+                                .short('f')
+                                .long("file")
+                                .action(clap::ArgAction::Set)
+                                .required(true)
+                                .help("The file to use as template. Variables are {{title}}, {{id}}, {{date}}, {{author}}, {{tags}}, {{deciders}} and {{slug}}"),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("people")
+                .about("Manage People (Decision Makers, Experts, Stakeholders)")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("list")
+                        .about("List who participated in how many decisions and in which role"),
+                ),
+        )
         .subcommand(
             Command::new("lf")
                 .about("Manages ADRs lifecycle")
                 .subcommand_required(true)
                 .subcommand(
                     Command::new("new")
-                        .about("Creates a new Decision Record")
-                        .version("0.1.0")
+                        .about("Creates a new Decision Record (asks for the details if no title is given)")
                         .arg(
                             Arg::new("title")
                                 .short('t')
                                 .long("title")
                                 .action(clap::ArgAction::Set)
-                                .required(true)
+                                .required(false)
                                 .help("Give the title of your Decision Record"),
                         )
                         .arg(
@@ -316,32 +1375,78 @@ fn main() {
                                 .long("path")
                                 .action(clap::ArgAction::Set)
                                 .required(false)
+                                .add(ArgValueCandidates::new(completions::dirs))
                                 .help("Specify relative path (nested directories)"),
+                        )
+                        .arg(
+                            Arg::new("template")
+                                .long("template")
+                                .action(clap::ArgAction::Set)
+                                .required(false)
+                                .add(ArgValueCandidates::new(completions::templates))
+                                .help("The name of the template to use (see adr templates list), adr_template_file by default"),
+                        )
+                        .arg(
+                            Arg::new("tags")
+                                .long("tags")
+                                .action(clap::ArgAction::Set)
+                                .required(false)
+                                .add(ArgValueCompleter::new(completions::tag_list))
+                                .help("Comma separated list of tags, e.g. \"security, deployment view\""),
+                        )
+                        .arg(
+                            Arg::new("deciders")
+                                .long("deciders")
+                                .action(clap::ArgAction::Set)
+                                .required(false)
+                                .help("Comma separated list of Decision Makers (the author by default)"),
+                        )
+                        .arg(
+                            Arg::new("interactive")
+                                .short('i')
+                                .long("interactive")
+                                .action(clap::ArgAction::SetTrue)
+                                .help("Ask for the title, template, nested directory, tags and deciders (values given as flags are proposed)"),
+                        )
+                        .arg(
+                            Arg::new("edit")
+                                .short('e')
+                                .long("edit")
+                                .action(clap::ArgAction::SetTrue)
+                                .help("Open the new Decision Record in $VISUAL (or $EDITOR)"),
+                        )
+                        .arg(commit_arg())
+                        .arg(
+                            Arg::new("branch")
+                                .long("branch")
+                                .action(clap::ArgAction::SetTrue)
+                                .help("Create and check out a branch (named after branch_pattern) for the new Decision Record (always done if auto_branch is true)"),
                         ),
                 )
                 .subcommand(
                     Command::new("decided")
                         .about("update the Status to Decide")
-                        .version("0.1.0")
                         .arg(
                             Arg::new("path")
                                 .short('p')
                                 .long("path")
                                 .action(clap::ArgAction::Set)
                                 .required(true)
+                                .add(ArgValueCandidates::new(completions::paths(TransitionStatus::DECIDED)))
                                 .help("Give the path of your Decision Record"),
-                        ),
+                        )
+                        .arg(commit_arg()),
                 )
                 .subcommand(
                     Command::new("superseded-by")
                         .about("Supersede the decision with another decision")
-                        .version("0.1.0")
                         .arg(
                             Arg::new("path")
                                 .short('p')
                                 .long("path")
                                 .action(clap::ArgAction::Set)
                                 .required(true)
+                                .add(ArgValueCandidates::new(completions::paths(TransitionStatus::SUPERSEDED)))
                                 .help("Give the path of your Decision Record"),
                         )
                         .arg(
@@ -350,19 +1455,21 @@ fn main() {
                                 .long("by")
                                 .action(clap::ArgAction::Set)
                                 .required(true)
+                                .add(ArgValueCandidates::new(completions::paths(TransitionStatus::SUPERSEDES)))
                                 .help("Give the path of your Decision Record"),
-                        ),
+                        )
+                        .arg(commit_arg()),
                 )
                 .subcommand(
                     Command::new("completed-by")
                         .about("Complete a decision with another decision")
-                        .version("0.1.0")
                         .arg(
                             Arg::new("path")
                                 .short('p')
                                 .long("path")
                                 .action(clap::ArgAction::Set)
                                 .required(true)
+                                .add(ArgValueCandidates::new(completions::paths(TransitionStatus::COMPLETED)))
                                 .help("Give the path of the DR which is completed by"),
                         )
                         .arg(
@@ -371,77 +1478,280 @@ fn main() {
                                 .long("by")
                                 .action(clap::ArgAction::Set)
                                 .required(true)
+                                .add(ArgValueCandidates::new(completions::paths(TransitionStatus::COMPLETES)))
                                 .help("Give the path of the DR which completes"),
-                        ),
+                        )
+                        .arg(commit_arg()),
                 )
                 .subcommand(
                     Command::new("obsoleted")
                         .about("Uodate the Status to Obsoleted")
-                        .version("0.1.0")
                         .arg(
                             Arg::new("path")
                                 .short('p')
                                 .long("path")
                                 .action(clap::ArgAction::Set)
                                 .required(true)
+                                .add(ArgValueCandidates::new(completions::paths(TransitionStatus::CANCELLED)))
                                 .help("Give the path of your Decision Record"),
-                        ),
+                        )
+                        .arg(commit_arg()),
                 ),
         )
         .subcommand(
             Command::new("search")
                 .about("Search across all ADRs")
-                .version("0.1.0")
+                .after_long_help(SEARCH_SYNTAX)
                 .args(&[
                     Arg::new("query")
                         .short('q')
                         .long("query")
                         .action(clap::ArgAction::Set)
                         .required(true)
-                        .conflicts_with_all(&["build-index", "title"])
-                        .help("Provide your search query. The following syntax can be used :\n\
-                            \ta AND b OR c will search for documents containing terms (a and b) or c, \n\
-                            \t-b will search documents that do not contain the term b, \n\
-                            \t+c will search documents that must contain the term c, \n\
-                            \ttags:a AND tags:b will search for documents that have the tags a and b, \n\
-                            \ttitle:a will search on title of the document, \n\
-                            \tdate:[2022-08-01T00:00:00Z TO 2023-10-02T18:00:00Z] AND tags:BPaaS will search between the specified range and date (and specified tag), \n\
-                            \tstatus:decided will search for decided documents"),
+                        .conflicts_with_all(["build-index", "title"])
+                        .help("Provide your search query (see adr search --help for the syntax)"),
                     Arg::new("build-index")
                         .short('b')
                         .long("build-index")
                         .action(clap::ArgAction::SetTrue)
                         .required(true)
-                        .conflicts_with_all(&["query", "title"])
+                        .conflicts_with_all(["query", "title"])
                         .help("Build the index based on available ADRs."),
                     Arg::new("title")
                         .short('t')
                         .long("title")
                         .action(clap::ArgAction::Set)
                         .required(true)
-                        .conflicts_with_all(&["build-index", "query"])
+                        .conflicts_with_all(["build-index", "query"])
                         .help("Search on title property of ADR only"),
                 ]),
-        );
+        )
+}
+
+///
+/// The main program - start the CLI ...
+fn main() {
+    clap_complete::CompleteEnv::with_factory(build_cli)
+        .var(completions::COMPLETE_VAR)
+        .complete();
 
     //
-    let _matches = cmd.get_matches();
+    let _matches = build_cli().get_matches();
     let subcommand = _matches.subcommand();
 
     match subcommand {
-        Some(("list", _matches)) => {
-            list_all_adr().unwrap();
+        Some(("list", matches)) => {
+            list_all_adr(
+                matches.get_one::<String>("decider").map(|s| s.as_str()),
+                matches.get_one::<String>("at"),
+            )
+            .unwrap();
         }
+        Some(("show", matches)) => {
+            show_adr(
+                matches.get_one::<String>("adr").unwrap(),
+                matches.get_flag("options"),
+                matches.get_flag("history"),
+                matches.get_one::<String>("at"),
+            )
+            .unwrap();
+        }
+        Some(("diff", matches)) => {
+            diff(
+                matches.get_one::<String>("from").unwrap(),
+                matches.get_one::<String>("to").unwrap(),
+            )
+            .unwrap();
+        }
+        Some(("log", matches)) => {
+            log_adr(matches.get_one::<String>("adr").unwrap()).unwrap();
+        }
+        Some(("edit", matches)) => {
+            if !edit_adr(matches.get_one::<String>("adr").unwrap()).unwrap() {
+                std::process::exit(1);
+            }
+        }
+        Some(("hooks", hooks_matches)) => match hooks_matches.subcommand() {
+            Some(("install", _matches)) => {
+                install_hook().unwrap();
+            }
+            Some(("uninstall", _matches)) => {
+                uninstall_hook().unwrap();
+            }
+            Some(("run", _matches)) => {
+                if !run_hook().unwrap() {
+                    std::process::exit(1);
+                }
+            }
+            _ => unreachable!(),
+        },
+        Some(("check", check_matches)) => match check_matches.subcommand() {
+            Some(("links", _matches)) => {
+                if !check_links().unwrap() {
+                    std::process::exit(1);
+                }
+            }
+            _ => unreachable!(),
+        },
+        Some(("lint", matches)) => {
+            if !lint(matches.get_flag("fix"), matches.get_one::<String>("format").unwrap()).unwrap() {
+                std::process::exit(1);
+            }
+        }
+        Some(("import", import_matches)) => match import_matches.subcommand() {
+            Some((format, matches)) => {
+                import(
+                    format,
+                    matches.get_one::<String>("dir").unwrap(),
+                    matches.get_flag("dry-run"),
+                )
+                .unwrap();
+            }
+            _ => unreachable!(),
+        },
+        Some(("serve", matches)) => {
+            let cfg: AdrToolConfig = adr_config::config::get_config();
+            server::serve(cfg, *matches.get_one::<u16>("port").unwrap()).unwrap();
+        }
+        Some(("watch", matches)) => {
+            let cfg: AdrToolConfig = adr_config::config::get_config();
+            let outputs = watch::Outputs {
+                site: matches.get_one::<String>("site").cloned(),
+                graph: matches.get_one::<String>("graph").cloned(),
+            };
+            if let Err(why) = watch::watch(cfg, outputs) {
+                eprintln!("{}", why);
+                std::process::exit(1);
+            }
+        }
+        Some(("docs", matches)) => match matches.subcommand() {
+            Some(("man", man_matches)) => {
+                let dir = man_matches.get_one::<String>("dir").unwrap();
+                match docs::write_man_pages(build_cli(), Path::new(dir)) {
+                    Ok(files) => println!("{} man page(s) written in [{}]", files.len(), dir),
+                    Err(why) => {
+                        eprintln!("cannot write the man pages in [{}] - {}", dir, why);
+                        std::process::exit(1);
+                    }
+                }
+            }
+            Some(("markdown", markdown_matches)) => {
+                let markdown = docs::markdown(&build_cli());
+                match markdown_matches.get_one::<String>("output") {
+                    Some(file) => {
+                        if let Err(why) = std::fs::write(file, markdown) {
+                            eprintln!("cannot write [{}] - {}", file, why);
+                            std::process::exit(1);
+                        }
+                    }
+                    None => print!("{}", markdown),
+                }
+            }
+            _ => unreachable!(),
+        },
+        Some(("completions", matches)) => {
+            if let Err(why) = completions::print_registration(matches.get_one::<String>("shell").unwrap()) {
+                eprintln!("{}", why);
+                std::process::exit(1);
+            }
+        }
+        Some(("tui", _matches)) => {
+            let cfg: AdrToolConfig = adr_config::config::get_config();
+            if let Err(why) = tui::run(cfg) {
+                eprintln!("{}", why);
+                std::process::exit(1);
+            }
+        }
+        Some(("review", matches)) => {
+            review(
+                matches.get_one::<String>("base").unwrap(),
+                matches.get_one::<String>("head"),
+            )
+            .unwrap();
+        }
+        Some(("graph", matches)) => {
+            graph(
+                matches.get_one::<String>("format").unwrap(),
+                matches.get_one::<String>("tag"),
+                matches.get_one::<String>("dir"),
+                matches.get_one::<String>("at"),
+            )
+            .unwrap();
+        }
+        Some(("export", export_matches)) => match export_matches.subcommand() {
+            Some(("site", matches)) => {
+                export_site(matches.get_one::<String>("out-dir").unwrap()).unwrap();
+            }
+            _ => unreachable!(),
+        },
         Some(("init", _matches)) => {
             init().unwrap();
         }
+        Some(("doctor", doctor_matches)) => match doctor_matches.subcommand() {
+            Some(("ids", _matches)) => {
+                if !check_ids().unwrap() {
+                    std::process::exit(1);
+                }
+            }
+            _ => unreachable!(),
+        },
+        Some(("renumber", matches)) => {
+            renumber(
+                matches.get_one::<String>("path").unwrap(),
+                matches.get_one::<usize>("id").copied(),
+            )
+            .unwrap();
+        }
+        Some(("migrate", matches)) => {
+            migrate(matches.get_flag("dry-run")).unwrap();
+        }
         Some(("lf", matches)) => match matches.subcommand() {
             Some(("new", matches)) => {
-                if matches.get_one::<String>("title").is_some() {
-                    adr_core::adr_repo::create_adr(
-                        adr_config::config::get_config(),
-                        matches.get_one::<String>("path").map(|s| s.as_str()),
-                        matches.get_one::<String>("title").unwrap(),
+                let cfg: AdrToolConfig = adr_config::config::get_config();
+                let split = |name: &str| {
+                    matches
+                        .get_one::<String>(name)
+                        .map(|s| wizard::split_list(s))
+                        .unwrap_or_default()
+                };
+                let mut new_adr = wizard::NewAdr {
+                    title: matches.get_one::<String>("title").cloned().unwrap_or_default(),
+                    template: matches.get_one::<String>("template").cloned(),
+                    path: matches.get_one::<String>("path").cloned(),
+                    tags: split("tags"),
+                    deciders: split("deciders"),
+                };
+                if matches.get_flag("interactive") || new_adr.title.is_empty() {
+                    if !io::stdin().is_terminal() {
+                        eprintln!("--title is required when not running in a terminal");
+                        std::process::exit(1);
+                    }
+                    new_adr = wizard::prompt(&cfg, new_adr).unwrap();
+                }
+
+                let base_path = cfg.adr_src_dir.clone();
+                let created = adr_core::adr_repo::create_adr_from_template(
+                    cfg,
+                    new_adr.path.as_deref(),
+                    &new_adr.title,
+                    new_adr.template.as_deref(),
+                    &new_adr.vars(),
+                )
+                .unwrap();
+                if let Some(path) = created {
+                    if matches.get_flag("edit") && !editor::edit(Path::new(&base_path), &path).unwrap() {
+                        std::process::exit(1);
+                    }
+                    let cfg: AdrToolConfig = adr_config::config::get_config();
+                    if matches.get_flag("branch") || cfg.auto_branch {
+                        let adr = adr_core::adr_repo::build_adr(Path::new(&base_path), &path).unwrap();
+                        let branch = adr_core::adr_git::create_branch(&cfg, &adr).unwrap();
+                        println!("Switched to the new branch [{}]", branch);
+                    }
+                    commit_lifecycle(
+                        matches.get_flag("commit"),
+                        &format!("created \"{}\"", new_adr.title),
+                        &[path],
                     )
                     .unwrap();
                 }
@@ -452,7 +1762,9 @@ fn main() {
                     let cfg: AdrToolConfig = adr_config::config::get_config();
                     let base_path = Path::new(&cfg.adr_src_dir);
 
-                    adr_core::adr_repo::transition_to_decided(base_path, file_path).unwrap();
+                    if adr_core::adr_repo::transition_to_decided(base_path, file_path).unwrap() {
+                        commit_lifecycle(set_matches.get_flag("commit"), "decided", &[PathBuf::from(file_path)]).unwrap();
+                    }
                 }
             }
             Some(("completed-by", set_matches)) => {
@@ -462,8 +1774,14 @@ fn main() {
                     let file_path = set_matches.get_one::<String>("path").unwrap();
                     let by_path = set_matches.get_one::<String>("by").unwrap();
 
-                    adr_core::adr_repo::transition_to_completed_by(base_path, file_path, by_path)
+                    if adr_core::adr_repo::transition_to_completed_by(base_path, file_path, by_path).unwrap() {
+                        commit_lifecycle(
+                            set_matches.get_flag("commit"),
+                            &format!("completed by {}", get_adr_ref(by_path).unwrap()),
+                            &[PathBuf::from(file_path), PathBuf::from(by_path)],
+                        )
                         .unwrap();
+                    }
                 }
             }
             Some(("superseded-by", set_matches)) => {
@@ -473,8 +1791,14 @@ fn main() {
                     let file_path = set_matches.get_one::<String>("path").unwrap();
                     let by_path = set_matches.get_one::<String>("by").unwrap();
 
-                    adr_core::adr_repo::transition_to_superseded_by(base_path, file_path, by_path)
+                    if adr_core::adr_repo::transition_to_superseded_by(base_path, file_path, by_path).unwrap() {
+                        commit_lifecycle(
+                            set_matches.get_flag("commit"),
+                            &format!("superseded by {}", get_adr_ref(by_path).unwrap()),
+                            &[PathBuf::from(file_path), PathBuf::from(by_path)],
+                        )
                         .unwrap();
+                    }
                 }
             }
             Some(("obsoleted", set_matches)) => {
@@ -483,7 +1807,9 @@ fn main() {
                     let base_path = Path::new(&cfg.adr_src_dir);
                     let file_path = set_matches.get_one::<String>("path").unwrap();
 
-                    adr_core::adr_repo::transition_to_obsoleted(base_path, file_path).unwrap();
+                    if adr_core::adr_repo::transition_to_obsoleted(base_path, file_path).unwrap() {
+                        commit_lifecycle(set_matches.get_flag("commit"), "obsoleted", &[PathBuf::from(file_path)]).unwrap();
+                    }
                 }
             }
 
@@ -508,6 +1834,28 @@ fn main() {
             }
             _ => unreachable!(),
         },
+        Some(("templates", templates_matches)) => match templates_matches.subcommand() {
+            Some(("list", _matches)) => {
+                list_all_templates().unwrap();
+            }
+            Some(("show", matches)) => {
+                show_template(matches.get_one::<String>("name").unwrap()).unwrap();
+            }
+            Some(("add", matches)) => {
+                add_template(
+                    matches.get_one::<String>("name").unwrap(),
+                    matches.get_one::<String>("file").unwrap(),
+                )
+                .unwrap();
+            }
+            _ => unreachable!(),
+        },
+        Some(("people", people_matches)) => match people_matches.subcommand() {
+            Some(("list", _remote_matches)) => {
+                list_all_people().unwrap();
+            }
+            _ => unreachable!(),
+        },
         Some(("search", search_matches)) => {
             if search_matches.get_one::<String>("query").is_some() {
                 let query = search_matches.get_one::<String>("query").unwrap().to_string();
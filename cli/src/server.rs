@@ -0,0 +1,816 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use slog::{debug, info, warn};
+
+use serde_json::{json, Value};
+
+use adr_config::config::AdrToolConfig;
+use adr_core::adr_repo::{self, Adr, TransitionStatus};
+use adr_core::{adr_export, adr_graph, adr_template};
+
+/// The web UI, a single page using the REST API
+const UI_HTML: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1.0">
+<title>Decision Records</title>
+<link rel="stylesheet" href="/css/theme.css" media="not all and (prefers-color-scheme: dark)">
+<link rel="stylesheet" href="/css/theme_dark.css" media="(prefers-color-scheme: dark)">
+<style>
+#content { display: flex; gap: 2em; max-width: none; }
+#list { flex: 1; min-width: 30em; }
+#adr { flex: 2; }
+.filters input, .filters select { margin-right: 1em; }
+#adrs tbody tr { cursor: pointer; }
+#adrs tbody tr.selected { font-weight: bold; }
+.tags { margin-right: .3em; }
+</style>
+</head>
+<body class="article">
+<div id="header"><h1>Decision Records</h1></div>
+<div id="content">
+<div id="list">
+<div class="filters">
+<input id="query" type="search" placeholder="Full-text search, e.g. kafka AND tags:security">
+<select id="status"><option value="">All statuses</option></select>
+<select id="tag"><option value="">All tags</option></select>
+</div>
+<p id="message"></p>
+<table id="adrs" class="tableblock frame-all grid-all stretch">
+<thead><tr><th>ID</th><th>Title</th><th>Status</th><th>Date</th><th>Tags</th></tr></thead>
+<tbody></tbody>
+</table>
+</div>
+<div id="adr"></div>
+</div>
+<script>
+var adrs = [];
+var found = null;
+
+function escapeHtml(val) {
+    return String(val).replace(/&/g, '&amp;').replace(/</g, '&lt;').replace(/>/g, '&gt;').replace(/"/g, '&quot;');
+}
+
+function getJson(url) {
+    return fetch(url).then(function (response) {
+        return response.json().then(function (body) {
+            if (!response.ok) { throw new Error(body.error || response.statusText); }
+            return body;
+        });
+    });
+}
+
+function render() {
+    var status = document.getElementById('status').value;
+    var tag = document.getElementById('tag').value;
+    var selected = decodeURIComponent(location.hash.replace(/^#adr=/, ''));
+    var rows = adrs.filter(function (adr) {
+        return (!status || adr.status === status)
+            && (!tag || adr.tags.indexOf(tag) >= 0)
+            && (!found || found.indexOf(adr.path) >= 0);
+    }).map(function (adr) {
+        return '<tr data-path="' + escapeHtml(adr.path) + '"' + (adr.path === selected ? ' class="selected"' : '') + '>'
+            + '<td>' + adr.id + '</td><td>' + escapeHtml(adr.title) + '</td><td>' + adr.status + '</td><td>' + escapeHtml(adr.date)
+            + '</td><td>' + adr.tags.map(function (t) { return '<span class="tags">#' + escapeHtml(t) + '</span>'; }).join('') + '</td></tr>';
+    });
+    document.querySelector('#adrs tbody').innerHTML = rows.join('');
+    document.getElementById('message').textContent = rows.length + ' / ' + adrs.length + ' Decision Record(s)';
+}
+
+function showAdr() {
+    var path = decodeURIComponent(location.hash.replace(/^#adr=/, ''));
+    if (!path) { document.getElementById('adr').innerHTML = ''; return; }
+    getJson('/api/adrs/' + encodeURIComponent(path)).then(function (adr) {
+        document.getElementById('adr').innerHTML = adr.html;
+        render();
+    }).catch(function (error) {
+        document.getElementById('adr').textContent = error.message;
+    });
+}
+
+function search() {
+    var query = document.getElementById('query').value.trim();
+    if (!query) { found = null; render(); return; }
+    getJson('/api/search?q=' + encodeURIComponent(query)).then(function (results) {
+        found = results.map(function (adr) { return adr.path; });
+        render();
+    }).catch(function (error) {
+        found = [];
+        render();
+        document.getElementById('message').textContent = error.message;
+    });
+}
+
+document.querySelector('#adrs tbody').addEventListener('click', function (event) {
+    var row = event.target.closest('tr');
+    if (row) { location.hash = 'adr=' + encodeURIComponent(row.dataset.path); }
+});
+//the tags of the rendered ADR filter the list
+document.getElementById('adr').addEventListener('click', function (event) {
+    var link = event.target.closest('a.tags');
+    if (link) {
+        event.preventDefault();
+        document.getElementById('tag').value = link.textContent.trim();
+        render();
+    }
+});
+document.getElementById('status').addEventListener('change', render);
+document.getElementById('tag').addEventListener('change', render);
+document.getElementById('query').addEventListener('change', search);
+window.addEventListener('hashchange', showAdr);
+
+Promise.all([getJson('/api/adrs'), getJson('/api/tags')]).then(function (results) {
+    adrs = results[0];
+    var statuses = adrs.map(function (adr) { return adr.status; }).filter(function (s, i, all) { return all.indexOf(s) === i; });
+    document.getElementById('status').innerHTML += statuses.map(function (s) { return '<option>' + s + '</option>'; }).join('');
+    document.getElementById('tag').innerHTML += Object.keys(results[1]).sort().map(function (t) {
+        return '<option value="' + escapeHtml(t) + '">' + escapeHtml(t) + ' (' + results[1][t] + ')</option>';
+    }).join('');
+    render();
+    showAdr();
+}).catch(function (error) {
+    document.getElementById('message').textContent = error.message;
+});
+</script>
+</body>
+</html>
+"##;
+
+/// The bodies of the POST requests are small JSON documents
+const MAX_BODY_LENGTH: usize = 1024 * 1024;
+
+/// A client which does not send its request in time is disconnected, instead of holding a thread forever
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+struct Request {
+    method: String,
+    path: String,
+    params: HashMap<String, String>,
+    /// the names are lowercase
+    headers: HashMap<String, String>,
+    body: String,
+}
+
+struct Response {
+    code: u16,
+    content_type: &'static str,
+    body: String,
+    etag: Option<String>,
+}
+
+impl Response {
+    fn json(body: Value) -> Response {
+        Response {
+            code: 200,
+            content_type: "application/json",
+            body: body.to_string(),
+            etag: None,
+        }
+    }
+
+    fn error(code: u16, message: &str) -> Response {
+        Response {
+            code,
+            content_type: "application/json",
+            body: json!({ "error": message }).to_string(),
+            etag: None,
+        }
+    }
+
+    /// The response to a request on a Decision Record, with its ETag
+    fn adr(code: u16, adr: &Adr, body: Value) -> Response {
+        let etag = etag(&adr.content);
+        let mut body = body;
+        body["etag"] = json!(etag);
+        Response {
+            code,
+            content_type: "application/json",
+            body: body.to_string(),
+            etag: Some(etag),
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.code {
+            200 => "OK",
+            201 => "Created",
+            400 => "Bad Request",
+            403 => "Forbidden",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            409 => "Conflict",
+            413 => "Payload Too Large",
+            415 => "Unsupported Media Type",
+            422 => "Unprocessable Entity",
+            428 => "Precondition Required",
+            _ => "Internal Server Error",
+        }
+    }
+}
+
+struct Server {
+    cfg: AdrToolConfig,
+    /// the port listened to, the only one accepted in the `Host` and `Origin` headers
+    port: u16,
+    /// the (path, content) of the indexed ADRs, the search index is rebuilt when they change
+    indexed: Mutex<Vec<(String, String)>>,
+    /// the ETags are checked and the files written under this lock
+    writing: Mutex<()>,
+}
+
+/// The ETag of a Decision Record: the FNV-1a hash of its content, so it is the same after a restart
+fn etag(content: &str) -> String {
+    let hash = content.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+
+    format!("\"{:016x}\"", hash)
+}
+
+/// Returns the ADR `id_or_path` (an id or a path relative to `adr_src_dir`) among `adrs`
+fn find<'a>(adrs: &'a [Adr], id_or_path: &str) -> Option<&'a Adr> {
+    match id_or_path.parse::<usize>() {
+        Ok(id) => adrs.iter().find(|adr| adr.file_id == id),
+        Err(_) => adrs.iter().find(|adr| adr.file_path == id_or_path),
+    }
+}
+
+/// A string, or a number for the ids
+fn get_str(body: &Value, name: &str) -> Option<String> {
+    match &body[name] {
+        Value::String(val) if !val.trim().is_empty() => Some(val.trim().to_string()),
+        Value::Number(val) => Some(val.to_string()),
+        _ => None,
+    }
+}
+
+/// A list given as an array or as a comma separated string
+fn get_list(body: &Value, name: &str) -> Vec<String> {
+    match &body[name] {
+        Value::Array(vals) => vals
+            .iter()
+            .filter_map(|v| v.as_str())
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .collect(),
+        Value::String(val) => crate::wizard::split_list(val),
+        _ => Vec::new(),
+    }
+}
+
+/// Serves the REST API (under `/api`) and the web UI on `127.0.0.1:<port>` until the process is stopped
+pub fn serve(cfg: AdrToolConfig, port: u16) -> io::Result<()> {
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    info!(crate::get_logger(), "Serving [{}] on [{}]", cfg.adr_src_dir, listener.local_addr()?);
+    println!("Serving the Decision Records on http://{} (Ctrl+C to stop)", listener.local_addr()?);
+
+    let server = Arc::new(Server {
+        cfg,
+        port: listener.local_addr()?.port(),
+        indexed: Mutex::new(Vec::new()),
+        writing: Mutex::new(()),
+    });
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(why) => {
+                warn!(crate::get_logger(), "Connection failed - [{}]", why);
+                continue;
+            }
+        };
+        let server = Arc::clone(&server);
+        std::thread::spawn(move || {
+            if let Err(why) = server.handle(stream) {
+                debug!(crate::get_logger(), "Unable to answer the request - [{}]", why);
+            }
+        });
+    }
+
+    Ok(())
+}
+
+/// Decodes the `%XX` sequences (and `+` in query strings)
+fn decode(val: &str, plus_as_space: bool) -> String {
+    let hex = |digits: &[u8]| {
+        std::str::from_utf8(digits)
+            .ok()
+            .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+    };
+    let bytes = val.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() && hex(&bytes[i + 1..i + 3]).is_some() => {
+                decoded.push(hex(&bytes[i + 1..i + 3]).unwrap());
+                i += 2;
+            }
+            b'+' if plus_as_space => decoded.push(b' '),
+            b => decoded.push(b),
+        }
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+fn summary(adr: &Adr) -> Value {
+    json!({
+        "id": adr.file_id,
+        "title": adr.title,
+        "status": adr.status.as_str(),
+        "date": adr.date,
+        "path": adr.file_path,
+        "tags": adr.tags_array.iter().map(|t| t.trim()).filter(|t| !t.is_empty()).collect::<Vec<&str>>(),
+        "deciders": adr.deciders,
+    })
+}
+
+impl Server {
+    /// Reads the request line, the headers and the body (if any, given its Content-Length)
+    fn read_request(stream: &TcpStream) -> io::Result<Request> {
+        stream.set_read_timeout(Some(READ_TIMEOUT))?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line)?;
+        let mut headers = HashMap::new();
+        let mut header = String::new();
+        while reader.read_line(&mut header)? > 0 && !header.trim().is_empty() {
+            if let Some((name, value)) = header.split_once(':') {
+                headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+            }
+            header.clear();
+        }
+
+        let length: usize = headers.get("content-length").and_then(|l| l.parse().ok()).unwrap_or(0);
+        if length > MAX_BODY_LENGTH {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "The body is too large"));
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body)?;
+
+        let mut parts = request_line.split_whitespace();
+        let method = parts.next().unwrap_or_default().to_string();
+        let target = parts.next().unwrap_or("/");
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let params: HashMap<String, String> = query
+            .split('&')
+            .filter_map(|param| param.split_once('='))
+            .map(|(name, value)| (decode(name, true), decode(value, true)))
+            .collect();
+
+        Ok(Request {
+            method,
+            path: decode(path, false),
+            params,
+            headers,
+            body: String::from_utf8_lossy(&body).to_string(),
+        })
+    }
+
+    fn handle(&self, stream: TcpStream) -> io::Result<()> {
+        let response = match Server::read_request(&stream) {
+            Ok(request) => {
+                let response = self.answer(&request);
+                debug!(crate::get_logger(), "[{} {}] - [{}]", request.method, request.path, response.code);
+                response
+            }
+            Err(why) if why.kind() == io::ErrorKind::InvalidData => Response::error(413, &why.to_string()),
+            Err(why) => return Err(why),
+        };
+
+        let mut stream = stream;
+        write!(
+            stream,
+            "HTTP/1.1 {} {}\r\nContent-Type: {}; charset=utf-8\r\nContent-Length: {}\r\n",
+            response.code,
+            response.reason(),
+            response.content_type,
+            response.body.len()
+        )?;
+        if let Some(etag) = &response.etag {
+            write!(stream, "ETag: {}\r\n", etag)?;
+        }
+        write!(stream, "Connection: close\r\n\r\n")?;
+        stream.write_all(response.body.as_bytes())?;
+        stream.flush()
+    }
+
+    fn answer(&self, request: &Request) -> Response {
+        if let Some(why) = self.check_origin(request) {
+            return Response::error(403, &why);
+        }
+        let response = match request.method.as_str() {
+            "GET" => self.route(&request.path, &request.params),
+            "POST" => self.post(request),
+            _ => Ok(Response::error(405, "Only GET and POST are supported")),
+        };
+
+        response.unwrap_or_else(|why| match why.kind() {
+            io::ErrorKind::NotFound => Response::error(404, &why.to_string()),
+            io::ErrorKind::InvalidInput => Response::error(400, &why.to_string()),
+            _ => Response::error(500, &why.to_string()),
+        })
+    }
+
+    /// Only the pages served by this server may call it: the `Host` (against DNS rebinding) and the `Origin`, if any,
+    /// must be `127.0.0.1:<port>` or `localhost:<port>`
+    fn check_origin(&self, request: &Request) -> Option<String> {
+        let hosts = [format!("127.0.0.1:{}", self.port), format!("localhost:{}", self.port)];
+        let host = request.headers.get("host").map(|h| h.to_lowercase()).unwrap_or_default();
+        if !hosts.contains(&host) {
+            return Some(format!("The host [{}] is not allowed", host));
+        }
+        match request.headers.get("origin").map(|o| o.to_lowercase()) {
+            Some(origin) if !hosts.iter().any(|h| origin == format!("http://{}", h)) => {
+                Some(format!("The origin [{}] is not allowed", origin))
+            }
+            _ => None,
+        }
+    }
+
+    fn route(&self, path: &str, params: &HashMap<String, String>) -> io::Result<Response> {
+        let base_path = Path::new(&self.cfg.adr_src_dir);
+        let param = |name: &str| params.get(name).map(|v| v.as_str()).filter(|v| !v.is_empty());
+
+        match path {
+            "/" | "/index.html" => Ok(Response {
+                code: 200,
+                content_type: "text/html",
+                body: UI_HTML.to_string(),
+                etag: None,
+            }),
+            "/css/theme.css" | "/css/theme_dark.css" => Ok(Response {
+                code: 200,
+                content_type: "text/css",
+                body: adr_export::get_theme(&self.cfg, path.trim_start_matches("/css/"))?,
+                etag: None,
+            }),
+            "/api/adrs" => {
+                let adrs: Vec<Value> = adr_repo::list_all_adr(base_path)?
+                    .iter()
+                    .filter(|adr| param("status").map(|s| adr.status.as_str() == s).unwrap_or(true))
+                    .filter(|adr| param("tag").map(|t| adr.tags_array.iter().any(|a| a.trim() == t)).unwrap_or(true))
+                    .map(summary)
+                    .collect();
+                Ok(Response::json(Value::from(adrs)))
+            }
+            "/api/tags" => {
+                let mut tags: HashMap<String, u32> = HashMap::new();
+                for (tag, count) in adr_repo::get_tags_popularity(base_path)? {
+                    *tags.entry(tag.trim().to_string()).or_default() += count;
+                }
+                Ok(Response::json(json!(tags)))
+            }
+            "/api/graph" => {
+                let adrs = adr_repo::list_all_adr(base_path)?;
+                let graph = adr_graph::build_graph(&adrs).filter(param("tag"), param("dir"));
+                Ok(Response {
+                    code: 200,
+                    content_type: "application/json",
+                    body: graph.to_json()?,
+                    etag: None,
+                })
+            }
+            "/api/search" => match param("q") {
+                Some(query) => self.search(query, param("limit").and_then(|l| l.parse().ok()).unwrap_or(100)),
+                None => Ok(Response::error(400, "The q parameter is missing")),
+            },
+            _ => match path.strip_prefix("/api/adrs/") {
+                Some(id_or_path) => self.get_adr(id_or_path),
+                None => Ok(Response::error(404, &format!("[{}] does not exist", path))),
+            },
+        }
+    }
+
+    /// Only the listed ADRs can be read, whatever the given path
+    fn get_adr(&self, id_or_path: &str) -> io::Result<Response> {
+        let adrs = adr_repo::list_all_adr(Path::new(&self.cfg.adr_src_dir))?;
+        let adr = match find(&adrs, id_or_path) {
+            Some(adr) => adr,
+            None => return Ok(Response::error(404, &format!("No ADR [{}]", id_or_path))),
+        };
+
+        //the references to the other ADRs open them in the UI
+        let links: HashMap<String, String> = adrs
+            .iter()
+            .map(|a| (a.file_name.clone(), format!("#adr={}", a.file_path)))
+            .collect();
+        let mut attributes = HashMap::new();
+        adr_export::asciidoc_to_html(&adr_template::get_status_labels(), &mut attributes, &HashMap::new(), "");
+        let html = adr_export::asciidoc_to_html(&adr.content, &mut attributes, &links, "");
+
+        let mut body = summary(adr);
+        body["experts"] = json!(adr.experts);
+        body["stakeholders"] = json!(adr.stakeholders);
+        body["outcome"] = json!(adr.outcome);
+        body["related"] = Value::from(
+            adr_graph::get_references(adr)
+                .iter()
+                .map(|(status, file)| json!({ "status": status.as_str(), "file": file }))
+                .collect::<Vec<Value>>(),
+        );
+        body["content"] = json!(adr.content);
+        body["html"] = json!(html);
+
+        Ok(Response::adr(200, adr, body))
+    }
+
+    /// `POST /api/adrs` creates a Decision Record, `POST /api/adrs/<id or path>/<transition>` transitions one
+    fn post(&self, request: &Request) -> io::Result<Response> {
+        //a cross-origin application/json request needs a preflight, which is never answered
+        let content_type = request.headers.get("content-type").map(|t| t.to_lowercase()).unwrap_or_default();
+        if content_type.split(';').next().unwrap_or_default().trim() != "application/json" {
+            return Ok(Response::error(415, "The Content-Type must be application/json"));
+        }
+        let body: Value = match request.body.trim().is_empty() {
+            true => json!({}),
+            false => match serde_json::from_str(&request.body) {
+                Ok(body) => body,
+                Err(why) => return Ok(Response::error(400, &format!("Invalid JSON body - {}", why))),
+            },
+        };
+
+        if request.path == "/api/adrs" {
+            return self.create(&body);
+        }
+        match request.path.strip_prefix("/api/adrs/").and_then(|p| p.rsplit_once('/')) {
+            Some((id_or_path, action)) => self.transition(id_or_path, action, request.headers.get("if-match"), &body),
+            None => Ok(Response::error(404, &format!("[POST {}] does not exist", request.path))),
+        }
+    }
+
+    /// Creates a Decision Record from `{"title", "path", "template", "tags", "deciders"}`, as `adr lf new` does
+    fn create(&self, body: &Value) -> io::Result<Response> {
+        let new_adr = crate::wizard::NewAdr {
+            title: get_str(body, "title").unwrap_or_default(),
+            template: get_str(body, "template"),
+            path: get_str(body, "path"),
+            tags: get_list(body, "tags"),
+            deciders: get_list(body, "deciders"),
+        };
+        if new_adr.title.is_empty() {
+            return Ok(Response::error(400, "The title is missing"));
+        }
+        if let Some(path) = &new_adr.path {
+            if Path::new(path).components().any(|c| !matches!(c, std::path::Component::Normal(_))) {
+                return Ok(Response::error(400, &format!("[{}] is not a sub directory of adr_src_dir", path)));
+            }
+        }
+
+        let _lock = self.writing.lock().unwrap();
+        let base_path = Path::new(&self.cfg.adr_src_dir);
+        let created = adr_repo::create_adr_from_template(
+            self.cfg.clone(),
+            new_adr.path.as_deref(),
+            &new_adr.title,
+            new_adr.template.as_deref(),
+            &new_adr.vars(),
+        )?;
+        let path = match created {
+            Some(path) => path,
+            None => return Ok(Response::error(409, &format!("[{}] already exists", new_adr.title))),
+        };
+        crate::commit_lifecycle(false, &format!("created \"{}\"", new_adr.title), std::slice::from_ref(&path))?;
+
+        let adr = adr_repo::build_adr(base_path, &path)?;
+        info!(crate::get_logger(), "[{}] created", adr.path());
+        Ok(Response::adr(201, &adr, summary(&adr)))
+    }
+
+    /// Runs the transition `action` (decided, superseded-by, completed-by or obsoleted, the other ADR being `{"by"}`),
+    /// if `If-Match` is the ETag of the Decision Record (and `{"by_etag"}`, if given, the one of the other ADR)
+    fn transition(&self, id_or_path: &str, action: &str, if_match: Option<&String>, body: &Value) -> io::Result<Response> {
+        let transition = match action {
+            "decided" => TransitionStatus::DECIDED,
+            "superseded-by" => TransitionStatus::SUPERSEDED,
+            "completed-by" => TransitionStatus::COMPLETED,
+            "obsoleted" => TransitionStatus::CANCELLED,
+            _ => return Ok(Response::error(404, &format!("[{}] is not a known transition", action))),
+        };
+        let if_match = match if_match {
+            Some(etag) => etag.trim_start_matches("W/"),
+            None => {
+                return Ok(Response::error(
+                    428,
+                    "The If-Match header (the ETag of the Decision Record) is required",
+                ))
+            }
+        };
+
+        let _lock = self.writing.lock().unwrap();
+        let base_path = Path::new(&self.cfg.adr_src_dir);
+        let adrs = adr_repo::list_all_adr(base_path)?;
+        let adr = match find(&adrs, id_or_path) {
+            Some(adr) => adr,
+            None => return Ok(Response::error(404, &format!("No ADR [{}]", id_or_path))),
+        };
+        if if_match != "*" && if_match != etag(&adr.content) {
+            let mut response = Response::error(409, &format!("[{}] has been modified since it was read", adr.file_path));
+            response.etag = Some(etag(&adr.content));
+            return Ok(response);
+        }
+
+        let by = match transition {
+            TransitionStatus::SUPERSEDED | TransitionStatus::COMPLETED => {
+                let by = match get_str(body, "by") {
+                    Some(by) => by,
+                    None => return Ok(Response::error(400, "The other Decision Record (by) is missing")),
+                };
+                let by = match find(&adrs, &by) {
+                    Some(by) => by,
+                    None => return Ok(Response::error(404, &format!("No ADR [{}]", by))),
+                };
+                if get_str(body, "by_etag").map(|e| e != etag(&by.content)).unwrap_or(false) {
+                    return Ok(Response::error(409, &format!("[{}] has been modified since it was read", by.file_path)));
+                }
+                Some(by)
+            }
+            _ => None,
+        };
+
+        let from_path = base_path.join(&adr.file_path);
+        let by_path = by.map(|by| base_path.join(&by.file_path));
+        let by_str = by_path.as_ref().map(|p| p.display().to_string()).unwrap_or_default();
+        if !adr_repo::transition_to(transition, base_path, &from_path.display().to_string(), &by_str)? {
+            let by = by.map(|by| format!(" by [{}] ({})", by.file_path, by.status.as_str())).unwrap_or_default();
+            return Ok(Response::error(
+                422,
+                &format!("[{}] ({}) cannot be transitioned to [{}]{}", adr.file_path, adr.status.as_str(), action, by),
+            ));
+        }
+
+        let message = match by {
+            Some(by) => format!("{} ADR-{}", action.replace('-', " "), by.file_id),
+            None => action.to_string(),
+        };
+        let mut files = vec![from_path.clone()];
+        files.extend(by_path);
+        crate::commit_lifecycle(false, &message, &files)?;
+
+        let adr = adr_repo::build_adr(base_path, &from_path)?;
+        Ok(Response::adr(200, &adr, summary(&adr)))
+    }
+
+    /// Full-text search (see `adr search`), the index is rebuilt first if the ADRs changed
+    fn search(&self, query: &str, limit: usize) -> io::Result<Response> {
+        let adrs = adr_repo::list_all_adr(Path::new(&self.cfg.adr_src_dir))?;
+        {
+            let mut indexed = self.indexed.lock().unwrap();
+            let current: Vec<(String, String)> = adrs.iter().map(|a| (a.path(), a.content.clone())).collect();
+            if *indexed != current {
+                std::fs::create_dir_all(&self.cfg.adr_search_index)?;
+                adr_search::search::build_index(self.cfg.adr_search_index.clone(), adrs.clone())
+                    .map_err(io::Error::other)?;
+                *indexed = current;
+            }
+        }
+
+        let results = match adr_search::search::search(self.cfg.adr_search_index.clone(), query.to_string(), limit) {
+            Ok(results) => results,
+            Err(why) => return Ok(Response::error(400, &why.to_string())),
+        };
+
+        let found: Vec<Value> = results
+            .iter()
+            .filter_map(|result| adrs.iter().find(|adr| adr.path() == result.path[0]))
+            .map(summary)
+            .collect();
+
+        Ok(Response::json(Value::from(found)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::sync::Mutex;
+
+    use adr_config::config::AdrToolConfig;
+    use adr_core::adr_repo::{self, Status};
+    use serde_json::{json, Value};
+    use tempdir::TempDir;
+
+    use std::collections::HashMap;
+
+    use super::{decode, etag, Request, Server};
+
+    fn get_server(src_dir: &str) -> Server {
+        Server {
+            cfg: AdrToolConfig {
+                adr_src_dir: src_dir.to_string(),
+                ..Default::default()
+            },
+            port: 8080,
+            indexed: Mutex::new(Vec::new()),
+            writing: Mutex::new(()),
+        }
+    }
+
+    fn post(headers: &[(&str, &str)]) -> Request {
+        Request {
+            method: String::from("POST"),
+            path: String::from("/api/adrs"),
+            params: HashMap::new(),
+            headers: headers.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect(),
+            body: String::from("{\"title\": \"Use Kafka\"}"),
+        }
+    }
+
+    #[test]
+    fn test_etag() {
+        assert_eq!(etag(""), "\"cbf29ce484222325\"");
+        assert_eq!(etag("== Use Kafka"), etag("== Use Kafka"));
+        assert_ne!(etag("== Use Kafka"), etag("== Use Kafka "));
+        assert_eq!(etag("== Use Kafka").len(), 18);
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode("use%20kafka+now", true), "use kafka now");
+        assert_eq!(decode("use%20kafka+now", false), "use kafka+now");
+        assert_eq!(decode("d%C3%A9cision", false), "décision");
+        //invalid or truncated sequences are kept
+        assert_eq!(decode("100%zz", false), "100%zz");
+        assert_eq!(decode("100%2", false), "100%2");
+        assert_eq!(decode("100%", false), "100%");
+    }
+
+    #[test]
+    fn test_transition() {
+        let src = TempDir::new("my_src_folder").unwrap();
+        for (file, title) in [("1-use-kafka.adoc", "Use Kafka"), ("2-use-pulsar.adoc", "Use Pulsar")] {
+            let content = format!("= {}\n\n*Status:* {{wip}}\n\n*Date:* 2024-01-02\n", title);
+            fs::write(src.path().join(file), content).unwrap();
+        }
+        let server = get_server(&format!("{}", src.path().display()));
+        let status = |id: &str| adr_repo::find_adr(src.path(), id).unwrap().status;
+        let current = |id: &str| etag(&adr_repo::find_adr(src.path(), id).unwrap().content);
+
+        //the ETag is required
+        let response = server.transition("1", "decided", None, &json!({})).unwrap();
+        assert_eq!(response.code, 428);
+
+        //stale ETag, the current one is returned
+        let response = server.transition("1", "decided", Some(&String::from("\"0\"")), &json!({})).unwrap();
+        assert_eq!(response.code, 409);
+        assert_eq!(response.etag, Some(current("1")));
+        assert_eq!(status("1"), Status::WIP);
+
+        //whatever the ETag
+        let response = server.transition("1", "decided", Some(&String::from("*")), &json!({})).unwrap();
+        assert_eq!(response.code, 200);
+        assert_eq!(status("1"), Status::DECIDED);
+        assert_eq!(response.etag, Some(current("1")));
+        let body: Value = serde_json::from_str(&response.body).unwrap();
+        assert_eq!(body["status"], "decided");
+
+        let response = server.transition("2", "decided", Some(&current("2")), &json!({})).unwrap();
+        assert_eq!(response.code, 200);
+
+        //the other Decision Record has been modified since it was read
+        let stale = json!({ "by": "2", "by_etag": "\"0\"" });
+        let response = server.transition("1", "superseded-by", Some(&current("1")), &stale).unwrap();
+        assert_eq!(response.code, 409);
+        assert_eq!(status("1"), Status::DECIDED);
+        assert_eq!(status("2"), Status::DECIDED);
+
+        let by = json!({ "by": "2", "by_etag": current("2") });
+        let response = server.transition("1", "superseded-by", Some(&format!("W/{}", current("1"))), &by).unwrap();
+        assert_eq!(response.code, 200);
+        assert_eq!(status("1"), Status::SUPERSEDED);
+        assert_eq!(status("2"), Status::SUPERSEDES);
+    }
+
+    #[test]
+    fn test_cross_origin_requests() {
+        let src = TempDir::new("my_src_folder").unwrap();
+        let server = get_server(&format!("{}", src.path().display()));
+
+        //a simple request, sent without preflight by any web page
+        let response = server.answer(&post(&[("host", "127.0.0.1:8080"), ("content-type", "text/plain")]));
+        assert_eq!(response.code, 415);
+        let response = server.answer(&post(&[("host", "127.0.0.1:8080")]));
+        assert_eq!(response.code, 415);
+        let json = ("content-type", "application/json; charset=utf-8");
+        let response = server.answer(&post(&[("host", "127.0.0.1:8080"), ("origin", "https://evil.com"), json]));
+        assert_eq!(response.code, 403);
+        //DNS rebinding
+        let response = server.answer(&post(&[("host", "evil.com:8080"), json]));
+        assert_eq!(response.code, 403);
+        let response = server.answer(&post(&[("host", "localhost:8081"), json]));
+        assert_eq!(response.code, 403);
+        assert_eq!(fs::read_dir(src.path()).unwrap().count(), 0);
+
+        //accepted, then refused by the transition itself
+        let mut request = post(&[("host", "localhost:8080"), ("origin", "http://localhost:8080"), json]);
+        request.path = String::from("/api/adrs/1/decided");
+        assert_eq!(server.answer(&request).code, 428);
+    }
+}
@@ -0,0 +1,556 @@
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Cell, Paragraph, Row, Table, TableState, Wrap};
+use ratatui::Frame;
+
+use adr_config::config::AdrToolConfig;
+use adr_core::adr_repo::{self, Adr, TransitionStatus};
+
+const HELP: &str = "↑↓ move  / search  s status  t tag  c clear  d decided  S superseded-by  C completed-by  o obsoleted  e edit  J/K scroll  r reload  q quit";
+
+/// What the bottom line is asking for
+#[derive(Debug, PartialEq, Copy, Clone)]
+enum Input {
+    Search,
+    /// y/n before a transition of the selected ADR alone
+    Confirm(TransitionStatus),
+    /// the id or path of the other ADR of the transition
+    By(TransitionStatus),
+}
+
+struct App {
+    cfg: AdrToolConfig,
+    adrs: Vec<Adr>,
+    statuses: Vec<&'static str>,
+    tags: Vec<String>,
+    status_filter: Option<usize>,
+    tag_filter: Option<usize>,
+    query: String,
+    /// the paths found by the full-text search, None without query
+    found: Option<Vec<String>>,
+    /// false when the search index has to be built again
+    indexed: bool,
+    /// the indexes (in adrs) of the listed ADRs
+    visible: Vec<usize>,
+    state: TableState,
+    scroll: u16,
+    input: Option<Input>,
+    buffer: String,
+    message: String,
+    /// the logs of the library and the editor write to the terminal, it has to be redrawn
+    redraw: bool,
+}
+
+/// Converts the colours of `get_cell_style`
+fn to_color(color: comfy_table::Color) -> Color {
+    match color {
+        comfy_table::Color::DarkYellow => Color::Yellow,
+        comfy_table::Color::DarkGreen => Color::Green,
+        comfy_table::Color::Green => Color::LightGreen,
+        comfy_table::Color::DarkRed => Color::Red,
+        _ => Color::Reset,
+    }
+}
+
+fn status_color(adr: &Adr) -> Color {
+    to_color(crate::get_cell_style(adr.status))
+}
+
+/// A plain text rendering of the AsciiDoc content: no attribute entries nor comments, bold titles, and the
+/// attribute references (e.g. the status labels) replaced by their text
+fn preview(adr: &Adr) -> Vec<Line<'static>> {
+    lazy_static! {
+        static ref RE_ATTRIBUTE: Regex = Regex::new(r"^:([\w-]+):\s*(.*)$").unwrap();
+        static ref RE_REFERENCE: Regex = Regex::new(r"\{([\w-]+)\}").unwrap();
+        static ref RE_LABEL: Regex = Regex::new(r"#([^#]+)#\]?$").unwrap();
+        static ref RE_ROLE: Regex = Regex::new(r"\[[\w.-]+\]#([^#]*)#").unwrap();
+        static ref RE_HEADING: Regex = Regex::new(r"^(=+) (.*)$").unwrap();
+    }
+
+    let mut attributes = std::collections::HashMap::new();
+    for line in adr.content.lines() {
+        if let Some(cap) = RE_ATTRIBUTE.captures(line) {
+            let value = cap[2].trim();
+            let value = match RE_LABEL.captures(value) {
+                Some(label) => label[1].to_string(),
+                None => value.to_string(),
+            };
+            attributes.insert(cap[1].to_string(), value);
+        }
+    }
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::raw("Status: "),
+            Span::styled(adr.status.as_str(), Style::new().fg(status_color(adr)).bold()),
+            Span::raw(format!("  Date: {}", adr.date)),
+        ]),
+        Line::from(format!("File: {}", adr.path())),
+        Line::from(format!("Tags: {}", adr.tags)).italic(),
+        Line::from(format!("Decision Makers: {}", adr.deciders.join(", "))),
+        Line::from(""),
+    ];
+    let mut blank = false;
+    for line in adr.content.lines() {
+        let trimmed = line.trim_start();
+        if RE_ATTRIBUTE.is_match(line) || trimmed.starts_with("//") || trimmed.starts_with("include::") {
+            continue;
+        }
+        //no more than one blank line
+        if line.trim().is_empty() {
+            if !blank && lines.len() > 5 {
+                lines.push(Line::from(""));
+            }
+            blank = true;
+            continue;
+        }
+        blank = false;
+
+        let text = RE_REFERENCE.replace_all(line, |cap: &regex::Captures| {
+            attributes.get(&cap[1]).cloned().unwrap_or_else(|| cap[0].to_string())
+        });
+        let text = RE_ROLE.replace_all(&text, "$1").to_string();
+        match RE_HEADING.captures(&text) {
+            Some(cap) if cap[1].len() <= 2 => lines.push(Line::from(cap[2].to_string()).bold().fg(status_color(adr))),
+            Some(cap) => lines.push(Line::from(cap[2].to_string()).bold().underlined()),
+            None => lines.push(Line::from(text)),
+        }
+    }
+
+    lines
+}
+
+impl App {
+    fn new(cfg: AdrToolConfig) -> io::Result<App> {
+        let mut app = App {
+            cfg,
+            adrs: Vec::new(),
+            statuses: Vec::new(),
+            tags: Vec::new(),
+            status_filter: None,
+            tag_filter: None,
+            query: String::new(),
+            found: None,
+            indexed: false,
+            visible: Vec::new(),
+            state: TableState::default().with_selected(Some(0)),
+            scroll: 0,
+            input: None,
+            buffer: String::new(),
+            message: String::new(),
+            redraw: false,
+        };
+        app.reload()?;
+
+        Ok(app)
+    }
+
+    /// Reads the Decision Records again, the selection and the filters are kept when possible
+    fn reload(&mut self) -> io::Result<()> {
+        let selected = self.selected().map(|adr| adr.path());
+        let status = self.status_filter.map(|i| self.statuses[i]);
+        let tag = self.tag_filter.map(|i| self.tags[i].clone());
+
+        self.adrs = adr_repo::list_all_adr(Path::new(&self.cfg.adr_src_dir))?;
+        self.statuses = self.adrs.iter().map(|adr| adr.status.as_str()).collect();
+        self.statuses.sort();
+        self.statuses.dedup();
+        self.tags = self
+            .adrs
+            .iter()
+            .flat_map(|adr| adr.tags_array.iter().map(|t| t.trim().to_string()))
+            .filter(|t| !t.is_empty())
+            .collect();
+        self.tags.sort();
+        self.tags.dedup();
+        self.status_filter = status.and_then(|s| self.statuses.iter().position(|v| *v == s));
+        self.tag_filter = tag.and_then(|t| self.tags.iter().position(|v| *v == t));
+        self.indexed = false;
+        if !self.query.is_empty() {
+            self.search();
+        }
+
+        self.filter();
+        if let Some(path) = selected {
+            if let Some(position) = self.visible.iter().position(|i| self.adrs[*i].path() == path) {
+                self.state.select(Some(position));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn filter(&mut self) {
+        let status = self.status_filter.map(|i| self.statuses[i]);
+        let tag = self.tag_filter.map(|i| self.tags[i].as_str());
+        self.visible = (0..self.adrs.len())
+            .filter(|i| {
+                let adr = &self.adrs[*i];
+                status.map(|s| adr.status.as_str() == s).unwrap_or(true)
+                    && tag.map(|t| adr.tags_array.iter().any(|a| a.trim() == t)).unwrap_or(true)
+                    && self.found.as_ref().map(|f| f.contains(&adr.path())).unwrap_or(true)
+            })
+            .collect();
+        self.select(self.state.selected().unwrap_or(0) as isize);
+    }
+
+    /// Full-text search (see `adr search`), the index is built the first time. An incomplete query keeps
+    /// the previous results
+    fn search(&mut self) {
+        if self.query.trim().is_empty() {
+            self.found = None;
+            return;
+        }
+        if !self.indexed {
+            let built = std::fs::create_dir_all(&self.cfg.adr_search_index).and_then(|_| {
+                adr_search::search::build_index(self.cfg.adr_search_index.clone(), self.adrs.clone())
+                    .map_err(io::Error::other)
+            });
+            self.redraw = true;
+            if let Err(why) = built {
+                self.message = format!("Cannot build the search index - {}", why);
+                return;
+            }
+            self.indexed = true;
+        }
+
+        match adr_search::search::search(self.cfg.adr_search_index.clone(), self.query.clone(), self.adrs.len().max(1)) {
+            Ok(results) => {
+                self.found = Some(results.into_iter().map(|r| r.path[0].clone()).collect());
+                self.message.clear();
+            }
+            Err(why) => self.message = why.to_string(),
+        }
+    }
+
+    fn selected(&self) -> Option<&Adr> {
+        self.state.selected().and_then(|i| self.visible.get(i)).map(|i| &self.adrs[*i])
+    }
+
+    fn select(&mut self, position: isize) {
+        let last = self.visible.len() as isize - 1;
+        let position = position.min(last).max(0) as usize;
+        if self.state.selected() != Some(position) {
+            self.scroll = 0;
+        }
+        self.state.select(Some(position));
+    }
+
+    fn cycle(filter: Option<usize>, len: usize) -> Option<usize> {
+        match filter {
+            None if len > 0 => Some(0),
+            Some(i) if i + 1 < len => Some(i + 1),
+            _ => None,
+        }
+    }
+
+    /// Runs the transition of the selected ADR, as the `adr lf` commands do (auto_commit included)
+    fn transition(&mut self, transition: TransitionStatus, by: Option<&str>) -> io::Result<()> {
+        let adr = match self.selected() {
+            Some(adr) => adr.clone(),
+            None => return Ok(()),
+        };
+        let by = match by {
+            Some(by) => {
+                let by = by.trim();
+                let found = match by.parse::<usize>() {
+                    Ok(id) => self.adrs.iter().find(|adr| adr.file_id == id),
+                    Err(_) => self.adrs.iter().find(|adr| adr.file_path == by || adr.path() == by),
+                };
+                match found {
+                    Some(found) => Some(found.clone()),
+                    None => {
+                        self.message = format!("No ADR [{}]", by);
+                        return Ok(());
+                    }
+                }
+            }
+            None => None,
+        };
+
+        let action = match (transition, &by) {
+            (TransitionStatus::SUPERSEDED, Some(by)) => format!("superseded by ADR-{}", by.file_id),
+            (TransitionStatus::COMPLETED, Some(by)) => format!("completed by ADR-{}", by.file_id),
+            (TransitionStatus::CANCELLED, _) => String::from("obsoleted"),
+            _ => String::from("decided"),
+        };
+        let base_path = Path::new(&self.cfg.adr_src_dir);
+        let by_path = by.as_ref().map(|by| by.path()).unwrap_or_default();
+        self.redraw = true;
+        let transitioned = match adr_repo::transition_to(transition, base_path, &adr.path(), &by_path) {
+            Ok(transitioned) => transitioned,
+            Err(why) => {
+                self.report_failure(format!("[{}] cannot be {} - {}", adr.file_path, action, why));
+                return Ok(());
+            }
+        };
+        if !transitioned {
+            self.message = match &by {
+                Some(by) => format!(
+                    "[{}] ({}) cannot be {} - [{}] is {}",
+                    adr.file_path,
+                    adr.status.as_str(),
+                    action,
+                    by.file_path,
+                    by.status.as_str()
+                ),
+                None => format!("[{}] ({}) cannot be {}", adr.file_path, adr.status.as_str(), action),
+            };
+            return Ok(());
+        }
+
+        let mut files = vec![PathBuf::from(adr.path())];
+        if let Some(by) = &by {
+            files.push(PathBuf::from(by.path()));
+        }
+        //the files have already been changed, e.g. no git identity or a failing hook
+        if let Err(why) = crate::commit_lifecycle(false, &action, &files) {
+            self.report_failure(format!("[{}] {} but not committed - {}", adr.file_path, action, why));
+            return Ok(());
+        }
+        self.reload()?;
+        self.message = format!("[{}] {}", adr.file_path, action);
+
+        Ok(())
+    }
+
+    /// Reports the failure of an action in the status line, instead of quitting, once the list matches the files again
+    fn report_failure(&mut self, message: String) {
+        self.message = match self.reload() {
+            Ok(()) => message,
+            Err(why) => format!("{} (unable to reload the ADRs - {})", message, why),
+        };
+    }
+
+    /// Opens the selected ADR in the editor, outside of the full-screen mode
+    fn edit(&mut self) -> io::Result<()> {
+        let path = match self.selected() {
+            Some(adr) => adr.path(),
+            None => return Ok(()),
+        };
+
+        terminal::disable_raw_mode()?;
+        execute!(io::stdout(), LeaveAlternateScreen)?;
+        let edited = crate::editor::edit(Path::new(&self.cfg.adr_src_dir), Path::new(&path));
+        execute!(io::stdout(), EnterAlternateScreen)?;
+        terminal::enable_raw_mode()?;
+        self.redraw = true;
+
+        self.reload()?;
+        self.message = match edited {
+            Ok(true) => format!("[{}] edited", path),
+            Ok(false) => format!("[{}] cannot be fully parsed", path),
+            Err(why) => format!("Cannot edit [{}] - {}", path, why),
+        };
+
+        Ok(())
+    }
+
+    fn on_input(&mut self, input: Input, key: KeyEvent) -> io::Result<()> {
+        match (input, key.code) {
+            (Input::Search, KeyCode::Esc) => {
+                self.query.clear();
+                self.found = None;
+                self.input = None;
+                self.message.clear();
+                self.filter();
+            }
+            (Input::Search, KeyCode::Enter) => self.input = None,
+            (Input::Search, KeyCode::Backspace) => {
+                self.query.pop();
+                self.search();
+                self.filter();
+            }
+            (Input::Search, KeyCode::Char(c)) => {
+                self.query.push(c);
+                self.search();
+                self.filter();
+            }
+            (Input::Confirm(transition), KeyCode::Char('y')) => {
+                self.input = None;
+                self.transition(transition, None)?;
+            }
+            (Input::Confirm(_), _) => self.input = None,
+            (Input::By(_), KeyCode::Esc) => self.input = None,
+            (Input::By(_), KeyCode::Backspace) => {
+                self.buffer.pop();
+            }
+            (Input::By(_), KeyCode::Char(c)) => self.buffer.push(c),
+            (Input::By(transition), KeyCode::Enter) => {
+                self.input = None;
+                let by = std::mem::take(&mut self.buffer);
+                if !by.trim().is_empty() {
+                    self.transition(transition, Some(&by))?;
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Returns false to quit
+    fn on_key(&mut self, key: KeyEvent) -> io::Result<bool> {
+        if let Some(input) = self.input {
+            self.on_input(input, key)?;
+            return Ok(true);
+        }
+
+        let position = self.state.selected().unwrap_or(0) as isize;
+        match key.code {
+            KeyCode::Char('q') => return Ok(false),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(false),
+            KeyCode::Down | KeyCode::Char('j') => self.select(position + 1),
+            KeyCode::Up | KeyCode::Char('k') => self.select(position - 1),
+            KeyCode::PageDown => self.select(position + 10),
+            KeyCode::PageUp => self.select(position - 10),
+            KeyCode::Home | KeyCode::Char('g') => self.select(0),
+            KeyCode::End | KeyCode::Char('G') => self.select(self.visible.len() as isize),
+            KeyCode::Char('J') => self.scroll = self.scroll.saturating_add(5),
+            KeyCode::Char('K') => self.scroll = self.scroll.saturating_sub(5),
+            KeyCode::Char('/') => {
+                self.input = Some(Input::Search);
+                self.message.clear();
+            }
+            KeyCode::Char('s') => {
+                self.status_filter = App::cycle(self.status_filter, self.statuses.len());
+                self.filter();
+            }
+            KeyCode::Char('t') => {
+                self.tag_filter = App::cycle(self.tag_filter, self.tags.len());
+                self.filter();
+            }
+            KeyCode::Char('c') | KeyCode::Esc => {
+                self.status_filter = None;
+                self.tag_filter = None;
+                self.query.clear();
+                self.found = None;
+                self.message.clear();
+                self.filter();
+            }
+            KeyCode::Char('d') if self.selected().is_some() => self.input = Some(Input::Confirm(TransitionStatus::DECIDED)),
+            KeyCode::Char('o') if self.selected().is_some() => {
+                self.input = Some(Input::Confirm(TransitionStatus::CANCELLED))
+            }
+            KeyCode::Char('S') if self.selected().is_some() => self.input = Some(Input::By(TransitionStatus::SUPERSEDED)),
+            KeyCode::Char('C') if self.selected().is_some() => self.input = Some(Input::By(TransitionStatus::COMPLETED)),
+            KeyCode::Char('e') => self.edit()?,
+            KeyCode::Char('r') => {
+                self.reload()?;
+                self.message = format!("{} Decision Record(s) read", self.adrs.len());
+            }
+            _ => {}
+        }
+
+        Ok(true)
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, status_line, bottom_line] =
+            Layout::vertical([Constraint::Min(3), Constraint::Length(1), Constraint::Length(1)]).areas(frame.area());
+        let [list, preview_area] =
+            Layout::horizontal([Constraint::Percentage(45), Constraint::Percentage(55)]).areas(main);
+
+        let rows: Vec<Row> = self
+            .visible
+            .iter()
+            .map(|i| {
+                let adr = &self.adrs[*i];
+                let style = Style::new().fg(status_color(adr));
+                Row::new(vec![
+                    Cell::from(adr.file_id.to_string()),
+                    Cell::from(adr.title.clone()).style(style),
+                    Cell::from(adr.status.as_str()).style(style),
+                    Cell::from(adr.date.clone()),
+                ])
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [Constraint::Length(6), Constraint::Fill(1), Constraint::Length(10), Constraint::Length(10)],
+        )
+        .header(Row::new(vec!["ID", "Title", "Status", "Date"]).bold())
+        .block(Block::bordered().title(format!(" Decision Records {}/{} ", self.visible.len(), self.adrs.len())))
+        .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(table, list, &mut self.state);
+
+        let (title, lines) = match self.selected() {
+            Some(adr) => (format!(" {} ", adr.title), preview(adr)),
+            None => (String::from(" No Decision Record "), Vec::new()),
+        };
+        let preview = Paragraph::new(lines)
+            .block(Block::bordered().title(title))
+            .wrap(Wrap { trim: false })
+            .scroll((self.scroll, 0));
+        frame.render_widget(preview, preview_area);
+
+        let mut filters = Vec::new();
+        if let Some(i) = self.status_filter {
+            filters.push(format!("status: {}", self.statuses[i]));
+        }
+        if let Some(i) = self.tag_filter {
+            filters.push(format!("tag: {}", self.tags[i]));
+        }
+        if !self.query.is_empty() {
+            filters.push(format!("search: {}", self.query));
+        }
+        let mut status = filters.join("  ");
+        if !self.message.is_empty() {
+            status = match status.is_empty() {
+                true => self.message.clone(),
+                false => format!("{}  |  {}", status, self.message),
+            };
+        }
+        frame.render_widget(Paragraph::new(status).italic(), status_line);
+
+        let file = self.selected().map(|adr| adr.file_path.clone()).unwrap_or_default();
+        let bottom = match self.input {
+            Some(Input::Search) => format!("/{}", self.query),
+            Some(Input::Confirm(TransitionStatus::CANCELLED)) => format!("Obsolete [{}]? (y/n)", file),
+            Some(Input::Confirm(_)) => format!("Decide [{}]? (y/n)", file),
+            Some(Input::By(TransitionStatus::SUPERSEDED)) => {
+                format!("[{}] superseded by (id or path, Esc to cancel): {}", file, self.buffer)
+            }
+            Some(Input::By(_)) => format!("[{}] completed by (id or path, Esc to cancel): {}", file, self.buffer),
+            None => String::from(HELP),
+        };
+        frame.render_widget(Paragraph::new(bottom).bold(), bottom_line);
+    }
+}
+
+/// Browses the Decision Records in full screen until `q` is pressed
+pub fn run(cfg: AdrToolConfig) -> io::Result<()> {
+    if !io::stdout().is_terminal() {
+        return Err(io::Error::other("adr tui needs a terminal"));
+    }
+
+    let mut app = App::new(cfg)?;
+    let mut terminal = ratatui::init();
+    let result = (|| -> io::Result<()> {
+        loop {
+            if app.redraw {
+                terminal.clear()?;
+                app.redraw = false;
+            }
+            terminal.draw(|frame| app.draw(frame))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press && !app.on_key(key)? {
+                    return Ok(());
+                }
+            }
+        }
+    })();
+    ratatui::restore();
+
+    result
+}
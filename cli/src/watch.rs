@@ -0,0 +1,161 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use slog::{debug, warn};
+
+use notify::event::ModifyKind;
+use notify::{EventKind, RecursiveMode, Watcher};
+
+use adr_config::config::AdrToolConfig;
+use adr_core::adr_repo::{self, Adr};
+use adr_core::{adr_export, adr_graph};
+
+/// The changes are handled once no file has been modified for this delay
+const DEBOUNCE_DELAY: Duration = Duration::from_millis(500);
+
+/// The exported artefacts to regenerate after each change
+pub struct Outputs {
+    /// the output directory of `adr export site`
+    pub site: Option<String>,
+    /// the file of `adr graph`, the format is given by the extension (`.mmd`, `.json`, DOT otherwise)
+    pub graph: Option<String>,
+}
+
+fn write_graph(adrs: &[Adr], file: &str) -> io::Result<()> {
+    let graph = adr_graph::build_graph(adrs);
+    let content = match Path::new(file).extension().and_then(|ext| ext.to_str()) {
+        Some("mmd") | Some("mermaid") => graph.to_mermaid(),
+        Some("json") => graph.to_json()?,
+        _ => graph.to_dot(),
+    };
+
+    fs::write(file, content)
+}
+
+fn regenerate(cfg: &AdrToolConfig, adrs: &BTreeMap<String, Adr>, outputs: &Outputs) {
+    if let Some(out_dir) = &outputs.site {
+        match adr_export::export_site(cfg, Path::new(out_dir)) {
+            Ok(files) => println!("  site: {} file(s) written in [{}]", files.len(), out_dir),
+            Err(why) => eprintln!("  site: cannot export [{}] - {}", out_dir, why),
+        }
+    }
+    if let Some(file) = &outputs.graph {
+        let adrs: Vec<Adr> = adrs.values().cloned().collect();
+        match write_graph(&adrs, file) {
+            Ok(_) => println!("  graph: [{}] written", file),
+            Err(why) => eprintln!("  graph: cannot write [{}] - {}", file, why),
+        }
+    }
+}
+
+/// Watches `adr_src_dir` recursively: the created, modified, renamed and deleted Decision Records are parsed again
+/// and updated in the search index, then the `outputs` are regenerated. Runs until the process is stopped
+pub fn watch(cfg: AdrToolConfig, outputs: Outputs) -> io::Result<()> {
+    let base_path = PathBuf::from(&cfg.adr_src_dir);
+    if !base_path.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("[{}] is not a directory", base_path.display()),
+        ));
+    }
+    //the events may give the canonical paths
+    let watched = base_path.canonicalize()?;
+
+    //by file path, relative to adr_src_dir
+    let mut adrs: BTreeMap<String, Adr> = adr_repo::list_all_adr(&base_path)?
+        .into_iter()
+        .map(|adr| (adr.file_path.clone(), adr))
+        .collect();
+    fs::create_dir_all(&cfg.adr_search_index)?;
+    adr_search::search::build_index(cfg.adr_search_index.clone(), adrs.values().cloned().collect())
+        .map_err(io::Error::other)?;
+    regenerate(&cfg, &adrs, &outputs);
+
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender).map_err(io::Error::other)?;
+    watcher
+        .watch(&watched, RecursiveMode::Recursive)
+        .map_err(io::Error::other)?;
+    println!(
+        "Watching [{}] - {} Decision Record(s) indexed, press Ctrl+C to stop",
+        base_path.display(),
+        adrs.len()
+    );
+
+    while let Ok(first) = receiver.recv() {
+        let mut events = vec![first];
+        while let Ok(event) = receiver.recv_timeout(DEBOUNCE_DELAY) {
+            events.push(event);
+        }
+
+        let mut files: Vec<PathBuf> = events
+            .into_iter()
+            .filter_map(|result| match result {
+                Ok(event) => Some(event),
+                Err(why) => {
+                    warn!(crate::get_logger(), "Got error [{:?}] while watching [{}]", why, base_path.display());
+                    None
+                }
+            })
+            //reading the files (e.g. to export them) gives access events
+            .filter(|event| match event.kind {
+                EventKind::Modify(ModifyKind::Metadata(_)) => false,
+                EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_) => true,
+                _ => false,
+            })
+            .flat_map(|event| event.paths)
+            .filter_map(|path| {
+                let relative = path.strip_prefix(&watched).or_else(|_| path.strip_prefix(&base_path)).ok()?;
+                Some(base_path.join(relative))
+            })
+            .filter(|path| adr_repo::is_adr_file(&base_path, path))
+            .collect();
+        files.sort();
+        files.dedup();
+        if files.is_empty() {
+            continue;
+        }
+        debug!(crate::get_logger(), "[{}] file(s) changed", files.len());
+
+        let mut updated = Vec::new();
+        let mut removed = Vec::new();
+        for file in files {
+            if file.is_file() {
+                let adr = match adr_repo::build_adr(&base_path, &file) {
+                    Ok(adr) => adr,
+                    Err(why) => {
+                        eprintln!("cannot read [{}] - {}", file.display(), why);
+                        continue;
+                    }
+                };
+                let action = match adrs.contains_key(&adr.file_path) {
+                    true => "modified",
+                    false => "created",
+                };
+                println!("{} [{}] {} ({})", action, adr.file_path, adr.title, adr.status.as_str());
+                adrs.insert(adr.file_path.clone(), adr.clone());
+                updated.push(adr);
+            } else {
+                let file_path = file.strip_prefix(&base_path).unwrap_or(&file).display().to_string();
+                if let Some(adr) = adrs.remove(&file_path) {
+                    println!("deleted [{}] {}", adr.file_path, adr.title);
+                    removed.push(adr.path());
+                }
+            }
+        }
+        if updated.is_empty() && removed.is_empty() {
+            continue;
+        }
+
+        if let Err(why) = adr_search::search::update_index(cfg.adr_search_index.clone(), updated, removed) {
+            eprintln!("  index: cannot be updated - {}", why);
+        }
+        regenerate(&cfg, &adrs, &outputs);
+    }
+
+    Ok(())
+}
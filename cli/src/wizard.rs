@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::io::{self};
+use std::path::Path;
+
+use dialoguer::theme::ColorfulTheme;
+use dialoguer::{Completion, Input, MultiSelect, Select};
+
+use adr_config::config::AdrToolConfig;
+use adr_core::adr_template;
+
+/// What is needed to create a new Decision Record, given as flags and/or asked by the wizard
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct NewAdr {
+    pub title: String,
+    pub template: Option<String>,
+    pub path: Option<String>,
+    pub tags: Vec<String>,
+    pub deciders: Vec<String>,
+}
+
+impl NewAdr {
+    /// The template variables of the new Decision Record (deciders default to the author if not specified)
+    pub fn vars(&self) -> HashMap<String, String> {
+        let mut vars = HashMap::new();
+        vars.insert(adr_template::VAR_TAGS.to_string(), self.tags.join(", "));
+        if !self.deciders.is_empty() {
+            vars.insert(adr_template::VAR_DECIDERS.to_string(), self.deciders.join(", "));
+        }
+
+        vars
+    }
+}
+
+/// Splits a comma separated list, e.g. `--tags "security, deployment view"`
+pub fn split_list(val: &str) -> Vec<String> {
+    val.split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// Tab completion on the existing sub directories of `adr_src_dir`
+struct DirectoryCompletion {
+    directories: Vec<String>,
+}
+
+impl Completion for DirectoryCompletion {
+    fn get(&self, input: &str) -> Option<String> {
+        self.directories
+            .iter()
+            .find(|dir| dir.starts_with(input) && dir.as_str() != input)
+            .cloned()
+    }
+}
+
+/// dialoguer prompts only fail on IO errors
+pub fn to_io_error(why: dialoguer::Error) -> io::Error {
+    match why {
+        dialoguer::Error::IO(why) => why,
+    }
+}
+
+/// Asks for the title, template, nested directory, tags and deciders of a new Decision Record.
+/// The values of `defaults` (e.g. given as flags) are proposed as initial answers.
+pub fn prompt(cfg: &AdrToolConfig, defaults: NewAdr) -> io::Result<NewAdr> {
+    let theme = ColorfulTheme::default();
+    let src_dir = Path::new(&cfg.adr_src_dir);
+
+    let title: String = Input::with_theme(&theme)
+        .with_prompt("Title")
+        .with_initial_text(defaults.title.as_str())
+        .validate_with(|input: &String| match input.trim().is_empty() {
+            true => Err("The title cannot be empty"),
+            false => Ok(()),
+        })
+        .interact_text()
+        .map_err(to_io_error)?;
+
+    let names: Vec<String> = adr_template::list_templates(cfg)?
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+    let mut items = vec![format!("{} (default)", cfg.adr_template_file)];
+    items.extend(names.iter().cloned());
+    let selected = defaults
+        .template
+        .as_ref()
+        .and_then(|name| names.iter().position(|n| n == name.trim_end_matches(".adoc")))
+        .map(|index| index + 1)
+        .unwrap_or(0);
+    let template = match Select::with_theme(&theme)
+        .with_prompt("Template")
+        .items(&items)
+        .default(selected)
+        .interact()
+        .map_err(to_io_error)?
+    {
+        0 => None,
+        index => Some(names[index - 1].clone()),
+    };
+
+    let completion = DirectoryCompletion {
+        directories: adr_core::adr_repo::list_sub_directories(src_dir)?,
+    };
+    let path: String = Input::with_theme(&theme)
+        .with_prompt("Nested directory (Tab to complete, empty for none)")
+        .with_initial_text(defaults.path.clone().unwrap_or_default())
+        .completion_with(&completion)
+        .allow_empty(true)
+        .interact_text()
+        .map_err(to_io_error)?;
+    let path = match path.trim().trim_matches('/') {
+        "" => None,
+        val => Some(val.to_string()),
+    };
+
+    let mut popularity: Vec<(String, u32)> = adr_core::adr_repo::get_tags_popularity(src_dir)?
+        .into_iter()
+        .map(|(tag, count)| (tag.trim().to_string(), count))
+        .collect();
+    popularity.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    let mut tags = Vec::new();
+    if !popularity.is_empty() {
+        let items: Vec<String> = popularity
+            .iter()
+            .map(|(tag, count)| format!("{} ({})", tag, count))
+            .collect();
+        let checked: Vec<bool> = popularity
+            .iter()
+            .map(|(tag, _)| defaults.tags.contains(tag))
+            .collect();
+        for index in MultiSelect::with_theme(&theme)
+            .with_prompt("Tags (Space to select, Enter to confirm)")
+            .items(&items)
+            .defaults(&checked)
+            .interact()
+            .map_err(to_io_error)?
+        {
+            tags.push(popularity[index].0.clone());
+        }
+    }
+    //the popular ones are selected above, an unticked tag is not proposed again
+    let other_tags: Vec<String> = defaults
+        .tags
+        .iter()
+        .filter(|tag| !popularity.iter().any(|(popular, _)| popular == *tag))
+        .cloned()
+        .collect();
+    let other_tags: String = Input::with_theme(&theme)
+        .with_prompt("Other tags (comma separated)")
+        .with_initial_text(other_tags.join(", "))
+        .allow_empty(true)
+        .interact_text()
+        .map_err(to_io_error)?;
+    for tag in split_list(&other_tags) {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+
+    let deciders = match defaults.deciders.is_empty() {
+        true => adr_template::get_author(),
+        false => defaults.deciders.join(", "),
+    };
+    let deciders: String = Input::with_theme(&theme)
+        .with_prompt("Decision Makers (comma separated)")
+        .with_initial_text(deciders)
+        .allow_empty(true)
+        .interact_text()
+        .map_err(to_io_error)?;
+
+    Ok(NewAdr {
+        title: title.trim().to_string(),
+        template,
+        path,
+        tags,
+        deciders: split_list(&deciders),
+    })
+}